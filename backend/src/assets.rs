@@ -0,0 +1,144 @@
+// backend/src/assets.rs
+// Chargement des overlays à leur taille finale : SVG rendu en vectoriel (resvg),
+// PNG @2x préféré quand l'image doit être agrandie
+use anyhow::{anyhow, Context, Result};
+use image::{imageops, DynamicImage, RgbaImage};
+use resvg::{tiny_skia, usvg};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use tracing::debug;
+
+/// Polices disponibles pour les textes des SVG (système + dossier `fonts` des overlays)
+static FONT_DB: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+
+/// Taille voulue pour un overlay
+#[derive(Debug, Clone, Copy)]
+pub enum Fit {
+    /// Hauteur imposée, largeur proportionnelle (badges)
+    Height(u32),
+    /// Largeur imposée, hauteur proportionnelle (gradients)
+    Width(u32),
+    /// Dimensions imposées (bordures pleine page)
+    Exact(u32, u32),
+}
+
+impl Fit {
+    fn dimensions(self, width: f32, height: f32) -> (u32, u32) {
+        let (w, h) = match self {
+            Fit::Height(h) => (width * h as f32 / height, h as f32),
+            Fit::Width(w) => (w as f32, height * w as f32 / width),
+            Fit::Exact(w, h) => (w as f32, h as f32),
+        };
+        (w.round().max(1.0) as u32, h.round().max(1.0) as u32)
+    }
+
+    fn height(self, width: u32, height: u32) -> u32 {
+        self.dimensions(width as f32, height as f32).1
+    }
+}
+
+/// Fichier à utiliser pour `path` : la variante `.svg` si elle existe, sinon le fichier lui-même
+pub fn resolve(path: &Path) -> Option<PathBuf> {
+    let svg = path.with_extension("svg");
+    if svg.exists() {
+        Some(svg)
+    } else if path.exists() {
+        Some(path.to_path_buf())
+    } else {
+        None
+    }
+}
+
+/// Charge un overlay résolu par [`resolve`] aux dimensions demandées
+pub fn load(path: &Path, fit: Fit, fonts_dir: &Path) -> Result<DynamicImage> {
+    if is_svg(path) {
+        return rasterize_svg(path, fit, fonts_dir);
+    }
+
+    let source = hidpi_variant(path, fit).unwrap_or_else(|| path.to_path_buf());
+    let img = image::open(&source).with_context(|| format!("Overlay illisible : {}", source.display()))?;
+    let (width, height) = fit.dimensions(img.width() as f32, img.height() as f32);
+
+    if (width, height) == (img.width(), img.height()) {
+        return Ok(img);
+    }
+    Ok(img.resize_exact(width, height, imageops::FilterType::Lanczos3))
+}
+
+fn is_svg(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+/// `<dossier>/@2x/<nom>@2x.png`, seulement si le PNG standard devrait être agrandi
+fn hidpi_variant(path: &Path, fit: Fit) -> Option<PathBuf> {
+    let (width, height) = image::image_dimensions(path).ok()?;
+    if fit.height(width, height) <= height {
+        return None;
+    }
+
+    let stem = path.file_stem()?.to_str()?;
+    let extension = path.extension()?.to_str()?;
+    let candidate = path.parent()?.join("@2x").join(format!("{}@2x.{}", stem, extension));
+    if !candidate.exists() {
+        return None;
+    }
+
+    debug!(path = %candidate.display(), "Variante @2x utilisée");
+    Some(candidate)
+}
+
+fn rasterize_svg(path: &Path, fit: Fit, fonts_dir: &Path) -> Result<DynamicImage> {
+    let data = std::fs::read(path).with_context(|| format!("SVG illisible : {}", path.display()))?;
+
+    let mut options = usvg::Options {
+        resources_dir: path.parent().map(Path::to_path_buf),
+        ..Default::default()
+    };
+    options.fontdb = font_db(fonts_dir);
+
+    let tree = usvg::Tree::from_data(&data, &options)
+        .with_context(|| format!("SVG invalide : {}", path.display()))?;
+
+    let size = tree.size();
+    let (width, height) = fit.dimensions(size.width(), size.height());
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| anyhow!("Dimensions SVG invalides : {}x{}", width, height))?;
+
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    // tiny-skia travaille en alpha prémultiplié, `image` en alpha droit
+    let mut rgba = RgbaImage::new(width, height);
+    for (dst, src) in rgba.pixels_mut().zip(pixmap.pixels()) {
+        let color = src.demultiply();
+        *dst = image::Rgba([color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+
+    debug!(path = %path.display(), width, height, "SVG rendu");
+    Ok(DynamicImage::ImageRgba8(rgba))
+}
+
+/// Base de polices partagée, chargée au premier SVG rendu
+fn font_db(fonts_dir: &Path) -> Arc<usvg::fontdb::Database> {
+    FONT_DB.get_or_init(|| {
+        let mut db = usvg::fontdb::Database::new();
+        db.load_system_fonts();
+        if fonts_dir.is_dir() {
+            db.load_fonts_dir(fonts_dir);
+        }
+
+        // "sans-serif" → Avenir des overlays : les images Docker n'ont souvent aucune police système
+        let avenir = db.faces()
+            .find(|face| face.families.iter().any(|(name, _)| name.starts_with("Avenir")))
+            .and_then(|face| face.families.first())
+            .map(|(name, _)| name.clone());
+        if let Some(family) = avenir {
+            db.set_sans_serif_family(family);
+        }
+        debug!(faces = db.len(), "Polices SVG chargées");
+        Arc::new(db)
+    }).clone()
+}
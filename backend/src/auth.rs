@@ -0,0 +1,319 @@
+// backend/src/auth.rs
+// Authentification du serveur : clé API (Bearer / X-Api-Key), Basic auth optionnelle, secret webhook, CORS
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use backend::locale;
+use std::env;
+use std::sync::Arc;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::{info, warn};
+
+const REALM: &str = "Basic realm=\"Rustizarr\"";
+
+#[derive(Debug, Clone, Default)]
+pub struct AuthConfig {
+    /// Clé attendue sur /api/* et les routes de scan (API_KEY)
+    pub api_key: Option<String>,
+    /// Identifiants Basic auth pour le frontend (BASIC_AUTH_USER / BASIC_AUTH_PASSWORD)
+    pub basic: Option<(String, String)>,
+    /// Secret partagé attendu dans `/webhook?token=...` (WEBHOOK_SECRET)
+    pub webhook_secret: Option<String>,
+    /// Origines CORS autorisées (CORS_ORIGINS, séparées par des virgules). Vide = permissif
+    pub cors_origins: Vec<String>,
+    /// /metrics accessible sans authentification (METRICS_PUBLIC=true)
+    pub metrics_public: bool,
+}
+
+impl AuthConfig {
+    pub fn from_env() -> Self {
+        let non_empty = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let basic = match (non_empty("BASIC_AUTH_USER"), non_empty("BASIC_AUTH_PASSWORD")) {
+            (Some(user), Some(password)) => Some((user, password)),
+            (None, None) => None,
+            _ => {
+                warn!("{}", locale::t("log.auth.basic_incomplete"));
+                None
+            }
+        };
+
+        let cors_origins = non_empty("CORS_ORIGINS").map(|v| parse_origins(&v)).unwrap_or_default();
+
+        Self {
+            api_key: non_empty("API_KEY"),
+            basic,
+            webhook_secret: non_empty("WEBHOOK_SECRET"),
+            cors_origins,
+            metrics_public: non_empty("METRICS_PUBLIC").is_some_and(|v| v == "true" || v == "1"),
+        }
+    }
+
+    /// Résumé de la configuration au démarrage (sans jamais afficher les secrets)
+    pub fn log_summary(&self) {
+        info!(
+            api_key = self.api_key.is_some(),
+            basic_auth = self.basic.is_some(),
+            webhook_secret = self.webhook_secret.is_some(),
+            cors_origins = ?self.cors_origins,
+            metrics_public = self.metrics_public,
+            "{}", locale::t("log.auth.configured")
+        );
+        if self.api_key.is_none() && self.basic.is_none() {
+            warn!("{}", locale::t("log.auth.open"));
+        }
+        if self.webhook_secret.is_none() {
+            warn!("{}", locale::t("log.auth.webhook_open"));
+        }
+    }
+
+    /// CORS permissif : aucune origine configurée ou joker "*"
+    fn cors_permissive(&self) -> bool {
+        self.cors_origins.is_empty() || self.cors_origins.iter().any(|o| o == "*")
+    }
+
+    /// CORS : permissif si aucune origine n'est configurée, sinon liste blanche avec credentials
+    pub fn cors_layer(&self) -> CorsLayer {
+        if self.cors_permissive() {
+            return CorsLayer::permissive();
+        }
+
+        let origins: Vec<HeaderValue> = self.cors_origins.iter()
+            .filter_map(|o| match o.parse() {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    warn!(origin = %o, "{}", locale::t("log.auth.cors_invalid"));
+                    None
+                }
+            })
+            .collect();
+
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::HeaderName::from_static("x-api-key")])
+            .expose_headers([header::HeaderName::from_static("x-total-count")])
+            .allow_credentials(true)
+    }
+
+    fn api_enabled(&self) -> bool {
+        self.api_key.is_some() || self.basic.is_some()
+    }
+
+    /// Vérifie la clé API (header Authorization / X-Api-Key ou paramètre `api_key`)
+    fn check_api_key(&self, headers: &HeaderMap, query: Option<&str>) -> bool {
+        let Some(expected) = &self.api_key else {
+            return false;
+        };
+
+        let bearer = header_str(headers, header::AUTHORIZATION.as_str())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        let api_key_header = header_str(headers, "x-api-key");
+        // Les balises <img> et EventSource ne peuvent pas envoyer de header
+        let api_key_query = query_param(query, "api_key");
+
+        let matches = |provided: &str| constant_time_eq(provided.trim(), expected);
+        bearer.is_some_and(matches)
+            || api_key_header.is_some_and(matches)
+            || api_key_query.as_deref().is_some_and(matches)
+    }
+
+    /// Vérifie les identifiants Basic auth
+    fn check_basic(&self, headers: &HeaderMap) -> bool {
+        let Some((user, password)) = &self.basic else {
+            return false;
+        };
+
+        let Some(encoded) = header_str(headers, header::AUTHORIZATION.as_str())
+            .and_then(|v| v.strip_prefix("Basic ")) else {
+            return false;
+        };
+
+        let Some(decoded) = STANDARD.decode(encoded.trim()).ok().and_then(|b| String::from_utf8(b).ok()) else {
+            return false;
+        };
+
+        match decoded.split_once(':') {
+            // Les deux comparaisons sont faites pour ne pas révéler lequel est faux
+            Some((u, p)) => constant_time_eq(u, user) & constant_time_eq(p, password),
+            None => false,
+        }
+    }
+
+    /// Vérifie le secret webhook (paramètre `token`) ; toujours vrai sans WEBHOOK_SECRET
+    fn check_webhook_token(&self, query: Option<&str>) -> bool {
+        let Some(expected) = &self.webhook_secret else {
+            return true;
+        };
+        query_param(query, "token").is_some_and(|token| constant_time_eq(&token, expected))
+    }
+
+    fn unauthorized(&self) -> Response {
+        let challenge = if self.basic.is_some() { REALM } else { "Bearer" };
+        (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, challenge)],
+            locale::t("api.error.unauthorized"),
+        ).into_response()
+    }
+}
+
+// ==================== MIDDLEWARES ====================
+
+/// Protège /api/*, les routes de scan et /metrics : clé API ou Basic auth
+pub async fn require_api_auth(State(auth): State<Arc<AuthConfig>>, request: Request, next: Next) -> Response {
+    if !auth.api_enabled() {
+        return next.run(request).await;
+    }
+
+    let headers = request.headers();
+    if auth.check_api_key(headers, request.uri().query()) || auth.check_basic(headers) {
+        return next.run(request).await;
+    }
+
+    warn!(path = %request.uri().path(), "{}", locale::t("log.auth.rejected"));
+    auth.unauthorized()
+}
+
+/// Protège le frontend servi par le backend (Basic auth uniquement)
+pub async fn require_basic_auth(State(auth): State<Arc<AuthConfig>>, request: Request, next: Next) -> Response {
+    if auth.basic.is_none() || auth.check_basic(request.headers()) {
+        return next.run(request).await;
+    }
+
+    auth.unauthorized()
+}
+
+/// Protège /webhook : secret partagé dans le paramètre `token`
+pub async fn require_webhook_secret(State(auth): State<Arc<AuthConfig>>, request: Request, next: Next) -> Response {
+    if auth.check_webhook_token(request.uri().query()) {
+        return next.run(request).await;
+    }
+
+    warn!("{}", locale::t("log.auth.webhook_rejected"));
+    (StatusCode::UNAUTHORIZED, locale::t("api.error.webhook_secret")).into_response()
+}
+
+// ==================== HELPERS ====================
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// Liste CORS_ORIGINS : entrées séparées par des virgules, vides ignorées
+fn parse_origins(value: &str) -> Vec<String> {
+    value.split(',').map(|o| o.trim().to_string()).filter(|o| !o.is_empty()).collect()
+}
+
+fn query_param(query: Option<&str>, name: &str) -> Option<String> {
+    let query = query?;
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Comparaison en temps constant (évite de deviner un secret par mesure du temps de réponse)
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_key(key: &str) -> AuthConfig {
+        AuthConfig { api_key: Some(key.to_string()), ..AuthConfig::default() }
+    }
+
+    fn with_basic(user: &str, password: &str) -> AuthConfig {
+        AuthConfig { basic: Some((user.to_string(), password.to_string())), ..AuthConfig::default() }
+    }
+
+    fn headers(entries: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in entries {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn basic_header(credentials: &str) -> HeaderMap {
+        headers(&[("authorization", &format!("Basic {}", STANDARD.encode(credentials)))])
+    }
+
+    #[test]
+    fn api_key_accepted_from_bearer_header_or_query() {
+        let auth = with_key("s3cret");
+        assert!(auth.check_api_key(&headers(&[("authorization", "Bearer s3cret")]), None));
+        assert!(auth.check_api_key(&headers(&[("x-api-key", " s3cret ")]), None));
+        assert!(auth.check_api_key(&HeaderMap::new(), Some("page=2&api_key=s3cret")));
+    }
+
+    #[test]
+    fn api_key_rejected_when_wrong_or_missing() {
+        let auth = with_key("s3cret");
+        assert!(!auth.check_api_key(&headers(&[("authorization", "Bearer nope")]), None));
+        assert!(!auth.check_api_key(&headers(&[("authorization", "s3cret")]), None));
+        assert!(!auth.check_api_key(&headers(&[("x-api-key", "s3cret2")]), None));
+        assert!(!auth.check_api_key(&HeaderMap::new(), Some("token=s3cret")));
+        assert!(!auth.check_api_key(&HeaderMap::new(), None));
+        // Sans API_KEY, aucune clé n'est valide
+        assert!(!AuthConfig::default().check_api_key(&headers(&[("x-api-key", "s3cret")]), None));
+    }
+
+    #[test]
+    fn basic_auth_decodes_credentials() {
+        let auth = with_basic("admin", "pa:ss");
+        assert!(auth.check_basic(&basic_header("admin:pa:ss")));
+        assert!(!auth.check_basic(&basic_header("admin:wrong")));
+        assert!(!auth.check_basic(&basic_header("other:pa:ss")));
+    }
+
+    #[test]
+    fn basic_auth_rejects_malformed_headers() {
+        let auth = with_basic("admin", "password");
+        assert!(!auth.check_basic(&headers(&[("authorization", "Basic %%%not-base64")])));
+        assert!(!auth.check_basic(&basic_header("adminpassword")));
+        assert!(!auth.check_basic(&headers(&[("authorization", "Bearer password")])));
+        assert!(!auth.check_basic(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn webhook_secret_must_match() {
+        let auth = AuthConfig { webhook_secret: Some("hook".to_string()), ..AuthConfig::default() };
+        assert!(auth.check_webhook_token(Some("token=hook")));
+        assert!(!auth.check_webhook_token(Some("token=hooks")));
+        assert!(!auth.check_webhook_token(Some("api_key=hook")));
+        assert!(!auth.check_webhook_token(None));
+        assert!(AuthConfig::default().check_webhook_token(None));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_lengths() {
+        assert!(constant_time_eq("abc", "abc"));
+        assert!(!constant_time_eq("abc", "abd"));
+        assert!(!constant_time_eq("abc", "abcd"));
+        assert!(!constant_time_eq("", "a"));
+        assert!(constant_time_eq("", ""));
+    }
+
+    #[test]
+    fn cors_mode_follows_origins() {
+        let cors = |value: &str| AuthConfig { cors_origins: parse_origins(value), ..AuthConfig::default() };
+        assert!(cors("").cors_permissive());
+        assert!(cors(" , ").cors_permissive());
+        assert!(cors("http://a.lan,*").cors_permissive());
+
+        let list = cors("http://a.lan, http://b.lan");
+        assert_eq!(list.cors_origins, ["http://a.lan", "http://b.lan"]);
+        assert!(!list.cors_permissive());
+    }
+}
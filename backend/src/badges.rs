@@ -0,0 +1,541 @@
+// backend/src/badges.rs
+// Badges générés (pilule + texte + icône) : style, badges personnalisés du fichier BADGES_FILE
+use crate::locale;
+use crate::plex::{PlexMedia, PlexMovie, STREAM_AUDIO, STREAM_SUBTITLE};
+use crate::ratings::RatingsConfig;
+use crate::theme::PosterTheme;
+use image::Rgba;
+use serde::Deserialize;
+use std::env;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Fichier de configuration par défaut, à la racine du dossier overlays
+const DEFAULT_FILE: &str = "badges.json";
+
+// ==================== STYLE ====================
+
+/// Apparence d'un badge généré
+#[derive(Debug, Clone)]
+pub struct BadgeStyle {
+    pub background: Rgba<u8>,
+    pub text: Rgba<u8>,
+    pub border: Option<Rgba<u8>>,
+    /// Police du texte (dossier `fonts` des overlays)
+    pub font: String,
+    /// Police d'icônes (Material Icons, Font Awesome...) pour le champ `icon`
+    pub icon_font: Option<String>,
+    /// Rayon des coins, en fraction de la hauteur (0.5 = pilule)
+    pub radius: f32,
+}
+
+impl Default for BadgeStyle {
+    fn default() -> Self {
+        Self {
+            background: Rgba([0, 0, 0, 190]),
+            text: Rgba([255, 255, 255, 255]),
+            border: Some(Rgba([255, 255, 255, 220])),
+            font: "AvenirNextLTPro-Bold.ttf".to_string(),
+            icon_font: None,
+            radius: 0.3,
+        }
+    }
+}
+
+/// Style partiel du fichier de configuration : seuls les champs présents remplacent le style de base
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StyleOverride {
+    pub background: Option<String>,
+    pub text: Option<String>,
+    /// Couleur de bordure, "none" pour la supprimer
+    pub border: Option<String>,
+    pub font: Option<String>,
+    pub icon_font: Option<String>,
+    pub radius: Option<f32>,
+}
+
+impl BadgeStyle {
+    pub fn merged(&self, o: &StyleOverride) -> Self {
+        let mut style = self.clone();
+        if let Some(color) = o.background.as_deref().and_then(parse_color) {
+            style.background = color;
+        }
+        if let Some(color) = o.text.as_deref().and_then(parse_color) {
+            style.text = color;
+        }
+        if let Some(border) = o.border.as_deref() {
+            style.border = if border.eq_ignore_ascii_case("none") { None } else { parse_color(border).or(style.border) };
+        }
+        if let Some(font) = &o.font {
+            style.font = font.clone();
+        }
+        if let Some(icon_font) = &o.icon_font {
+            style.icon_font = Some(icon_font.clone());
+        }
+        if let Some(radius) = o.radius {
+            style.radius = radius.clamp(0.0, 0.5);
+        }
+        style
+    }
+}
+
+/// Couleur "#RRGGBB" ou "#RRGGBBAA"
+pub fn parse_color(value: &str) -> Option<Rgba<u8>> {
+    let hex = value.trim().trim_start_matches('#');
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+
+    let color = match hex.len() {
+        6 => Some(Rgba([channel(0)?, channel(2)?, channel(4)?, 255])),
+        8 => Some(Rgba([channel(0)?, channel(2)?, channel(4)?, channel(6)?])),
+        _ => None,
+    };
+    if color.is_none() {
+        warn!(color = value, "{}", locale::t("log.badges.invalid_color"));
+    }
+    color
+}
+
+/// Badge prêt à dessiner
+#[derive(Debug, Clone)]
+pub struct BadgeSpec {
+    pub text: String,
+    pub icon: Option<char>,
+    /// Icône image (logo de source de note), dessinée avant le texte à la place de `icon`
+    pub image: Option<PathBuf>,
+    pub style: BadgeStyle,
+}
+
+/// Icône : caractère littéral ou point de code hexadécimal ("e050", "U+F028")
+fn parse_icon(value: &str) -> Option<char> {
+    let trimmed = value.trim();
+    let hex = trimmed.trim_start_matches("U+").trim_start_matches("u+").trim_start_matches("0x");
+    if hex.len() >= 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    trimmed.chars().next()
+}
+
+// ==================== CONFIGURATION ====================
+
+/// Rangée où placer un badge personnalisé
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BadgePosition {
+    TopLeft,
+    #[default]
+    BottomLeft,
+}
+
+/// Conditions d'affichage (toutes celles renseignées doivent être vraies)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BadgeCondition {
+    /// Langue d'une piste audio ("fr", "fra", "fre")
+    pub audio_language: Option<String>,
+    /// Langue d'un sous-titre
+    pub subtitle_language: Option<String>,
+    /// Codec d'une piste audio ("flac", "opus", "truehd")
+    pub audio_codec: Option<String>,
+    /// Résolution Plex ("4k", "1080")
+    pub resolution: Option<String>,
+    /// Label Plex
+    pub label: Option<String>,
+    /// Disposition des canaux de la meilleure piste ("2.0", "5.1", "7.1")
+    pub channels: Option<String>,
+    /// Audio objet (Atmos, DTS:X)
+    pub object_audio: Option<bool>,
+}
+
+/// Badge défini par l'utilisateur
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomBadge {
+    pub text: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub when: BadgeCondition,
+    #[serde(default)]
+    pub position: BadgePosition,
+    #[serde(default)]
+    pub style: StyleOverride,
+}
+
+/// Contenu de `badges.json` :
+/// `{ "style": {...}, "badges": [{ "text": "VF", "when": { "audio_language": "fr" } }] }`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BadgeConfig {
+    /// Style commun (badges générés et personnalisés)
+    #[serde(default)]
+    pub style: StyleOverride,
+    #[serde(default)]
+    pub badges: Vec<CustomBadge>,
+    /// Rangée des notes (bas-droite)
+    #[serde(default)]
+    pub ratings: RatingsConfig,
+    /// Style du thème du poster, sur lequel s'applique `style`
+    #[serde(skip)]
+    theme_style: BadgeStyle,
+}
+
+impl BadgeConfig {
+    /// BADGES_FILE, sinon `<overlays>/badges.json` ; configuration vide si absent
+    pub fn load(overlays_base: &Path) -> Self {
+        let path = env::var("BADGES_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| overlays_base.join(DEFAULT_FILE));
+
+        let mut config = Self::read(&path);
+        config.ratings = config.ratings.with_env();
+        config
+    }
+
+    fn read(path: &Path) -> Self {
+        let Ok(raw) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str::<Self>(&raw) {
+            Ok(config) => {
+                debug!(path = %path.display(), badges = config.badges.len(), "Badges personnalisés chargés");
+                config
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "{}", locale::t("log.badges.invalid_file"));
+                Self::default()
+            }
+        }
+    }
+
+    /// Adopte les couleurs du thème résolu pour le poster
+    pub fn with_theme(mut self, theme: &PosterTheme) -> Self {
+        self.theme_style = theme.badge_style();
+        self
+    }
+
+    pub fn base_style(&self) -> BadgeStyle {
+        self.theme_style.merged(&self.style)
+    }
+
+    /// Badge générique (attribut sans PNG dédié) au style commun
+    pub fn generic(&self, text: impl Into<String>) -> BadgeSpec {
+        BadgeSpec { text: text.into(), icon: None, image: None, style: self.base_style() }
+    }
+
+    pub fn spec(&self, badge: &CustomBadge) -> BadgeSpec {
+        BadgeSpec {
+            text: badge.text.clone(),
+            icon: badge.icon.as_deref().and_then(parse_icon),
+            image: None,
+            style: self.base_style().merged(&badge.style),
+        }
+    }
+
+    /// Badges personnalisés dont les conditions sont remplies
+    pub fn matching<'a>(&'a self, facts: &'a MediaFacts) -> impl Iterator<Item = &'a CustomBadge> + 'a {
+        self.badges.iter().filter(move |badge| badge.when.matches(facts))
+    }
+}
+
+// ==================== CONDITIONS ====================
+
+/// Attributs d'un film testés par les conditions
+#[derive(Debug, Clone, Default)]
+pub struct MediaFacts {
+    /// Langues audio (ISO 639-1 quand elle est connue : "fr" pour "fra"/"fre")
+    pub audio_languages: Vec<String>,
+    pub subtitle_languages: Vec<String>,
+    pub audio_codecs: Vec<String>,
+    pub resolution: Option<String>,
+    /// Disposition de la piste ayant le plus de canaux ("7.1")
+    pub channels: Option<String>,
+    /// Nombre de canaux de cette piste
+    pub channel_count: u64,
+    /// Une piste est en audio objet (Atmos, DTS:X)
+    pub object_audio: bool,
+    labels: Vec<String>,
+}
+
+impl MediaFacts {
+    pub fn from_movie(movie: &PlexMovie, media: Option<&PlexMedia>) -> Self {
+        let mut facts = media.map(Self::from_media).unwrap_or_default();
+        facts.labels = movie.label_tags();
+        facts
+    }
+
+    /// Attributs d'une version (sans les labels de l'élément)
+    pub fn from_media(media: &PlexMedia) -> Self {
+        let mut facts = Self {
+            resolution: media.video_resolution.as_ref().map(|r| r.to_lowercase()),
+            ..Default::default()
+        };
+        for stream in media.streams_of(STREAM_AUDIO) {
+            facts.audio_languages.extend(stream_languages(stream));
+            if let Some(codec) = stream.get("codec").and_then(|v| v.as_str()) {
+                facts.audio_codecs.push(codec.to_lowercase());
+            }
+            if let Some((count, layout)) = channel_layout(stream) {
+                if count > facts.channel_count {
+                    facts.channel_count = count;
+                    facts.channels = Some(layout);
+                }
+            }
+            facts.object_audio |= is_object_audio(stream);
+        }
+        if let Some(codec) = &media.audio_codec {
+            facts.audio_codecs.push(codec.to_lowercase());
+        }
+        for stream in media.streams_of(STREAM_SUBTITLE) {
+            facts.subtitle_languages.extend(stream_languages(stream));
+        }
+
+        dedup_in_order(&mut facts.audio_languages);
+        dedup_in_order(&mut facts.subtitle_languages);
+        facts
+    }
+
+    /// Badge de langue vu depuis la langue `local` ("fr") :
+    /// VF (locale seule), VF/VO (+ une autre), MULTI (+ plusieurs), VOSTFR (VO sous-titrée), VO
+    pub fn language_badge(&self, local: &str) -> Option<String> {
+        if self.audio_languages.is_empty() {
+            return None;
+        }
+
+        let local = normalize_language(local);
+        let has_local = self.audio_languages.contains(&local);
+        let others = self.audio_languages.iter().filter(|l| **l != local).count();
+
+        let key = match (has_local, others) {
+            (true, 0) => "badge.language.local",
+            (true, 1) => "badge.language.dual",
+            (true, _) => "badge.language.multi",
+            (false, _) if self.subtitle_languages.contains(&local) => "badge.language.subtitled",
+            (false, _) => "badge.language.original",
+        };
+
+        // Les libellés du catalogue (VF, VOSTFR...) supposent la langue de l'interface
+        let language = locale::current().language();
+        let code = local.to_uppercase();
+        let text = if local == language {
+            locale::tf(key, &[("code", &code)])
+        } else {
+            locale::tf(&format!("{}.other", key), &[("code", &code)])
+        };
+        Some(text)
+    }
+}
+
+fn dedup_in_order(values: &mut Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+    values.retain(|v| seen.insert(v.clone()));
+}
+
+// ==================== AUDIO ====================
+
+/// Nombre de canaux et disposition ("7.1") d'une piste audio
+fn channel_layout(stream: &serde_json::Value) -> Option<(u64, String)> {
+    let channels = stream.get("channels").and_then(|v| v.as_u64())?;
+
+    // `audioChannelLayout` : "7.1(side)", "5.1", "stereo"...
+    let declared = stream.get("audioChannelLayout").and_then(|v| v.as_str())
+        .map(|l| l.split('(').next().unwrap_or(l).trim().to_string())
+        .filter(|l| l.split('.').count() == 2 && l.split('.').all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())));
+
+    let layout = declared.unwrap_or_else(|| match channels {
+        1 => "1.0".to_string(),
+        2 => "2.0".to_string(),
+        3 => "2.1".to_string(),
+        6 => "5.1".to_string(),
+        7 => "6.1".to_string(),
+        8 => "7.1".to_string(),
+        n => format!("{}.0", n),
+    });
+    Some((channels, layout))
+}
+
+/// Atmos (TrueHD/E-AC3 JOC) ou DTS:X : pistes à objets
+fn is_object_audio(stream: &serde_json::Value) -> bool {
+    let field = |key: &str| stream.get(key).and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+    let titles = format!("{} {} {}", field("title"), field("displayTitle"), field("extendedDisplayTitle"));
+    titles.contains("atmos") || titles.contains("dts:x") || field("audioProfile") == "dts:x"
+}
+
+/// Badges audio optionnels : CHANNEL_BADGE=true, LANGUAGE_BADGE=true (langue de LOCALE) ou un code ("fr")
+#[derive(Debug, Clone, Default)]
+pub struct AudioBadges {
+    pub channels: bool,
+    /// Langue de référence du badge de langue, `None` = désactivé
+    pub language: Option<String>,
+}
+
+impl AudioBadges {
+    pub fn from_env() -> Self {
+        let flag = |name: &str| env::var(name).ok().map(|v| v.trim().to_lowercase()).filter(|v| !v.is_empty());
+
+        let language = match flag("LANGUAGE_BADGE").as_deref() {
+            None | Some("false") | Some("0") | Some("no") => None,
+            Some("true") | Some("1") | Some("yes") => Some(locale::current().language()),
+            Some(code) => Some(normalize_language(code)),
+        };
+
+        Self {
+            channels: flag("CHANNEL_BADGE").is_some_and(|v| matches!(v.as_str(), "true" | "1" | "yes")),
+            language,
+        }
+    }
+}
+
+fn stream_languages(stream: &serde_json::Value) -> Vec<String> {
+    let mut languages: Vec<String> = ["languageTag", "languageCode"].iter()
+        .filter_map(|key| stream.get(*key).and_then(|v| v.as_str()))
+        .map(normalize_language)
+        .collect();
+    languages.dedup();
+    // "und" (non défini) et "zxx" (pas de contenu linguistique) ne comptent pas
+    languages.retain(|l| !l.is_empty() && l != "und" && l != "zxx");
+    languages
+}
+
+/// Codes ISO 639-2 (B et T) des langues courantes → ISO 639-1
+const LANGUAGE_CODES: &[(&str, &[&str])] = &[
+    ("fr", &["fra", "fre"]), ("en", &["eng"]), ("de", &["deu", "ger"]), ("es", &["spa"]),
+    ("it", &["ita"]), ("pt", &["por"]), ("nl", &["nld", "dut"]), ("ja", &["jpn"]),
+    ("zh", &["zho", "chi"]), ("ko", &["kor"]), ("ru", &["rus"]), ("sv", &["swe"]),
+    ("da", &["dan"]), ("no", &["nor", "nob", "nno"]), ("fi", &["fin"]), ("pl", &["pol"]),
+    ("ar", &["ara"]), ("he", &["heb"]), ("hi", &["hin"]), ("tr", &["tur"]),
+    ("cs", &["ces", "cze"]), ("el", &["ell", "gre"]), ("hu", &["hun"]), ("uk", &["ukr"]),
+];
+
+/// "fra", "fre", "fr-CA", "FR" → "fr" ; codes inconnus seulement mis en minuscules
+pub fn normalize_language(code: &str) -> String {
+    let code = code.trim().split(['-', '_']).next().unwrap_or("").to_lowercase();
+    LANGUAGE_CODES.iter()
+        .find(|(_, aliases)| aliases.contains(&code.as_str()))
+        .map(|(iso1, _)| iso1.to_string())
+        .unwrap_or(code)
+}
+
+impl BadgeCondition {
+    pub fn matches(&self, facts: &MediaFacts) -> bool {
+        let contains = |values: &[String], wanted: &Option<String>| {
+            wanted.as_ref().is_none_or(|w| values.iter().any(|v| v.eq_ignore_ascii_case(w.trim())))
+        };
+        let language = |values: &[String], wanted: &Option<String>| {
+            contains(values, &wanted.as_deref().map(normalize_language))
+        };
+
+        language(&facts.audio_languages, &self.audio_language)
+            && language(&facts.subtitle_languages, &self.subtitle_language)
+            && contains(&facts.audio_codecs, &self.audio_codec)
+            && contains(&facts.labels, &self.label)
+            && self.resolution.as_ref().is_none_or(|r| facts.resolution.as_deref().is_some_and(|f| f.eq_ignore_ascii_case(r.trim())))
+            && self.channels.as_ref().is_none_or(|c| facts.channels.as_deref() == Some(c.trim()))
+            && self.object_audio.is_none_or(|o| facts.object_audio == o)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_color_accepts_rgb_and_rgba() {
+        assert_eq!(parse_color("#1E90FF"), Some(Rgba([0x1e, 0x90, 0xff, 255])));
+        assert_eq!(parse_color(" 00000080 "), Some(Rgba([0, 0, 0, 0x80])));
+    }
+
+    #[test]
+    fn parse_color_rejects_invalid_values() {
+        assert_eq!(parse_color("blue"), None);
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#GG0000"), None);
+    }
+
+    // ==================== LANGUES ====================
+
+    fn facts(audio: &[&str], subtitles: &[&str]) -> MediaFacts {
+        MediaFacts {
+            audio_languages: audio.iter().map(|l| normalize_language(l)).collect(),
+            subtitle_languages: subtitles.iter().map(|l| normalize_language(l)).collect(),
+            ..MediaFacts::default()
+        }
+    }
+
+    #[test]
+    fn normalize_language_maps_iso_639_2() {
+        assert_eq!(normalize_language("fre"), "fr");
+        assert_eq!(normalize_language("fra"), "fr");
+        assert_eq!(normalize_language(" FR "), "fr");
+        assert_eq!(normalize_language("fr-CA"), "fr");
+        assert_eq!(normalize_language("tlh"), "tlh");
+    }
+
+    #[test]
+    fn language_badge_distinguishes_original_and_dubbed() {
+        // Libellés du catalogue français (VF, VOSTFR...) : langue de l'interface = langue du badge
+        locale::init(Some("fr"));
+
+        assert_eq!(facts(&["fre"], &[]).language_badge("fr").as_deref(), Some("VF"));
+        assert_eq!(facts(&["eng", "fra"], &[]).language_badge("fr").as_deref(), Some("VF/VO"));
+        assert_eq!(facts(&["eng"], &["fre"]).language_badge("fr").as_deref(), Some("VOSTFR"));
+        assert_eq!(facts(&["eng"], &["ger"]).language_badge("fr").as_deref(), Some("VO"));
+        assert_eq!(facts(&[], &["fre"]).language_badge("fr"), None);
+    }
+
+    #[test]
+    fn language_badge_multi_audio() {
+        locale::init(Some("fr"));
+
+        assert_eq!(facts(&["fra", "eng", "ger"], &[]).language_badge("fr").as_deref(), Some("MULTI"));
+        // Autre langue que celle de l'interface : libellés génériques avec le code
+        assert_eq!(facts(&["ger", "eng"], &[]).language_badge("de").as_deref(), Some("DE/VO"));
+        assert_eq!(facts(&["ger"], &[]).language_badge("ger").as_deref(), Some("DE"));
+    }
+
+    // ==================== AUDIO ====================
+
+    #[test]
+    fn channel_layout_from_count_or_declared_layout() {
+        let layout = |stream: serde_json::Value| channel_layout(&stream);
+        assert_eq!(layout(json!({ "channels": 2 })), Some((2, "2.0".into())));
+        assert_eq!(layout(json!({ "channels": 6 })), Some((6, "5.1".into())));
+        assert_eq!(layout(json!({ "channels": 8 })), Some((8, "7.1".into())));
+        assert_eq!(layout(json!({ "channels": 8, "audioChannelLayout": "7.1(side)" })), Some((8, "7.1".into())));
+        assert_eq!(layout(json!({ "channels": 6, "audioChannelLayout": "5.1(side)" })), Some((6, "5.1".into())));
+        // Disposition non numérique ("stereo") : déduite du nombre de canaux
+        assert_eq!(layout(json!({ "channels": 2, "audioChannelLayout": "stereo" })), Some((2, "2.0".into())));
+        assert_eq!(layout(json!({ "audioChannelLayout": "5.1" })), None);
+    }
+
+    #[test]
+    fn object_audio_detects_atmos_and_dts_x() {
+        let truehd = json!({ "codec": "truehd", "displayTitle": "Anglais (TRUEHD 7.1 Atmos)" });
+        let eac3_joc = json!({ "codec": "eac3", "extendedDisplayTitle": "English (EAC3 5.1 Atmos)", "audioProfile": "lc" });
+        let dts_x = json!({ "codec": "dca", "audioProfile": "dts:x", "displayTitle": "English (DTS 7.1)" });
+        let dts_hd = json!({ "codec": "dca", "audioProfile": "ma", "displayTitle": "English (DTS-HD MA 7.1)" });
+
+        assert!(is_object_audio(&truehd));
+        assert!(is_object_audio(&eac3_joc));
+        assert!(is_object_audio(&dts_x));
+        assert!(!is_object_audio(&dts_hd));
+        assert!(!is_object_audio(&json!({ "codec": "aac", "channels": 2 })));
+    }
+
+    #[test]
+    fn media_facts_keep_widest_track() {
+        let media: PlexMedia = serde_json::from_value(json!({
+            "videoResolution": "4k",
+            "Part": [{ "Stream": [
+                { "streamType": 2, "codec": "aac", "channels": 2, "languageCode": "fre" },
+                { "streamType": 2, "codec": "truehd", "channels": 8, "languageCode": "eng", "displayTitle": "English (TrueHD 7.1 Atmos)" },
+                { "streamType": 3, "languageCode": "fra" }
+            ] }]
+        })).unwrap();
+        let facts = MediaFacts::from_media(&media);
+
+        assert_eq!(facts.audio_languages, ["fr", "en"]);
+        assert_eq!(facts.subtitle_languages, ["fr"]);
+        assert_eq!(facts.channels.as_deref(), Some("7.1"));
+        assert_eq!(facts.channel_count, 8);
+        assert!(facts.object_audio);
+    }
+}
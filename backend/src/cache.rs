@@ -0,0 +1,61 @@
+// backend/src/cache.rs
+// Caches du serveur (films, séries, saisons) adossés aux index incrémentaux
+use anyhow::Result;
+use backend::library_index::{LibraryIndex, SyncStats};
+use backend::metrics;
+use backend::plex::{PlexClient, PlexItem};
+use std::time::{Duration, Instant};
+
+/// Durée pendant laquelle l'index est servi sans interroger Plex
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Cache d'une section : une lecture après expiration ne demande à Plex
+/// que les éléments ajoutés, modifiés ou supprimés
+pub struct LibraryCache<T: PlexItem> {
+    /// Nom du cache dans les métriques ("movies", "shows", "seasons")
+    name: &'static str,
+    pub index: LibraryIndex<T>,
+    last_sync: Option<Instant>,
+}
+
+impl<T: PlexItem> LibraryCache<T> {
+    pub fn new(name: &'static str, library_id: &str) -> Self {
+        Self {
+            name,
+            index: LibraryIndex::new(library_id),
+            last_sync: None,
+        }
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        self.last_sync.is_some_and(|last| last.elapsed() < SYNC_INTERVAL)
+    }
+
+    /// Synchronise l'index s'il a expiré et renvoie les éléments
+    pub async fn items(&mut self, plex: &PlexClient) -> Result<Vec<T>> {
+        let fresh = self.is_fresh();
+        metrics::record_cache_access(self.name, fresh);
+        if !fresh {
+            self.sync(plex).await?;
+        }
+        Ok(self.index.items())
+    }
+
+    pub async fn sync(&mut self, plex: &PlexClient) -> Result<SyncStats> {
+        let stats = self.index.sync(plex).await?;
+        self.last_sync = Some(Instant::now());
+        Ok(stats)
+    }
+
+    /// Relecture complète de la section (rafraîchissement manuel)
+    pub async fn full_sync(&mut self, plex: &PlexClient) -> Result<SyncStats> {
+        let stats = self.index.full_sync(plex).await?;
+        self.last_sync = Some(Instant::now());
+        Ok(stats)
+    }
+
+    /// La prochaine lecture déclenchera une synchronisation incrémentale
+    pub fn invalidate(&mut self) {
+        self.last_sync = None;
+    }
+}
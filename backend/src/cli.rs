@@ -1,16 +1,16 @@
 // backend/src/cli.rs
-use clap::{Parser, Subcommand};
-use dotenv::dotenv;
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use std::env;
 use std::path::PathBuf;
+use tracing::{debug, error, info, warn};
 
-mod plex;
-mod tmdb;
-mod image_ops;
-mod processor;
-
-use plex::PlexClient;
-use tmdb::TmdbClient;
+use backend::plex::{ExternalId, PlexClient, PlexMovie, PlexShow};
+use backend::tmdb::TmdbClient;
+use backend::locale;
+use backend::logging::{self, LogFormat};
+use backend::processor::{self, RenderConfig};
+use backend::report::{self, MediaKind, ProcessReport, ReportFormat, ReportSummary};
 
 #[derive(Parser)]
 #[command(name = "rustizarr")]
@@ -18,6 +18,26 @@ use tmdb::TmdbClient;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Exporte un rapport détaillé du traitement (json ou csv)
+    #[arg(long, global = true, value_enum)]
+    report: Option<ReportFormat>,
+
+    /// Chemin du rapport exporté (défaut: rustizarr-report.<format>)
+    #[arg(long, global = true)]
+    report_path: Option<PathBuf>,
+
+    /// Augmente la verbosité des logs (-v: debug, -vv: trace)
+    #[arg(short = 'v', long = "verbose", global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Réduit la verbosité des logs (-q: warn, -qq: error)
+    #[arg(short = 'q', long = "quiet", global = true, action = clap::ArgAction::Count)]
+    quiet: u8,
+
+    /// Format des logs (défaut: variable LOG_FORMAT, sinon pretty)
+    #[arg(long, global = true, value_enum)]
+    log_format: Option<LogFormat>,
 }
 
 #[derive(Subcommand)]
@@ -26,6 +46,7 @@ enum Commands {
     
     /// Lance un scan complet de la bibliothèque FILMS
     Scan {
+        /// ID Plex de la bibliothèque films (défaut: LIBRARY_ID)
         #[arg(short, long)]
         library: Option<String>,
         
@@ -38,11 +59,19 @@ enum Commands {
         parallel: usize,
     },
     
-    /// Traite un seul film par son ID Plex
+    /// Traite un seul film par son ID Plex, TMDB ou IMDb
     Process {
         /// ID du film à traiter
         #[arg(short, long)]
         id: Option<String>,
+
+        /// ID TMDB du film (recherché dans la bibliothèque Plex)
+        #[arg(long, conflicts_with_all = ["id", "imdb", "all"])]
+        tmdb: Option<String>,
+
+        /// ID IMDb du film (ex: tt0133093)
+        #[arg(long, conflicts_with_all = ["id", "all"])]
+        imdb: Option<String>,
         
         /// Traiter toute la bibliothèque
         #[arg(short, long)]
@@ -55,15 +84,18 @@ enum Commands {
     
     /// Affiche les informations d'un film
     Info {
+        /// ID Plex du film
         #[arg(short, long)]
         id: String,
     },
     
     /// Liste tous les films de la bibliothèque
     List {
+        /// ID Plex de la bibliothèque films (défaut: LIBRARY_ID)
         #[arg(short, long)]
         library: Option<String>,
         
+        /// Lister uniquement les films non traités
         #[arg(long)]
         unprocessed: bool,
     },
@@ -72,6 +104,7 @@ enum Commands {
     
     /// Lance un scan complet de la bibliothèque SÉRIES
     ScanShows {
+        /// ID Plex de la bibliothèque séries (défaut: SHOWS_LIBRARY_ID)
         #[arg(short, long)]
         library: Option<String>,
         
@@ -82,6 +115,10 @@ enum Commands {
         /// Nombre de séries à traiter en parallèle (défaut: 1, max: 10)
         #[arg(short, long, default_value = "1")]
         parallel: usize,
+
+        /// Traiter aussi les saisons de chaque série
+        #[arg(long)]
+        seasons: bool,
     },
     
     /// Traite une seule série par son ID Plex
@@ -93,13 +130,19 @@ enum Commands {
         /// Forcer le retraitement
         #[arg(short, long)]
         force: bool,
+
+        /// Traiter aussi les saisons de chaque série
+        #[arg(long)]
+        seasons: bool,
     },
     
     /// Liste toutes les séries de la bibliothèque
     ListShows {
+        /// ID Plex de la bibliothèque séries (défaut: SHOWS_LIBRARY_ID)
         #[arg(short, long)]
         library: Option<String>,
         
+        /// Lister uniquement les séries non traitées
         #[arg(long)]
         unprocessed: bool,
     },
@@ -134,7 +177,7 @@ enum Commands {
 }
 
 /// Charge le fichier .env depuis plusieurs emplacements possibles
-fn load_env() {
+fn load_env() -> Option<PathBuf> {
     let env_paths = vec![
         PathBuf::from(".env"),                                          // Dossier courant
         PathBuf::from("backend/.env"),                                  // Dossier backend (si lancé depuis la racine)
@@ -148,14 +191,23 @@ fn load_env() {
     
     for path in &env_paths {
         if path.exists() {
-            println!("📁 Configuration chargée depuis: {}", path.display());
             dotenv::from_path(path).ok();
-            return;
+            return Some(path.clone());
         }
     }
     
-    eprintln!("⚠️  Aucun fichier .env trouvé!");
-    eprintln!("   Emplacements cherchés:");
+    None
+}
+
+/// Variable obligatoire du .env (message localisé si absente)
+fn required_env(name: &str) -> anyhow::Result<String> {
+    env::var(name).map_err(|_| anyhow::anyhow!(locale::tf("cli.env.required", &[("name", name)])))
+}
+
+/// Explique où placer le fichier .env quand aucun n'a été trouvé
+fn print_env_hint() {
+    eprintln!("{}", locale::t("cli.env.missing"));
+    eprintln!("{}", locale::t("cli.env.searched"));
     eprintln!("   - ./.env");
     eprintln!("   - ./backend/.env");
     if let Some(config) = dirs::config_dir() {
@@ -164,33 +216,130 @@ fn load_env() {
     if let Some(home) = dirs::home_dir() {
         eprintln!("   - {}", home.join(".rustizarr.env").display());
     }
-    eprintln!("\n💡 Créez un fichier .env avec:");
-    eprintln!("   PLEX_URL=http://votre-plex:32400");
-    eprintln!("   PLEX_TOKEN=votre_token");
-    eprintln!("   TMDB_KEY=votre_cle");
-    eprintln!("   LIBRARY_ID=1");
-    eprintln!("   SHOWS_LIBRARY_ID=2");
+    eprintln!("{}", locale::t("cli.env.hint"));
+    eprintln!("{}", locale::t("cli.env.example"));
+}
+
+/// Aide traduite : description du CLI, des sous-commandes et des arguments depuis le catalogue
+fn localized_command() -> clap::Command {
+    let mut command = localize_args(Cli::command().about(locale::t("cli.about")), "cli.arg")
+        .mut_arg("log_format", |arg| arg.value_parser(localized_log_formats()));
+    let names: Vec<String> = command.get_subcommands().map(|c| c.get_name().to_string()).collect();
+
+    for name in names {
+        let about = locale::t(&format!("cli.cmd.{}", name));
+        let prefix = format!("cli.arg.{}", name);
+        command = command.mut_subcommand(&name, |sub| localize_args(sub.about(about), &prefix));
+    }
+    command
+}
+
+/// Valeurs de --log-format avec leur description traduite (`cli.value.log_format.<valeur>`)
+fn localized_log_formats() -> impl TypedValueParser<Value = LogFormat> {
+    let values = LogFormat::value_variants().iter()
+        .filter_map(LogFormat::to_possible_value)
+        .map(|value| {
+            let help = locale::t(&format!("cli.value.log_format.{}", value.get_name()));
+            value.help(help)
+        });
+    PossibleValuesParser::new(values)
+        .map(|name| LogFormat::from_str(&name, true).expect("valeur validée par PossibleValuesParser"))
+}
+
+/// Aide de chaque argument : clé `<prefix>.<id>` (ex: `cli.arg.scan.force`)
+fn localize_args(mut command: clap::Command, prefix: &str) -> clap::Command {
+    let ids: Vec<String> = command.get_arguments().map(|a| a.get_id().to_string()).collect();
+
+    for id in ids {
+        let help = locale::t(&format!("{}.{}", prefix, id));
+        command = command.mut_arg(id, |arg| arg.help(help));
+    }
+    command
+}
+
+/// Journalise le résultat d'un élément traité
+fn log_report(report: &ProcessReport) {
+    if report.outcome.is_failure() {
+        warn!(title = %report.title, outcome = report.outcome.code(), detail = %report.outcome.detail(), "{}", locale::t("log.process.failed"));
+    } else {
+        info!(title = %report.title, outcome = report.outcome.code(), total_ms = report.timings.total_ms, "{}", locale::t("log.process.done"));
+    }
+}
+
+/// Traite les saisons d'une série (--seasons) ; une erreur n'interrompt pas le scan
+async fn process_seasons_into(
+    plex: &PlexClient,
+    tmdb: &TmdbClient,
+    render_config: &RenderConfig,
+    show: &PlexShow,
+    force: bool,
+    reports: &mut Vec<ProcessReport>
+) {
+    match processor::process_show_seasons(plex, tmdb, render_config, show, force).await {
+        Ok(season_reports) => {
+            for report in season_reports {
+                log_report(&report);
+                reports.push(report);
+            }
+        },
+        Err(e) => warn!(title = %show.title, error = ?e, "{}", locale::t("log.seasons.failed")),
+    }
 }
 
+/// Affiche le résumé d'un traitement à partir des rapports
+fn print_summary(reports: &[ProcessReport]) {
+    let summary = ReportSummary::from_reports(reports);
+
+    println!("{}", locale::t("cli.summary.title"));
+    println!("{}", locale::tf("cli.summary.rendered", &[("count", &summary.rendered.to_string())]));
+    println!("{}", locale::tf("cli.summary.skipped", &[("count", &summary.skipped.to_string())]));
+    println!("{}", locale::tf("cli.summary.failed", &[("count", &summary.failed.to_string())]));
+
+    for (code, count) in &summary.by_outcome {
+        if *code != "rendered" && *code != "skipped" {
+            println!("      • {} : {}", code, count);
+        }
+    }
+}
+
+/// Écrit le rapport demandé via --report
+fn export_reports(reports: &[ProcessReport], format: Option<ReportFormat>, path: Option<PathBuf>) -> anyhow::Result<()> {
+    let Some(format) = format else { return Ok(()) };
+
+    let path = path.unwrap_or_else(|| PathBuf::from(format!("rustizarr-report.{}", format.extension())));
+    report::write_reports(reports, format, &path)?;
+    info!(path = %path.display(), "{}", locale::t("log.cli.report_exported"));
+    Ok(())
+}
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    load_env();
-    
-    let cli = Cli::parse();
+    // .env et langue avant l'analyse des arguments : l'aide est traduite selon LOCALE
+    let env_file = load_env();
+    locale::init(None);
+    let cli = Cli::from_arg_matches(&localized_command().get_matches()).unwrap_or_else(|e| e.exit());
+
+    let log_format = cli.log_format.unwrap_or_else(LogFormat::from_env);
+    logging::init(logging::level_from_verbosity(cli.verbose, cli.quiet), log_format);
+
+    match env_file {
+        Some(path) => debug!(path = %path.display(), "Configuration chargée"),
+        None => print_env_hint(),
+    }
     
    // Récupère les variables d'environnement
-    let plex_url = env::var("PLEX_URL")
-        .expect("❌ PLEX_URL manquant dans le fichier .env");
-    let plex_token = env::var("PLEX_TOKEN")
-        .expect("❌ PLEX_TOKEN manquant dans le fichier .env");
-    let tmdb_key = env::var("TMDB_KEY")
-        .expect("❌ TMDB_KEY manquant dans le fichier .env");
+    let plex_url = required_env("PLEX_URL")?;
+    let plex_token = required_env("PLEX_TOKEN")?;
+    let tmdb_key = required_env("TMDB_KEY")?;
     let default_library = env::var("LIBRARY_ID").unwrap_or("1".to_string());
     let default_shows_library = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
     
     let plex = PlexClient::new(plex_url, plex_token);
     let tmdb = TmdbClient::new(tmdb_key);
+    let render_config = processor::RenderConfig::load();
+
+    // Rapports collectés par les commandes de traitement
+    let mut reports: Vec<ProcessReport> = Vec::new();
     
     match cli.command {
         // ==================== FILMS ====================
@@ -200,144 +349,124 @@ async fn main() -> anyhow::Result<()> {
             let concurrency = parallel.min(10);
             
             if concurrency > 1 {
-                println!("🔍 Scan PARALLÈLE de la bibliothèque {} (x{})", lib_id, concurrency);
+                info!(library = %lib_id, concurrency, "{}", locale::t("log.cli.scan_movies_parallel"));
             } else {
-                println!("🔍 Scan séquentiel de la bibliothèque {}", lib_id);
+                info!(library = %lib_id, "{}", locale::t("log.cli.scan_movies_sequential"));
             }
             
-            let movie_summaries = plex.get_library_items(&lib_id).await?;
-            println!("📚 {} films trouvés", movie_summaries.len());
-            
-            let mut movies = Vec::new();
-            for summary in movie_summaries {
-                match plex.get_item_details(&summary.rating_key).await {
-                    Ok(movie) => movies.push(movie),
-                    Err(e) => println!("⚠️ Erreur pour '{}': {:?}", summary.title, e),
-                }
-            }
+            let movies = plex.get_library_items_with_labels(&lib_id).await?;
+            info!(count = movies.len(), "{}", locale::t("log.cli.movies_found"));
             
             if concurrency > 1 {
-                let results = processor::process_library_parallel(&plex, &tmdb, movies, concurrency, force).await;
-                
-                let mut success = 0;
-                let mut skipped = 0;
-                let mut errors = 0;
+                let results = processor::process_library_parallel(&plex, &tmdb, &render_config, movies, concurrency, force).await;
                 
-                for (title, result) in results {
-                    match result {
-                        Ok(msg) => {
-                            if msg.contains("⏭️") {
-                                skipped += 1;
-                                println!("⏭️  {}", title);
-                            } else {
-                                success += 1;
-                                println!("✅ {}", title);
-                            }
-                        },
-                        Err(e) => {
-                            errors += 1;
-                            println!("❌ {} : {:?}", title, e);
-                        }
-                    }
+                for report in results {
+                    log_report(&report);
+                    reports.push(report);
                 }
-                
-                println!("\n📊 Résumé:");
-                println!("   ✅ Succès : {}", success);
-                println!("   ⏭️  Ignorés : {}", skipped);
-                println!("   ❌ Erreurs : {}", errors);
-                
             } else {
                 for (index, movie) in movies.iter().enumerate() {
-                    println!("\n[{}/{}] {}", index + 1, movies.len(), movie.title);
+                    info!(index = index + 1, total = movies.len(), title = %movie.title, "{}", locale::t("log.scan.item"));
                     
                     if !force && movie.has_label("Rustizarr") {
-                        println!("   ⏭️  Déjà traité");
+                        debug!("Déjà traité");
+                        reports.push(ProcessReport::already_processed(MediaKind::Movie, &movie.rating_key, &movie.title));
                         continue;
                     }
                     
-                    println!("   ⚙️  Traitement en cours...");
-                    
-                    match processor::process_movie(&plex, &tmdb, movie.clone()).await {
-                        Ok(msg) => println!("   {}", msg),
-                        Err(e) => println!("   ❌ Erreur: {:?}", e),
-                    }
+                    let report = processor::process_movie(&plex, &tmdb, &render_config, movie.clone()).await;
+                    log_report(&report);
+                    reports.push(report);
                 }
             }
             
-            println!("\n✅ Scan terminé !");
+            print_summary(&reports);
+            info!("{}", locale::t("log.scan.done"));
         },
         
-        Commands::Process { id, all, force } => {
+        Commands::Process { id, tmdb: tmdb_id, imdb, all, force } => {
+            let external = tmdb_id.map(ExternalId::Tmdb).or(imdb.map(ExternalId::Imdb));
+            let id = match (id, external) {
+                (Some(id), _) => Some(id),
+                (None, Some(external)) => {
+                    let lib_id = env::var("LIBRARY_ID").unwrap_or("1".to_string());
+                    let matches = plex.find_by_guid::<PlexMovie>(&lib_id, &external).await?;
+                    let Some(found) = matches.first() else {
+                        anyhow::bail!(locale::tf("cli.process.no_match", &[("guid", &external.to_guid())]));
+                    };
+                    if matches.len() > 1 {
+                        warn!(count = matches.len(), "{}", locale::t("log.cli.several_matches"));
+                    }
+                    info!(guid = %external.to_guid(), rating_key = %found.rating_key, title = %found.title, "{}", locale::t("log.cli.movie_found"));
+                    Some(found.rating_key.clone())
+                },
+                (None, None) => None,
+            };
+
             if let Some(movie_id) = id {
-                println!("⚙️  Traitement du film ID: {}", movie_id);
+                info!(rating_key = %movie_id, "{}", locale::t("log.cli.processing_movie"));
                 
                 let movie = plex.get_item_details(&movie_id).await?;
-                println!("🎬 Film: {}", movie.title);
+                info!(title = %movie.title, "{}", locale::t("log.cli.movie_loaded"));
                 
                 if !force && movie.has_label("Rustizarr") {
-                    println!("⏭️  Film déjà traité. Utilisez --force pour retraiter.");
-                    return Ok(());
-                }
-                
-                if force {
-                    println!("🔥 Mode FORCE activé");
-                }
-                
-                match processor::process_movie(&plex, &tmdb, movie).await {
-                    Ok(msg) => println!("✅ {}", msg),
-                    Err(e) => println!("❌ Erreur: {:?}", e),
+                    warn!("{}", locale::t("cli.already_processed.movie"));
+                    reports.push(ProcessReport::already_processed(MediaKind::Movie, &movie.rating_key, &movie.title));
+                } else {
+                    if force {
+                        info!("{}", locale::t("log.cli.force"));
+                    }
+                    
+                    let report = processor::process_movie(&plex, &tmdb, &render_config, movie).await;
+                    log_report(&report);
+                    reports.push(report);
                 }
                 
             } else if all {
-                println!("⚙️  Traitement de toute la bibliothèque (force: {})", force);
+                info!(force, "{}", locale::t("log.cli.processing_all"));
                 let lib_id = env::var("LIBRARY_ID").unwrap_or("1".to_string());
-                let movies = plex.get_library_items(&lib_id).await?;
+                let movies = plex.get_library_items_with_labels(&lib_id).await?;
                 
-                println!("📚 {} films à traiter", movies.len());
+                info!(count = movies.len(), "{}", locale::t("log.cli.movies_to_process"));
                 
-                for (index, movie_summary) in movies.iter().enumerate() {
-                    println!("\n[{}/{}] {}", index + 1, movies.len(), movie_summary.title);
+                for (index, movie) in movies.iter().enumerate() {
+                    info!(index = index + 1, total = movies.len(), title = %movie.title, "{}", locale::t("log.scan.item"));
                     
-                    match plex.get_item_details(&movie_summary.rating_key).await {
-                        Ok(movie) => {
-                            if !force && movie.has_label("Rustizarr") {
-                                println!("   ⏭️  Déjà traité");
-                                continue;
-                            }
-                            
-                            match processor::process_movie(&plex, &tmdb, movie).await {
-                                Ok(msg) => println!("   {}", msg),
-                                Err(e) => println!("   ❌ Erreur: {:?}", e),
-                            }
-                        },
-                        Err(e) => println!("   ❌ Erreur: {:?}", e),
-                    }
+                    let report = if !force && movie.has_label("Rustizarr") {
+                        debug!("Déjà traité");
+                        ProcessReport::already_processed(MediaKind::Movie, &movie.rating_key, &movie.title)
+                    } else {
+                        processor::process_movie(&plex, &tmdb, &render_config, movie.clone()).await
+                    };
+                    log_report(&report);
+                    reports.push(report);
                 }
                 
-                println!("\n✅ Traitement terminé !");
+                print_summary(&reports);
+                info!("{}", locale::t("log.process.done"));
                 
             } else {
-                println!("❌ Erreur: Vous devez spécifier --id ou --all");
+                error!("{}", locale::t("cli.process.missing_target"));
             }
         },
         
         Commands::Info { id } => {
             let movie = plex.get_item_details(&id).await?;
             
-            println!("\n📽️  Informations du film");
+            println!("{}", locale::t("cli.info.heading"));
             println!("─────────────────────────────");
-            println!("Titre: {}", movie.title);
+            println!("{}", locale::tf("cli.info.title", &[("title", &movie.title)]));
             println!("Rating Key: {}", movie.rating_key);
-            println!("Année: {:?}", movie.year);
+            println!("{}", locale::tf("cli.info.year", &[("year", &format!("{:?}", movie.year))]));
             
             if let Some(rating) = movie.audience_rating {
-                println!("Score: {:.1}/10", rating);
+                println!("{}", locale::tf("cli.info.score", &[("score", &locale::current().decimal(rating, 1))]));
             }
             
             if movie.has_label("Rustizarr") {
-                println!("✅ Déjà traité par Rustizarr");
+                println!("{}", locale::t("cli.info.processed"));
             } else {
-                println!("⏸️  Pas encore traité");
+                println!("{}", locale::t("cli.info.unprocessed"));
             }
         },
         
@@ -353,7 +482,7 @@ async fn main() -> anyhow::Result<()> {
                 movies.iter().collect()
             };
             
-            println!("\n📋 {} films", filtered.len());
+            println!("{}", locale::tf("cli.list.movies", &[("count", &filtered.len().to_string())]));
             println!("─────────────────────────────");
             
             for movie in filtered {
@@ -364,86 +493,73 @@ async fn main() -> anyhow::Result<()> {
 
         // ==================== SÉRIES ====================
         
-        Commands::ScanShows { library, force, parallel } => {
+        Commands::ScanShows { library, force, parallel, seasons } => {
             let lib_id = library.unwrap_or(default_shows_library);
             let concurrency = parallel.min(10);
             
             if concurrency > 1 {
-                println!("📺 Scan PARALLÈLE des séries (bibliothèque {}, x{})", lib_id, concurrency);
+                info!(library = %lib_id, concurrency, "{}", locale::t("log.cli.scan_shows_parallel"));
             } else {
-                println!("📺 Scan séquentiel des séries (bibliothèque {})", lib_id);
+                info!(library = %lib_id, "{}", locale::t("log.cli.scan_shows_sequential"));
             }
             
            let shows = plex.get_shows_library_items(&lib_id).await?;
-            println!("📚 {} séries trouvées", shows.len());
+            info!(count = shows.len(), "{}", locale::t("log.cli.shows_found"));
             
             if concurrency > 1 {
-                let results = processor::process_shows_parallel(&plex, &tmdb, shows, concurrency, force).await;
+                let results = processor::process_shows_parallel(&plex, &tmdb, &render_config, shows.clone(), concurrency, force).await;
                 
-                let mut success = 0;
-                let mut skipped = 0;
-                let mut errors = 0;
-                
-                for (title, result) in results {
-                    match result {
-                        Ok(msg) => {
-                            if msg.contains("⏭️") {
-                                skipped += 1;
-                                println!("⏭️  {}", title);
-                            } else {
-                                success += 1;
-                                println!("✅ {}", title);
-                            }
-                        },
-                        Err(e) => {
-                            errors += 1;
-                            println!("❌ {} : {:?}", title, e);
-                        }
-                    }
+                for report in results {
+                    log_report(&report);
+                    reports.push(report);
                 }
-                
-                println!("\n📊 Résumé:");
-                println!("   ✅ Succès : {}", success);
-                println!("   ⏭️  Ignorés : {}", skipped);
-                println!("   ❌ Erreurs : {}", errors);
-                
             } else {
                 for (index, show) in shows.iter().enumerate() {
-                    println!("\n[{}/{}] 📺 {}", index + 1, shows.len(), show.title);
+                    info!(index = index + 1, total = shows.len(), title = %show.title, "{}", locale::t("log.scan.item"));
                     
                     if !force && show.has_label("Rustizarr") {
-                        println!("   ⏭️  Déjà traitée");
+                        debug!("Déjà traitée");
+                        reports.push(ProcessReport::already_processed(MediaKind::Show, &show.rating_key, &show.title));
                         continue;
                     }
                     
-                    match processor::process_show(&plex, &tmdb, show.clone()).await {
-                        Ok(msg) => println!("   {}", msg),
-                        Err(e) => println!("   ❌ Erreur: {:?}", e),
-                    }
+                    let report = processor::process_show(&plex, &tmdb, &render_config, show.clone()).await;
+                    log_report(&report);
+                    reports.push(report);
+                }
+            }
+
+            if seasons {
+                for show in &shows {
+                    process_seasons_into(&plex, &tmdb, &render_config, show, force, &mut reports).await;
                 }
             }
             
-            println!("\n✅ Scan des séries terminé !");
+            print_summary(&reports);
+            info!("{}", locale::t("log.cli.scan_shows_done"));
         },
         
-        Commands::ProcessShow { id, force } => {
-            println!("⚙️  Traitement de la série ID: {}", id);
+        Commands::ProcessShow { id, force, seasons } => {
+            info!(rating_key = %id, "{}", locale::t("log.cli.processing_show"));
             
             let show = plex.get_show_details(&id).await?;
-            println!("📺 Série: {}", show.title);
+            info!(title = %show.title, "{}", locale::t("log.cli.show_loaded"));
             
             if !force && show.has_label("Rustizarr") {
-                println!("⏭️  Série déjà traitée. Utilisez --force pour retraiter.");
-                return Ok(());
-            }
-            
-            if force {
-                println!("🔥 Mode FORCE activé");
+                warn!("{}", locale::t("cli.already_processed.show"));
+                reports.push(ProcessReport::already_processed(MediaKind::Show, &show.rating_key, &show.title));
+            } else {
+                if force {
+                    info!("{}", locale::t("log.cli.force"));
+                }
+                
+                let report = processor::process_show(&plex, &tmdb, &render_config, show.clone()).await;
+                log_report(&report);
+                reports.push(report);
             }
-            
-            match processor::process_show(&plex, &tmdb, show).await {
-                Ok(msg) => println!("✅ {}", msg),
-                Err(e) => println!("❌ Erreur: {:?}", e),
+
+            if seasons {
+                process_seasons_into(&plex, &tmdb, &render_config, &show, force, &mut reports).await;
             }
         },
         
@@ -459,7 +575,7 @@ async fn main() -> anyhow::Result<()> {
                 shows.iter().collect()
             };
             
-            println!("\n📺 {} séries", filtered.len());
+            println!("{}", locale::tf("cli.list.shows", &[("count", &filtered.len().to_string())]));
             println!("─────────────────────────────");
             
             for show in filtered {
@@ -471,64 +587,77 @@ async fn main() -> anyhow::Result<()> {
         // ==================== SAISONS ====================
         
         Commands::ScanSeasons { show_id, force } => {
-            println!("🔍 Récupération de la série...");
+            debug!("Récupération de la série");
             let show = plex.get_show_details(&show_id).await?;
-            println!("📺 Série: {}", show.title);
-            
-            let tmdb_id = PlexClient::extract_tmdb_id_from_show(&show)
-                .ok_or_else(|| anyhow::anyhow!("Pas d'ID TMDB trouvé pour cette série"))?;
-            
-            let show_status = tmdb.get_show_status(&tmdb_id).await.ok().flatten();
+            info!(title = %show.title, "{}", locale::t("log.cli.show_loaded"));
             
-            println!("🔍 Récupération des saisons...");
-            let seasons = plex.get_show_seasons(&show_id).await?;
-            println!("📚 {} saisons trouvées", seasons.len());
-            
-            for (index, season) in seasons.iter().enumerate() {
-                println!("\n[{}/{}] 📀 Saison {}", index + 1, seasons.len(), season.season_number);
-                
-                if !force && season.has_label("Rustizarr") {
-                    println!("   ⏭️  Déjà traitée");
-                    continue;
-                }
-                
-                match processor::process_season(&plex, &tmdb, season.clone(), &tmdb_id, show_status.clone()).await {
-                    Ok(msg) => println!("   {}", msg),
-                    Err(e) => println!("   ❌ Erreur: {:?}", e),
-                }
+            for report in processor::process_show_seasons(&plex, &tmdb, &render_config, &show, force).await? {
+                log_report(&report);
+                reports.push(report);
             }
             
-            println!("\n✅ Traitement des saisons terminé !");
+            print_summary(&reports);
+            info!("{}", locale::t("log.cli.seasons_done"));
         },
         
         Commands::ProcessSeason { show_id, season_number, force } => {
-            println!("🔍 Récupération de la série...");
+            debug!("Récupération de la série");
             let show = plex.get_show_details(&show_id).await?;
-            println!("📺 Série: {}", show.title);
-            
-            let tmdb_id = PlexClient::extract_tmdb_id_from_show(&show)
-                .ok_or_else(|| anyhow::anyhow!("Pas d'ID TMDB trouvé pour cette série"))?;
-            
-            let show_status = tmdb.get_show_status(&tmdb_id).await.ok().flatten();
+            info!(title = %show.title, "{}", locale::t("log.cli.show_loaded"));
             
-            let seasons = plex.get_show_seasons(&show_id).await?;
-            let season = seasons.iter()
+            let show_seasons = processor::ShowSeasons::load(&plex, &tmdb, &show).await?;
+            let season = show_seasons.seasons.iter()
                 .find(|s| s.season_number == season_number)
-                .ok_or_else(|| anyhow::anyhow!("Saison {} introuvable", season_number))?;
+                .ok_or_else(|| anyhow::anyhow!(locale::tf("cli.season.not_found", &[("n", &season_number.to_string())])))?;
             
-            println!("📀 Traitement de la saison {}", season_number);
+            info!(season = season_number, "{}", locale::t("log.cli.processing_season"));
             
             if !force && season.has_label("Rustizarr") {
-                println!("⏭️  Saison déjà traitée. Utilisez --force pour retraiter.");
-                return Ok(());
-            }
-            
-            match processor::process_season(&plex, &tmdb, season.clone(), &tmdb_id, show_status).await {
-                Ok(msg) => println!("✅ {}", msg),
-                Err(e) => println!("❌ Erreur: {:?}", e),
+                warn!("{}", locale::t("cli.already_processed.season"));
             }
+            let report = show_seasons.process(&plex, &tmdb, &render_config, season, force).await;
+            log_report(&report);
+            reports.push(report);
         },
     }
+
+    export_reports(&reports, cli.report, cli.report_path)?;
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn catalog(raw: &str) -> HashMap<String, String> {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    /// Sans clé, l'aide afficherait la clé brute (`cli.arg.scan.force`)
+    #[test]
+    fn every_argument_has_a_help_key() {
+        let catalogs = [
+            ("en", catalog(include_str!("../locales/en.json"))),
+            ("fr", catalog(include_str!("../locales/fr.json"))),
+        ];
+        let command = Cli::command();
+        let mut keys: Vec<String> = command.get_arguments()
+            .map(|a| format!("cli.arg.{}", a.get_id()))
+            .collect();
+        keys.extend(LogFormat::value_variants().iter()
+            .filter_map(LogFormat::to_possible_value)
+            .map(|v| format!("cli.value.log_format.{}", v.get_name())));
+        for sub in command.get_subcommands() {
+            keys.push(format!("cli.cmd.{}", sub.get_name()));
+            keys.extend(sub.get_arguments().map(|a| format!("cli.arg.{}.{}", sub.get_name(), a.get_id())));
+        }
+
+        for (code, messages) in &catalogs {
+            for key in &keys {
+                assert!(messages.contains_key(key), "{} absent du catalogue {}", key, code);
+            }
+        }
+    }
+}
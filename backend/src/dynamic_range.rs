@@ -0,0 +1,260 @@
+// backend/src/dynamic_range.rs
+// Plage dynamique d'un flux vidéo Plex : Dolby Vision (profil, couche compatible),
+// HDR10, HDR10+, HLG ou SDR, à partir des champs du flux plutôt que de son libellé
+use crate::plex::{PlexMedia, STREAM_VIDEO};
+use serde_json::Value;
+
+/// Format de la couche de base (ce que voit un écran sans Dolby Vision)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HdrFormat {
+    Sdr,
+    Hlg,
+    Hdr10,
+    Hdr10Plus,
+}
+
+/// Métadonnées Dolby Vision du flux (`DOVIProfile`, `DOVIBLCompatID`) ; `DOVILevel` ne décrit que
+/// le débit requis du décodeur et n'apparaît sur aucun badge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DolbyVision {
+    pub profile: Option<u8>,
+    /// Compatibilité de la couche de base : 1 = HDR10, 2 = SDR, 4 = HLG
+    pub bl_compat_id: Option<u8>,
+}
+
+impl DolbyVision {
+    /// Couche de base déduite du profil : P5 n'en a pas, P7 est HDR10, P8 dépend de l'ID de compatibilité
+    fn fallback(&self) -> Option<Option<HdrFormat>> {
+        match (self.profile?, self.bl_compat_id) {
+            (5, _) => Some(None),
+            (7, _) | (8, Some(1)) => Some(Some(HdrFormat::Hdr10)),
+            (8, Some(2)) => Some(Some(HdrFormat::Sdr)),
+            (8, Some(4)) => Some(Some(HdrFormat::Hlg)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicRange {
+    pub dolby_vision: Option<DolbyVision>,
+    /// Couche de base ; SDR aussi pour un Dolby Vision sans couche compatible (profil 5)
+    pub hdr: HdrFormat,
+    pub bit_depth: Option<u8>,
+}
+
+impl DynamicRange {
+    /// Plage dynamique du premier flux vidéo de la version
+    pub fn from_media(media: &PlexMedia) -> Option<Self> {
+        media.streams_of(STREAM_VIDEO).first().map(|stream| Self::classify(stream))
+    }
+
+    pub fn classify(stream: &Value) -> Self {
+        let text = |key: &str| stream.get(key).and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+        let titles = format!("{} {} {}", text("displayTitle"), text("extendedDisplayTitle"), text("title"));
+        let color_trc = text("colorTrc");
+        let bit_depth = number(stream, "bitDepth");
+
+        let dolby_vision = is_dolby_vision(stream, &titles).then(|| DolbyVision {
+            profile: number(stream, "DOVIProfile"),
+            bl_compat_id: number(stream, "DOVIBLCompatID"),
+        });
+
+        // Couche de base d'après les champs couleur ; le libellé Plex ne sert qu'en dernier recours
+        let signalled = if titles.contains("hdr10+") || titles.contains("hdr10 plus") {
+            HdrFormat::Hdr10Plus
+        } else if color_trc == "smpte2084" {
+            HdrFormat::Hdr10
+        } else if color_trc == "arib-std-b67" {
+            HdrFormat::Hlg
+        } else if color_trc.is_empty() && text("colorPrimaries") == "bt2020" && bit_depth.is_some_and(|d| d >= 10) {
+            // Certains fichiers n'exposent pas colorTrc : BT.2020 en 10 bits est du HDR en pratique
+            HdrFormat::Hdr10
+        } else if titles.contains("hlg") {
+            HdrFormat::Hlg
+        } else if titles.contains("hdr") {
+            HdrFormat::Hdr10
+        } else {
+            HdrFormat::Sdr
+        };
+
+        let hdr = match dolby_vision.as_ref().and_then(DolbyVision::fallback) {
+            // Le HDR10+ dynamique se superpose à une couche HDR10
+            Some(Some(HdrFormat::Hdr10)) if signalled == HdrFormat::Hdr10Plus => HdrFormat::Hdr10Plus,
+            Some(fallback) => fallback.unwrap_or(HdrFormat::Sdr),
+            None => signalled,
+        };
+
+        Self { dolby_vision, hdr, bit_depth }
+    }
+
+    pub fn is_sdr(&self) -> bool {
+        self.dolby_vision.is_none() && self.hdr == HdrFormat::Sdr
+    }
+
+    /// Ordre de préférence : Dolby Vision (selon sa couche compatible) > HDR10+ > HDR10 > HLG > SDR
+    pub fn rank(&self) -> u8 {
+        let base = match self.hdr {
+            HdrFormat::Sdr => 0,
+            HdrFormat::Hlg => 1,
+            HdrFormat::Hdr10 => 2,
+            HdrFormat::Hdr10Plus => 3,
+        };
+        if self.dolby_vision.is_some() { 4 + base } else { base }
+    }
+
+    /// Préfixe des fichiers `media_info/codec` ("DV-HDR", "Plus"...), `None` sans visuel dédié (HLG, SDR)
+    pub fn asset_token(&self) -> Option<&'static str> {
+        match (&self.dolby_vision, self.hdr) {
+            (Some(_), HdrFormat::Hdr10Plus) => Some("DV-Plus"),
+            (Some(_), HdrFormat::Hdr10) => Some("DV-HDR"),
+            (Some(_), _) => Some("DV"),
+            (None, HdrFormat::Hdr10Plus) => Some("Plus"),
+            (None, HdrFormat::Hdr10) => Some("HDR"),
+            (None, _) => None,
+        }
+    }
+
+    /// Variante large du visuel seul ("DOLBY VISION · HDR10+"), quand aucun codec audio ne l'accompagne
+    pub fn extended_asset(&self) -> Option<String> {
+        match self.asset_token()? {
+            token @ ("DV-HDR" | "DV-Plus") => Some(format!("{}_extended.png", token)),
+            _ => None,
+        }
+    }
+
+    /// Libellé des badges générés : "DV P8 HDR10", "DV P5", "HDR10+", "HLG" ; `None` en SDR
+    pub fn label(&self) -> Option<String> {
+        let base = match self.hdr {
+            HdrFormat::Sdr => None,
+            HdrFormat::Hlg => Some("HLG"),
+            HdrFormat::Hdr10 => Some("HDR10"),
+            HdrFormat::Hdr10Plus => Some("HDR10+"),
+        };
+
+        match &self.dolby_vision {
+            Some(dv) => {
+                let mut label = "DV".to_string();
+                if let Some(profile) = dv.profile {
+                    label.push_str(&format!(" P{}", profile));
+                }
+                if let Some(base) = base {
+                    label.push(' ');
+                    label.push_str(base);
+                }
+                Some(label)
+            }
+            None => base.map(str::to_string),
+        }
+    }
+}
+
+fn is_dolby_vision(stream: &Value, titles: &str) -> bool {
+    let present = stream.get("DOVIPresent").is_some_and(|v| {
+        v.as_bool().unwrap_or(false) || v.as_u64() == Some(1) || v.as_str() == Some("1")
+    });
+    present
+        || stream.get("DOVIProfile").is_some()
+        || stream.get("doviprofile").is_some()
+        || titles.contains("dolby vision")
+        || titles.contains("dovi")
+}
+
+/// Champ numérique, que Plex le renvoie en nombre ou en chaîne
+fn number(stream: &Value, key: &str) -> Option<u8> {
+    let value = stream.get(key)?;
+    value.as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        .and_then(|n| u8::try_from(n).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn dolby_vision(range: &DynamicRange) -> (Option<u8>, Option<u8>) {
+        let dv = range.dolby_vision.as_ref().expect("Dolby Vision attendu");
+        (dv.profile, dv.bl_compat_id)
+    }
+
+    #[test]
+    fn dv_profile_5_has_no_compatible_layer() {
+        let range = DynamicRange::classify(&json!({ "DOVIPresent": true, "DOVIProfile": 5, "DOVILevel": 6 }));
+
+        assert_eq!(dolby_vision(&range), (Some(5), None));
+        assert_eq!(range.hdr, HdrFormat::Sdr);
+        assert!(!range.is_sdr());
+        assert_eq!(range.asset_token(), Some("DV"));
+        assert_eq!(range.label().as_deref(), Some("DV P5"));
+    }
+
+    #[test]
+    fn dv_profile_7_uses_hdr10_base_layer() {
+        let range = DynamicRange::classify(&json!({ "DOVIProfile": 7, "DOVIBLCompatID": 6, "colorTrc": "smpte2084" }));
+
+        assert_eq!(range.hdr, HdrFormat::Hdr10);
+        assert_eq!(range.asset_token(), Some("DV-HDR"));
+        assert_eq!(range.label().as_deref(), Some("DV P7 HDR10"));
+    }
+
+    #[test]
+    fn dv_profile_8_1_is_hdr10_compatible() {
+        let range = DynamicRange::classify(&json!({ "DOVIProfile": "8", "DOVIBLCompatID": "1" }));
+
+        assert_eq!(dolby_vision(&range), (Some(8), Some(1)));
+        assert_eq!(range.hdr, HdrFormat::Hdr10);
+        assert_eq!(range.asset_token(), Some("DV-HDR"));
+        assert_eq!(range.extended_asset().as_deref(), Some("DV-HDR_extended.png"));
+    }
+
+    #[test]
+    fn dv_profile_8_1_keeps_hdr10_plus_metadata() {
+        let range = DynamicRange::classify(&json!({
+            "DOVIProfile": 8, "DOVIBLCompatID": 1, "colorTrc": "smpte2084",
+            "displayTitle": "4K DoVi/HDR10+ (HEVC Main 10)",
+        }));
+
+        assert_eq!(range.hdr, HdrFormat::Hdr10Plus);
+        assert_eq!(range.asset_token(), Some("DV-Plus"));
+    }
+
+    #[test]
+    fn dv_profile_8_4_is_hlg_compatible() {
+        let range = DynamicRange::classify(&json!({ "DOVIProfile": 8, "DOVIBLCompatID": 4, "colorTrc": "arib-std-b67" }));
+
+        assert_eq!(range.hdr, HdrFormat::Hlg);
+        assert_eq!(range.asset_token(), Some("DV"));
+        assert_eq!(range.label().as_deref(), Some("DV P8 HLG"));
+    }
+
+    #[test]
+    fn hlg_has_label_but_no_asset() {
+        let range = DynamicRange::classify(&json!({ "colorTrc": "arib-std-b67", "bitDepth": 10 }));
+
+        assert!(range.dolby_vision.is_none());
+        assert_eq!(range.hdr, HdrFormat::Hlg);
+        assert_eq!(range.asset_token(), None);
+        assert_eq!(range.label().as_deref(), Some("HLG"));
+        assert!(!range.is_sdr());
+    }
+
+    #[test]
+    fn bt2020_without_color_trc_is_hdr10_in_10_bits_only() {
+        let ten_bits = DynamicRange::classify(&json!({ "colorPrimaries": "bt2020", "bitDepth": 10 }));
+        assert_eq!(ten_bits.hdr, HdrFormat::Hdr10);
+        assert_eq!(ten_bits.asset_token(), Some("HDR"));
+
+        let eight_bits = DynamicRange::classify(&json!({ "colorPrimaries": "bt2020", "bitDepth": 8 }));
+        assert!(eight_bits.is_sdr());
+    }
+
+    #[test]
+    fn bt709_is_sdr() {
+        let range = DynamicRange::classify(&json!({ "colorTrc": "bt709", "displayTitle": "1080p (H.264)" }));
+
+        assert!(range.is_sdr());
+        assert_eq!(range.label(), None);
+        assert_eq!(range.rank(), 0);
+    }
+}
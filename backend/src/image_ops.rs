@@ -1,13 +1,73 @@
-use image::{imageops, DynamicImage, Rgba};
+use image::{imageops, DynamicImage, Rgb, Rgba, RgbaImage};
 use std::path::{Path, PathBuf};
 use anyhow::Result;
 use imageproc::drawing::{draw_text_mut, text_size};
 use rusttype::{Font, Scale};
 use std::fs;
 use std::env;
+use tracing::{debug, warn};
+use crate::assets::{self, Fit};
+use crate::badges::BadgeSpec;
+use crate::locale;
+use crate::metrics;
+use crate::output::{self, OutputSettings};
+use crate::text_layout::{self, FontChain, TitleStyle};
+use crate::theme::ThemeVariant;
+use std::collections::HashMap;
+
+/// Marge entre les badges et le bord du poster
+const BADGE_MARGIN: u32 = 30;
+/// Espace entre deux badges d'une même rangée
+const BADGE_SPACING: u32 = 12;
+/// Miniature analysée pour la palette (ratio 2:3)
+const PALETTE_SAMPLE_WIDTH: u32 = 60;
+const PALETTE_SAMPLE_HEIGHT: u32 = 90;
+/// Réduction du calque de halo du titre avant flou
+const GLOW_DOWNSCALE: u32 = 4;
 
 pub struct ImageProcessor;
 
+/// Couleurs d'un poster, mesurées avant tout overlay
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    /// Couleur la plus représentée
+    pub dominant: Rgb<u8>,
+    /// Couleur saturée la plus présente (dominante à défaut)
+    pub accent: Rgb<u8>,
+    /// Luminosité moyenne (0-1) du poster entier
+    pub luminance: f32,
+    /// Luminosité moyenne (0-1) des bandes haute et basse, où se posent titre et badges
+    pub overlay_luminance: f32,
+}
+
+/// Rangée de badges remplie depuis son coin (haut-gauche, bas-gauche ou bas-droite)
+pub struct BadgeRow {
+    align_bottom: bool,
+    /// Remplie de droite à gauche, `x` est alors compté depuis le bord droit
+    align_right: bool,
+    /// Hauteur des badges en fraction de la hauteur du poster
+    height_percentage: f32,
+    x: u32,
+}
+
+impl BadgeRow {
+    pub fn top_left(height_percentage: f32) -> Self {
+        Self { align_bottom: false, align_right: false, height_percentage, x: BADGE_MARGIN }
+    }
+
+    pub fn bottom_left(height_percentage: f32) -> Self {
+        Self { align_bottom: true, align_right: false, height_percentage, x: BADGE_MARGIN }
+    }
+
+    pub fn bottom_right(height_percentage: f32) -> Self {
+        Self { align_bottom: true, align_right: true, height_percentage, x: BADGE_MARGIN }
+    }
+
+    pub fn badge_height(&self, base_image: &DynamicImage) -> u32 {
+        (base_image.height() as f32 * self.height_percentage) as u32
+    }
+}
+
 impl ImageProcessor {
     /// Retourne le chemin de base des overlays
     fn get_overlays_base_path() -> PathBuf {
@@ -43,25 +103,111 @@ impl ImageProcessor {
         PathBuf::from("overlays")
     }
 
-    /// Télécharge et standardise une image à 2000x3000px
-    pub async fn download_image(url: &str) -> Result<DynamicImage> {
+    /// Image contenant du texte : variante `<dossier>/<langue>/<fichier>` si elle existe
+    fn localized_asset(dir: &Path, filename: &str) -> PathBuf {
+        let localized = dir.join(locale::current().language()).join(filename);
+        if assets::resolve(&localized).is_some() {
+            localized
+        } else {
+            dir.join(filename)
+        }
+    }
+
+    /// Overlay à sa taille finale (SVG vectoriel, PNG @2x si agrandi), `None` s'il n'existe pas
+    fn load_asset(path: &Path, fit: Fit) -> Result<Option<DynamicImage>> {
+        let Some(resolved) = assets::resolve(path) else {
+            return Ok(None);
+        };
+        let fonts_dir = Self::get_overlays_base_path().join("fonts");
+        Ok(Some(assets::load(&resolved, fit, &fonts_dir)?))
+    }
+
+    /// Gradient à la largeur du poster, ramené à la hauteur du canevas s'il la dépasse (formats larges)
+    fn load_gradient(path: &Path, width: u32, height: u32) -> Result<Option<DynamicImage>> {
+        match Self::load_asset(path, Fit::Width(width))? {
+            Some(gradient) if gradient.height() > height => Self::load_asset(path, Fit::Exact(width, height)),
+            gradient => Ok(gradient),
+        }
+    }
+
+    /// Télécharge une image et l'adapte au canvas de sortie (recadrage ou marges, sans étirement)
+    pub async fn download_image(url: &str, output: &OutputSettings) -> Result<DynamicImage> {
+        let img = Self::fetch_image(url, "original").await?;
+
+        let _timer = metrics::render_stage_timer("canvas");
+        Ok(output::fit_canvas(img, output))
+    }
+
+    /// Télécharge un logo de titre (taille d'origine, transparence conservée)
+    pub async fn download_logo(url: &str) -> Result<DynamicImage> {
+        Self::fetch_image(url, "logo").await
+    }
+
+    async fn fetch_image(url: &str, endpoint: &str) -> Result<DynamicImage> {
         let client = reqwest::Client::builder()
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
             .timeout(std::time::Duration::from_secs(30))
             .build()?;
         
-        let resp = client.get(url).send().await?;
+        let resp = metrics::timed_send("tmdb_image", endpoint, client.get(url)).await?;
         let bytes = resp.bytes().await?;
-        let img = image::load_from_memory(&bytes)?;
+        metrics::record_download_bytes(bytes.len());
+
+        let _timer = metrics::render_stage_timer("decode");
+        Ok(image::load_from_memory(&bytes)?)
+    }
+
+    /// Palette du poster : couleurs quantifiées (4 bits par canal) sur une miniature
+    pub fn extract_palette(image: &DynamicImage) -> Palette {
+        let _timer = metrics::render_stage_timer("palette");
+        let thumb = image.resize_exact(PALETTE_SAMPLE_WIDTH, PALETTE_SAMPLE_HEIGHT, imageops::FilterType::Triangle).to_rgb8();
+
+        // Bandes des badges haut (15 %) et du titre / badges bas (30 %) : hors de la zone centrale
+        let center = PALETTE_SAMPLE_HEIGHT * 15 / 100..PALETTE_SAMPLE_HEIGHT * 70 / 100;
+        let mut buckets: HashMap<u16, (u32, [u32; 3])> = HashMap::new();
+        let (mut total, mut edges, mut edge_count) = (0.0, 0.0, 0u32);
+        for (_, y, pixel) in thumb.enumerate_pixels() {
+            let [r, g, b] = pixel.0;
+            let key = ((r as u16 >> 4) << 8) | ((g as u16 >> 4) << 4) | (b as u16 >> 4);
+            let bucket = buckets.entry(key).or_insert((0, [0; 3]));
+            bucket.0 += 1;
+            for (sum, value) in bucket.1.iter_mut().zip([r, g, b]) {
+                *sum += value as u32;
+            }
 
-        // Standardisation : Force 2000x3000 pixels pour uniformité
-        let standardized_img = img.resize_exact(2000, 3000, imageops::FilterType::Lanczos3);
+            let lum = luminance(*pixel);
+            total += lum;
+            if !center.contains(&y) {
+                edges += lum;
+                edge_count += 1;
+            }
+        }
 
-        Ok(standardized_img)
+        let average = |(count, sums): (u32, [u32; 3])| Rgb(sums.map(|sum| (sum / count.max(1)) as u8));
+        let dominant = buckets.values().max_by_key(|(count, _)| *count).copied().map(average).unwrap_or(Rgb([0, 0, 0]));
+        let accent = buckets.values()
+            .map(|bucket| (average(*bucket), bucket.0))
+            .filter(|(color, _)| saturation(*color) >= 0.3 && value(*color) >= 0.25)
+            .max_by(|(a, ca), (b, cb)| {
+                let score = |color: Rgb<u8>, count: u32| count as f32 * saturation(color).powi(2);
+                score(*a, *ca).total_cmp(&score(*b, *cb))
+            })
+            .map(|(color, _)| color)
+            .unwrap_or(dominant);
+
+        let palette = Palette {
+            dominant,
+            accent,
+            luminance: total / thumb.pixels().len().max(1) as f32,
+            overlay_luminance: edges / edge_count.max(1) as f32,
+        };
+        debug!(dominant = ?palette.dominant.0, accent = ?palette.accent.0, luminance = palette.luminance, "Palette extraite");
+        palette
     }
 
-    /// Applique les gradients haut et bas via PNG overlay
-    pub fn add_gradient_masks(mut base_image: DynamicImage, overlays_base: &str) -> anyhow::Result<DynamicImage> {
+    /// Applique les gradients haut et bas via PNG overlay, opacité multipliée par `opacity`
+    pub fn add_gradient_masks(mut base_image: DynamicImage, overlays_base: &str, opacity: f32) -> anyhow::Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("gradients");
         let base_path = if overlays_base.is_empty() {
             Self::get_overlays_base_path()
         } else {
@@ -74,36 +220,31 @@ impl ImageProcessor {
         let poster_w = base_image.width();
         let poster_h = base_image.height();
 
+        if opacity <= 0.0 {
+            return Ok(base_image);
+        }
+
         // 1. Gradient Haut
-        if top_path.exists() {
-            let top_img = image::open(&top_path)?;
-            let scale = poster_w as f32 / top_img.width() as f32;
-            let new_h = (top_img.height() as f32 * scale) as u32;
-            
-            let top_resized = top_img.resize(poster_w, new_h, imageops::FilterType::Lanczos3);
-            imageops::overlay(&mut base_image, &top_resized, 0, 0);
+        if let Some(top_resized) = Self::load_gradient(&top_path, poster_w, poster_h)? {
+            imageops::overlay(&mut base_image, &with_opacity(top_resized, opacity), 0, 0);
         } else {
-            println!("   ⚠️ Gradient top introuvable : {:?}", top_path);
+            warn!(path = ?top_path, "{}", locale::t("log.assets.gradient_top_missing"));
         }
 
         // 2. Gradient Bas
-        if bottom_path.exists() {
-            let bottom_img = image::open(&bottom_path)?;
-            let scale = poster_w as f32 / bottom_img.width() as f32;
-            let new_h = (bottom_img.height() as f32 * scale) as u32;
-            
-            let bottom_resized = bottom_img.resize(poster_w, new_h, imageops::FilterType::Lanczos3);
-            let y_pos = poster_h - bottom_resized.height();
-            imageops::overlay(&mut base_image, &bottom_resized, 0, y_pos as i64);
+        if let Some(bottom_resized) = Self::load_gradient(&bottom_path, poster_w, poster_h)? {
+            let y_pos = poster_h.saturating_sub(bottom_resized.height());
+            imageops::overlay(&mut base_image, &with_opacity(bottom_resized, opacity), 0, y_pos as i64);
         } else {
-            println!("   ⚠️ Gradient bottom introuvable : {:?}", bottom_path);
+            warn!(path = ?bottom_path, "{}", locale::t("log.assets.gradient_bottom_missing"));
         }
         
         Ok(base_image)
     }
 
-    /// Ajoute le titre du film/série en bas (multiline + word wrap)
-    pub fn add_movie_title(base_image: DynamicImage, title: &str, overlays_base: &str) -> anyhow::Result<DynamicImage> {
+    /// Ajoute le titre du film/série en bas (taille auto-ajustée, lignes équilibrées) aux couleurs du thème
+    pub fn add_movie_title(base_image: DynamicImage, title: &str, overlays_base: &str, theme: &ThemeVariant) -> anyhow::Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("title");
         let base_path = if overlays_base.is_empty() {
             Self::get_overlays_base_path()
         } else {
@@ -112,76 +253,90 @@ impl ImageProcessor {
         
         let font_path = base_path.join("fonts/Colus-Regular.ttf");
         if !font_path.exists() { 
-            println!("   ⚠️ Police Colus introuvable : {:?}", font_path);
+            warn!(path = ?font_path, "{}", locale::t("log.assets.colus_missing"));
             return Ok(base_image); 
         }
         
-        let font_data = fs::read(&font_path)?;
-        let font = Font::try_from_vec(font_data)
-            .ok_or_else(|| anyhow::anyhow!("Erreur chargement police"))?;
+        let chain = FontChain::load(&base_path, "Colus-Regular.ttf")?;
+        let style = TitleStyle::from_env();
 
         let mut image_rgba = base_image.to_rgba8();
-        let img_width = image_rgba.width() as i32;
-        let img_height = image_rgba.height() as i32;
-        let title_upper = title.to_uppercase();
-
-        // Taille de police fixe et imposante
-        let font_size = 250.0;
-        let scale = Scale::uniform(font_size);
-        
-        // Marge de sécurité (92% de la largeur)
-        let max_line_width = img_width as f32 * 0.92;
-
-        // Word wrapping
-        let words: Vec<&str> = title_upper.split_whitespace().collect();
-        let mut lines: Vec<String> = Vec::new();
-        let mut current_line = String::new();
-
-        for word in words {
-            let attempt = if current_line.is_empty() {
-                word.to_string()
-            } else {
-                format!("{} {}", current_line, word)
-            };
-
-            let (w, _) = text_size(scale, &font, &attempt);
-            
-            if w as f32 > max_line_width {
-                if !current_line.is_empty() {
-                    lines.push(current_line);
-                }
-                current_line = word.to_string();
-            } else {
-                current_line = attempt;
+        let img_width = image_rgba.width();
+        let img_height = image_rgba.height() as f32;
+
+        let text = text_layout::prepare_text(chain.as_ref(), title, style.uppercase);
+        let layout = text_layout::layout_title(chain.as_ref(), &text, img_width, &style);
+
+        // Bas du bloc de texte à 430px du bas (poster de 3000px)
+        let margin_bottom = 430.0 * img_height / 3000.0;
+        let start_y = img_height - margin_bottom - layout.height();
+        let shadow_offset = (layout.font_size / 50.0).max(1.0);
+
+        // Halo : titre dessiné sur un calque transparent au quart de la résolution, flouté puis agrandi
+        if let Some(glow) = theme.title_glow {
+            let sigma = layout.font_size / 12.0;
+            let pad = (sigma * 3.0).ceil();
+            let top = (start_y - pad).max(0.0);
+            let layer_height = (layout.height() + pad * 2.0).min(img_height - top).max(1.0) as u32;
+            let scale = GLOW_DOWNSCALE as f32;
+
+            let mut layer = RgbaImage::from_pixel(
+                (img_width / GLOW_DOWNSCALE).max(1),
+                (layer_height / GLOW_DOWNSCALE).max(1),
+                Rgba([glow[0], glow[1], glow[2], 0]),
+            );
+            for (i, (line, width)) in layout.lines.iter().enumerate() {
+                let x = (img_width as f32 - width) / 2.0 / scale;
+                let y = (start_y - top + i as f32 * layout.line_height) / scale;
+                chain.draw_line(&mut layer, Rgba([glow[0], glow[1], glow[2], 255]), x, y, layout.font_size / scale, line);
             }
+            let blurred = imageops::resize(&imageops::blur(&layer, sigma / scale), img_width, layer_height, imageops::FilterType::Triangle);
+            let blurred = with_opacity(DynamicImage::ImageRgba8(blurred), glow[3] as f32 / 255.0);
+            imageops::overlay(&mut image_rgba, &blurred, 0, top as i64);
         }
-        if !current_line.is_empty() {
-            lines.push(current_line);
-        }
-
-        // Calcul position verticale (430px du bas)
-        let margin_bottom = 430;
-        let line_height = font_size * 0.85;
-        let total_text_height = lines.len() as f32 * line_height;
-        let start_y = (img_height as f32 - margin_bottom as f32 - total_text_height) as i32;
 
         // Dessin ligne par ligne
-        for (i, line) in lines.iter().enumerate() {
-            let (w, _h) = text_size(scale, &font, line);
-            let x = (img_width - w) / 2;
-            let y = start_y + (i as f32 * line_height) as i32;
-
-            // Ombre portée (Noir)
-            draw_text_mut(&mut image_rgba, Rgba([0, 0, 0, 220]), x + 5, y + 5, scale, &font, line);
-            // Texte principal (Blanc)
-            draw_text_mut(&mut image_rgba, Rgba([255, 255, 255, 255]), x, y, scale, &font, line);
+        for (i, (line, width)) in layout.lines.iter().enumerate() {
+            let x = (img_width as f32 - width) / 2.0;
+            let y = start_y + i as f32 * layout.line_height;
+
+            // Ombre portée
+            if let Some(shadow) = theme.title_shadow {
+                chain.draw_line(&mut image_rgba, shadow, x + shadow_offset, y + shadow_offset, layout.font_size, line);
+            }
+            // Texte principal
+            chain.draw_line(&mut image_rgba, theme.title, x, y, layout.font_size, line);
         }
 
         Ok(DynamicImage::ImageRgba8(image_rgba))
     }
 
-    /// Ajoute le border inner glow
-    pub fn add_inner_glow_border(mut base_image: DynamicImage, overlays_base: &str) -> anyhow::Result<DynamicImage> {
+    /// Place le logo de titre dans la zone du titre (même ancrage bas que `add_movie_title`)
+    pub fn add_logo(mut base_image: DynamicImage, logo: &DynamicImage) -> Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("logo");
+        let (img_width, img_height) = (base_image.width() as f32, base_image.height() as f32);
+
+        // Zone du titre : 80% de la largeur, 16% de la hauteur, bas à 430px du bas (poster de 3000px)
+        let box_width = img_width * 0.80;
+        let box_height = img_height * 0.16;
+        let box_bottom = img_height - 430.0 * img_height / 3000.0;
+
+        let scale = (box_width / logo.width() as f32).min(box_height / logo.height() as f32);
+        let width = (logo.width() as f32 * scale).round().max(1.0) as u32;
+        let height = (logo.height() as f32 * scale).round().max(1.0) as u32;
+        let resized = logo.resize_exact(width, height, imageops::FilterType::Lanczos3);
+
+        let x = ((img_width - width as f32) / 2.0) as i64;
+        let y = (box_bottom - height as f32) as i64;
+        imageops::overlay(&mut base_image, &resized, x, y);
+
+        debug!(width, height, "Logo de titre appliqué");
+        Ok(base_image)
+    }
+
+    /// Ajoute le border inner glow, teinté de `tint` si fourni
+    pub fn add_inner_glow_border(mut base_image: DynamicImage, overlays_base: &str, tint: Option<Rgb<u8>>) -> anyhow::Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("inner_glow");
         let base_path = if overlays_base.is_empty() {
             Self::get_overlays_base_path()
         } else {
@@ -189,23 +344,24 @@ impl ImageProcessor {
         };
         
         let border_path = base_path.join("overlay-innerglow.png");
-        if !border_path.exists() { 
-            println!("   ⚠️ Inner glow introuvable : {:?}", border_path);
+        let fit = Fit::Exact(base_image.width(), base_image.height());
+        let Some(border_resized) = Self::load_asset(&border_path, fit)? else {
+            warn!(path = ?border_path, "{}", locale::t("log.assets.inner_glow_missing"));
             return Ok(base_image); 
-        }
+        };
 
-        let border_img = image::open(&border_path)?;
-        let border_resized = border_img.resize_exact(
-            base_image.width(),
-            base_image.height(),
-            imageops::FilterType::Lanczos3
-        );
-        imageops::overlay(&mut base_image, &border_resized, 0, 0);
+        let border = match tint {
+            Some(color) => tinted(border_resized, color),
+            None => border_resized,
+        };
+        imageops::overlay(&mut base_image, &border, 0, 0);
         Ok(base_image)
     }
     
-    /// Ajoute une bordure (status ou recently added)
-    pub fn add_status_border(mut base_image: DynamicImage, overlays_base: &str, status_filename: &str) -> anyhow::Result<DynamicImage> {
+    /// Ajoute une bordure (status ou recently added) ; sa couleur porte le statut et n'est jamais teintée,
+    /// `tint` ne sert qu'à l'inner glow de repli
+    pub fn add_status_border(mut base_image: DynamicImage, overlays_base: &str, status_filename: &str, tint: Option<Rgb<u8>>) -> anyhow::Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("status_border");
         let base_path = if overlays_base.is_empty() {
             Self::get_overlays_base_path()
         } else {
@@ -215,143 +371,234 @@ impl ImageProcessor {
         // Si c'est "recently_added.png", chercher à la racine
         // Sinon chercher dans le dossier Status/
         let border_path = if status_filename == "recently_added.png" {
-            base_path.join(status_filename)
+            Self::localized_asset(&base_path, status_filename)
         } else {
-            base_path.join("Status").join(status_filename)
+            Self::localized_asset(&base_path.join("Status"), status_filename)
         };
         
-        println!("   🔍 Recherche bordure : {:?}", border_path);
-        
-        if !border_path.exists() { 
-            println!("   ⚠️ Bordure introuvable : {:?}", border_path);
-            println!("   🔄 Utilisation de l'inner glow par défaut");
-            return Self::add_inner_glow_border(base_image, overlays_base);
-        }
-
-        let border_img = image::open(&border_path)?;
+        debug!(path = ?border_path, "Recherche bordure");
         
-        let border_resized = border_img.resize_exact(
-            base_image.width(),
-            base_image.height(),
-            imageops::FilterType::Lanczos3
-        );
+        let fit = Fit::Exact(base_image.width(), base_image.height());
+        let Some(border_resized) = Self::load_asset(&border_path, fit)? else {
+            warn!(path = ?border_path, "{}", locale::t("log.assets.border_missing"));
+            return Self::add_inner_glow_border(base_image, overlays_base, tint);
+        };
         
         imageops::overlay(&mut base_image, &border_resized, 0, 0);
-        println!("   ✅ Bordure '{}' appliquée", status_filename);
+        debug!(border = status_filename, "Bordure appliquée");
         
         Ok(base_image)
     }
 
-    /// Ajoute un overlay (résolution, édition, status, recently added, etc.)
-    /// `align_bottom`: true = coin bas-gauche, false = coin haut-gauche
-    /// `offset_index`: position dans la pile d'overlays (0, 1, 2...)
-    pub fn add_overlay(
-        mut base_image: DynamicImage, 
-        overlay_path: &Path, 
-        offset_index: usize, 
-        align_bottom: bool,
-        height_percentage: f32 
-    ) -> Result<DynamicImage> {
-        
-        if !overlay_path.exists() { 
-            println!("      ⚠️ Overlay introuvable : {:?}", overlay_path);
-            return Ok(base_image); 
+    /// Overlay fichier (résolution, édition, codec...) à la hauteur de la rangée, `None` s'il n'existe pas
+    pub fn load_badge(overlay_path: &Path, base_image: &DynamicImage, row: &BadgeRow) -> Result<Option<DynamicImage>> {
+        let height = row.badge_height(base_image);
+        if height == 0 { return Ok(None); }
+        Self::load_asset(overlay_path, Fit::Height(height))
+    }
+
+    /// Ajoute un badge à la suite de sa rangée
+    pub fn add_badge(mut base_image: DynamicImage, badge: &DynamicImage, row: &mut BadgeRow) -> DynamicImage {
+        let _timer = metrics::render_stage_timer("overlay");
+
+        let final_y = if row.align_bottom {
+            base_image.height().saturating_sub(badge.height() + BADGE_MARGIN)
+        } else {
+            BADGE_MARGIN
+        };
+        let final_x = if row.align_right {
+            base_image.width() as i64 - row.x as i64 - badge.width() as i64
+        } else {
+            row.x as i64
+        };
+
+        imageops::overlay(&mut base_image, badge, final_x, final_y as i64);
+        row.x += badge.width() + BADGE_SPACING;
+        base_image
+    }
+
+    /// Dessine un badge générique : pilule arrondie, icône optionnelle puis texte
+    pub fn render_badge(badge: &BadgeSpec, height: u32, overlays_base: &str) -> Result<DynamicImage> {
+        let _timer = metrics::render_stage_timer("generated_badge");
+        let base_path = if overlays_base.is_empty() {
+            Self::get_overlays_base_path()
+        } else {
+            PathBuf::from(overlays_base)
+        };
+        let style = &badge.style;
+
+        let text_font = FontChain::load(&base_path, &style.font)?;
+        let icon_font = match (&badge.icon, &style.icon_font) {
+            (Some(_), Some(font)) => Some(FontChain::load(&base_path, font)?),
+            (Some(_), None) => Some(text_font.clone()),
+            _ => None,
+        };
+
+        let h = height as f32;
+        let font_size = h * 0.55;
+        let icon_size = h * 0.62;
+        let padding = h * 0.35;
+        let gap = h * 0.2;
+
+        let icon_image = match &badge.image {
+            Some(path) => Self::load_asset(path, Fit::Height(icon_size.round() as u32))?,
+            None => None,
+        };
+        let icon_text = badge.icon.filter(|_| icon_image.is_none()).map(String::from);
+        let icon_width = match (&icon_image, &icon_font, &icon_text) {
+            (Some(image), _, _) => image.width() as f32,
+            (None, Some(font), Some(icon)) => font.measure(icon, icon_size),
+            _ => 0.0,
+        };
+        let text_width = text_font.measure(&badge.text, font_size);
+        let inner_gap = if icon_width > 0.0 && !badge.text.is_empty() { gap } else { 0.0 };
+        let width = (padding * 2.0 + icon_width + inner_gap + text_width).ceil().max(h) as u32;
+
+        let mut canvas = RgbaImage::new(width, height);
+        draw_rounded_rect(&mut canvas, style.background, style.border, style.radius * h, (h * 0.05).max(1.0));
+
+        // Centrage optique sur la hauteur des capitales (~0.7 em)
+        let baseline = (h + font_size * 0.7) / 2.0;
+
+        let mut x = (width as f32 - icon_width - inner_gap - text_width) / 2.0;
+        if let Some(image) = &icon_image {
+            let top = (height.saturating_sub(image.height())) / 2;
+            imageops::overlay(&mut canvas, &image.to_rgba8(), x.round() as i64, top as i64);
+            x += icon_width + inner_gap;
+        } else if let (Some(font), Some(icon)) = (&icon_font, &icon_text) {
+            // Police d'icônes : glyphe carré posé sur la ligne de base ; sinon aligné sur le texte
+            let top = match style.icon_font {
+                Some(_) => (h - icon_size) / 2.0 + icon_size - font.ascent(icon_size),
+                None => baseline - font.ascent(icon_size),
+            };
+            font.draw_line(&mut canvas, style.text, x, top, icon_size, icon);
+            x += icon_width + inner_gap;
         }
-        
-        let overlay = image::open(overlay_path)?;
-        let target_icon_height = (base_image.height() as f32 * height_percentage) as u32;
 
-        if target_icon_height == 0 { return Ok(base_image); }
+        text_font.draw_line(&mut canvas, style.text, x, baseline - text_font.ascent(font_size), font_size, &badge.text);
 
-        let scale_factor = target_icon_height as f32 / overlay.height() as f32;
-        let target_icon_width = (overlay.width() as f32 * scale_factor) as u32;
+        debug!(text = %badge.text, width, height, "Badge généré");
+        Ok(DynamicImage::ImageRgba8(canvas))
+    }
 
-        let overlay_resized = overlay.resize_exact(
-            target_icon_width, 
-            target_icon_height, 
-            imageops::FilterType::Lanczos3
-        );
+    /// Badge audience score (PNG/SVG `audience_score`) avec la note superposée,
+    /// `None` si le visuel n'existe pas
+    pub fn render_score_badge(
+        overlay_path: &Path,
+        height: u32,
+        score: &str,
+        color: Rgba<u8>,
+        overlays_base: &str
+    ) -> Result<Option<DynamicImage>> {
+        let _timer = metrics::render_stage_timer("audience_badge");
+        if height == 0 { return Ok(None); }
 
-        let margin = 30; 
-        let spacing = 12;
+        let Some(badge) = Self::load_asset(overlay_path, Fit::Height(height))? else {
+            warn!(path = ?overlay_path, "{}", locale::t("log.assets.audience_badge_missing"));
+            return Ok(None);
+        };
 
-        let final_x = margin + (offset_index as u32 * (overlay_resized.width() + spacing));
-        
-        let final_y = if align_bottom {
-            base_image.height() - overlay_resized.height() - margin
+        let base_path = if overlays_base.is_empty() {
+            Self::get_overlays_base_path()
         } else {
-            margin
+            PathBuf::from(overlays_base)
+        };
+
+        let font_path = base_path.join("fonts/AvenirNextLTPro-Bold.ttf");
+        if !font_path.exists() {
+            warn!(path = ?font_path, "{}", locale::t("log.assets.avenir_missing"));
+            return Ok(Some(badge));
+        }
+
+        let font_data = fs::read(font_path)?;
+        let Some(font) = Font::try_from_vec(font_data) else {
+            return Ok(Some(badge));
         };
 
-        imageops::overlay(&mut base_image, &overlay_resized, final_x as i64, final_y as i64);
+        let mut image_rgba = badge.to_rgba8();
+        let scale = Scale::uniform(height as f32 * 0.65);
+        let (text_w, text_h) = text_size(scale, &font, score);
 
-        Ok(base_image)
+        let text_x = (image_rgba.width() as i32 - text_w) / 2;
+        let text_y = (image_rgba.height() as i32 - text_h) / 2 + 2;
+
+        draw_text_mut(&mut image_rgba, color, text_x, text_y, scale, &font, score);
+        Ok(Some(DynamicImage::ImageRgba8(image_rgba)))
     }
+}
 
-    /// Ajoute un badge audience score en bas à droite avec note superposée
-    pub fn add_overlay_bottom_right(
-        mut base_image: DynamicImage, 
-        overlay_path: &Path, 
-        height_percentage: f32,
-        score: Option<f64>,
-        overlays_base: &str
-    ) -> Result<DynamicImage> {
-        
-        if !overlay_path.exists() { 
-            println!("      ⚠️ Badge audience introuvable : {:?}", overlay_path);
-            return Ok(base_image); 
+/// Luminosité perçue (0-1)
+fn luminance(pixel: Rgb<u8>) -> f32 {
+    let [r, g, b] = pixel.0.map(|c| c as f32 / 255.0);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+fn value(color: Rgb<u8>) -> f32 {
+    *color.0.iter().max().unwrap_or(&0) as f32 / 255.0
+}
+
+fn saturation(color: Rgb<u8>) -> f32 {
+    let max = *color.0.iter().max().unwrap_or(&0) as f32;
+    let min = *color.0.iter().min().unwrap_or(&0) as f32;
+    if max == 0.0 { 0.0 } else { (max - min) / max }
+}
+
+/// Overlay dont l'alpha est multiplié par `opacity`
+fn with_opacity(overlay: DynamicImage, opacity: f32) -> RgbaImage {
+    let mut rgba = overlay.to_rgba8();
+    if opacity < 1.0 {
+        for pixel in rgba.pixels_mut() {
+            pixel[3] = (pixel[3] as f32 * opacity).round() as u8;
         }
-        
-        let overlay = image::open(overlay_path)?;
-        
-        let target_icon_height = (base_image.height() as f32 * height_percentage) as u32;
-        if target_icon_height == 0 { return Ok(base_image); }
-
-        let scale_factor = target_icon_height as f32 / overlay.height() as f32;
-        let target_icon_width = (overlay.width() as f32 * scale_factor) as u32;
-
-        let overlay_resized = overlay.resize_exact(
-            target_icon_width, 
-            target_icon_height, 
-            imageops::FilterType::Lanczos3
-        );
-
-        let margin = 30; 
-        let badge_x = base_image.width() - overlay_resized.width() - margin;
-        let badge_y = base_image.height() - overlay_resized.height() - margin;
-
-        imageops::overlay(&mut base_image, &overlay_resized, badge_x as i64, badge_y as i64);
-
-        // Ajout de la note si fournie
-        if let Some(val) = score {
-            let base_path = if overlays_base.is_empty() {
-                Self::get_overlays_base_path()
-            } else {
-                PathBuf::from(overlays_base)
-            };
-            
-            let font_path = base_path.join("fonts/AvenirNextLTPro-Bold.ttf");
-            if font_path.exists() {
-                let font_data = fs::read(font_path)?;
-                if let Some(font) = Font::try_from_vec(font_data) {
-                    let score_text = format!("{:.1}", val);
-                    let mut image_rgba = base_image.to_rgba8();
-                    let font_size = target_icon_height as f32 * 0.65;
-                    let scale = Scale::uniform(font_size);
-                    let (text_w, text_h) = text_size(scale, &font, &score_text);
-                    
-                    let text_x = badge_x as i32 + (overlay_resized.width() as i32 - text_w) / 2;
-                    let text_y = badge_y as i32 + (overlay_resized.height() as i32 - text_h) / 2 + 2;
-
-                    // Note en noir pour contraste sur badge clair
-                    draw_text_mut(&mut image_rgba, Rgba([0, 0, 0, 255]), text_x, text_y, scale, &font, &score_text);
-                    return Ok(DynamicImage::ImageRgba8(image_rgba));
-                }
-            } else {
-                println!("      ⚠️ Police Avenir introuvable pour note : {:?}", font_path);
-            }
+    }
+    rgba
+}
+
+/// Recolore un overlay dont la forme tient dans l'alpha (inner glow) : la teinte croît avec l'alpha,
+/// le voile léger du centre garde sa couleur d'origine
+fn tinted(overlay: DynamicImage, color: Rgb<u8>) -> DynamicImage {
+    let mut rgba = overlay.to_rgba8();
+    let (min, max) = rgba.pixels().fold((u8::MAX, 0), |(min, max), p| (min.min(p[3]), max.max(p[3])));
+    let range = (max.saturating_sub(min)).max(1) as f32;
+
+    for pixel in rgba.pixels_mut() {
+        let strength = (pixel[3].saturating_sub(min)) as f32 / range;
+        for channel in 0..3 {
+            pixel[channel] = (pixel[channel] as f32 * (1.0 - strength) + color[channel] as f32 * strength).round() as u8;
         }
-        
-        Ok(base_image)
+    }
+    DynamicImage::ImageRgba8(rgba)
+}
+
+/// Rectangle arrondi anticrénelé sur toute l'image (fond + bordure optionnelle)
+fn draw_rounded_rect(canvas: &mut RgbaImage, fill: Rgba<u8>, border: Option<Rgba<u8>>, radius: f32, border_width: f32) {
+    let (w, h) = (canvas.width() as f32, canvas.height() as f32);
+    let radius = radius.min(w / 2.0).min(h / 2.0);
+
+    for (px, py, pixel) in canvas.enumerate_pixels_mut() {
+        // Distance signée au bord du rectangle arrondi (négative à l'intérieur)
+        let cx = px as f32 + 0.5;
+        let cy = py as f32 + 0.5;
+        let dx = (cx - w / 2.0).abs() - (w / 2.0 - radius);
+        let dy = (cy - h / 2.0).abs() - (h / 2.0 - radius);
+        let outside = (dx.max(0.0).powi(2) + dy.max(0.0).powi(2)).sqrt();
+        let distance = outside + dx.max(dy).min(0.0) - radius;
+
+        let shape = (0.5 - distance).clamp(0.0, 1.0);
+        if shape == 0.0 {
+            continue;
+        }
+
+        // Transition fond → bordure également anticrénelée
+        let color = match border {
+            Some(border) => {
+                let t = (distance + border_width + 0.5).clamp(0.0, 1.0);
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+                Rgba([mix(fill[0], border[0]), mix(fill[1], border[1]), mix(fill[2], border[2]), mix(fill[3], border[3])])
+            }
+            None => fill,
+        };
+        let alpha = (color[3] as f32 * shape).round() as u8;
+        *pixel = Rgba([color[0], color[1], color[2], alpha]);
     }
 }
@@ -0,0 +1,18 @@
+// backend/src/lib.rs
+// Modules partagés entre le serveur (main.rs) et le CLI (cli.rs)
+pub mod assets;
+pub mod badges;
+pub mod dynamic_range;
+pub mod plex;
+pub mod tmdb;
+pub mod image_ops;
+pub mod library_index;
+pub mod locale;
+pub mod logging;
+pub mod metrics;
+pub mod output;
+pub mod processor;
+pub mod ratings;
+pub mod report;
+pub mod text_layout;
+pub mod theme;
@@ -0,0 +1,243 @@
+// backend/src/library_index.rs
+// Index local d'une bibliothèque Plex, synchronisé de façon incrémentale (updatedAt)
+use crate::plex::{PlexClient, PlexItem};
+use crate::locale;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// Intervalle de réconciliation complète (suppressions non signalées par le compteur)
+const FULL_RESYNC_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// Bilan d'une synchronisation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncStats {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    /// Liste complète des rating keys relue (premier chargement ou réconciliation)
+    pub full: bool,
+}
+
+impl SyncStats {
+    pub fn has_changes(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+pub struct LibraryIndex<T: PlexItem> {
+    library_id: String,
+    items: HashMap<String, T>,
+    /// Plus grand updatedAt connu : prochain filtre `updatedAt>>=`
+    watermark: Option<u64>,
+    last_full_sync: Option<Instant>,
+}
+
+impl<T: PlexItem> LibraryIndex<T> {
+    pub fn new(library_id: &str) -> Self {
+        Self {
+            library_id: library_id.to_string(),
+            items: HashMap::new(),
+            watermark: None,
+            last_full_sync: None,
+        }
+    }
+
+    pub fn library_id(&self) -> &str {
+        &self.library_id
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, rating_key: &str) -> Option<&T> {
+        self.items.get(rating_key)
+    }
+
+    /// Éléments triés par titre
+    pub fn items(&self) -> Vec<T> {
+        let mut items: Vec<T> = self.items.values().cloned().collect();
+        items.sort_by_cached_key(|i| i.title().to_lowercase());
+        items
+    }
+
+    /// Synchronise l'index : seuls les éléments ajoutés ou modifiés sont relus en détail
+    pub async fn sync(&mut self, plex: &PlexClient) -> Result<SyncStats> {
+        let needs_full = self.watermark.is_none()
+            || self.last_full_sync.is_none_or(|t| t.elapsed() >= FULL_RESYNC_INTERVAL);
+
+        if needs_full {
+            return self.full_sync(plex).await;
+        }
+
+        // 1. Éléments ajoutés ou modifiés depuis la dernière synchro
+        let changed: Vec<T> = plex.get_section_items(&self.library_id, self.watermark).await?;
+        let mut stats = self.apply_changes(plex, changed).await;
+
+        // 2. Suppressions : le compteur Plex diverge de l'index
+        let total = plex.count_section_items(&self.library_id, T::PLEX_TYPE).await?;
+        if total != self.items.len() {
+            debug!(total, indexed = self.items.len(), "Compteur divergent, réconciliation des rating keys");
+            let reconciled = self.reconcile(plex).await?;
+            stats.added += reconciled.added;
+            stats.removed += reconciled.removed;
+        }
+
+        if stats.has_changes() {
+            info!(library_id = %self.library_id, added = stats.added, updated = stats.updated, removed = stats.removed, "{}", locale::t("log.index.synced"));
+        } else {
+            debug!(library_id = %self.library_id, "Index à jour");
+        }
+        Ok(stats)
+    }
+
+    /// Relit toute la section (rating keys) et ne récupère en détail que le nouveau ou le modifié
+    pub async fn full_sync(&mut self, plex: &PlexClient) -> Result<SyncStats> {
+        let mut stats = self.reconcile(plex).await?;
+        stats.full = true;
+        self.last_full_sync = Some(Instant::now());
+
+        info!(
+            library_id = %self.library_id,
+            total = self.items.len(),
+            added = stats.added,
+            updated = stats.updated,
+            removed = stats.removed,
+            "{}", locale::t("log.index.rebuilt")
+        );
+        Ok(stats)
+    }
+
+    /// Compare la section complète à l'index : suppressions, ajouts et éléments modifiés
+    async fn reconcile(&mut self, plex: &PlexClient) -> Result<SyncStats> {
+        let listing: Vec<T> = plex.get_section_items(&self.library_id, None).await?;
+
+        let removed = self.prune(&listing);
+        let mut stats = self.apply_changes(plex, listing).await;
+        stats.removed = removed;
+        Ok(stats)
+    }
+
+    /// Récupère les détails des éléments modifiés et les insère dans l'index
+    async fn apply_changes(&mut self, plex: &PlexClient, changed: Vec<T>) -> SyncStats {
+        // Le filtre ">>=" renvoie aussi les éléments au watermark exact : on ignore ceux déjà à jour
+        let changed = self.stale(changed);
+        if changed.is_empty() {
+            return SyncStats::default();
+        }
+
+        let detailed = if T::NEEDS_DETAILS {
+            plex.fetch_details(changed).await
+        } else {
+            changed
+        };
+
+        self.insert(detailed)
+    }
+
+    /// Retire de l'index les éléments absents du listing complet, renvoie le nombre de suppressions
+    fn prune(&mut self, listing: &[T]) -> usize {
+        let live: HashSet<&str> = listing.iter().map(|i| i.rating_key()).collect();
+        let before = self.items.len();
+        self.items.retain(|key, _| live.contains(key.as_str()));
+        before - self.items.len()
+    }
+
+    /// Garde les éléments inconnus ou dont l'updatedAt diffère de l'index
+    fn stale(&self, items: Vec<T>) -> Vec<T> {
+        items.into_iter()
+            .filter(|i| self.items.get(i.rating_key()).is_none_or(|known| known.updated_at() != i.updated_at()))
+            .collect()
+    }
+
+    /// Insère les éléments détaillés et avance le watermark
+    fn insert(&mut self, detailed: Vec<T>) -> SyncStats {
+        let mut stats = SyncStats::default();
+        for item in detailed {
+            if let Some(updated_at) = item.updated_at() {
+                self.watermark = Some(self.watermark.map_or(updated_at, |w| w.max(updated_at)));
+            }
+            match self.items.insert(item.rating_key().to_string(), item) {
+                Some(_) => stats.updated += 1,
+                None => stats.added += 1,
+            }
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Deserialize)]
+    struct Item {
+        key: String,
+        updated_at: Option<u64>,
+    }
+
+    impl PlexItem for Item {
+        const PLEX_TYPE: u8 = 1;
+        const NEEDS_DETAILS: bool = false;
+
+        fn rating_key(&self) -> &str { &self.key }
+        fn title(&self) -> &str { &self.key }
+        fn updated_at(&self) -> Option<u64> { self.updated_at }
+    }
+
+    fn item(key: &str, updated_at: Option<u64>) -> Item {
+        Item { key: key.to_string(), updated_at }
+    }
+
+    fn keys(items: &[Item]) -> Vec<&str> {
+        items.iter().map(|i| i.key.as_str()).collect()
+    }
+
+    #[test]
+    fn insert_advances_watermark_to_max_updated_at() {
+        let mut index = LibraryIndex::new("1");
+        let stats = index.insert(vec![item("a", Some(20)), item("b", None), item("c", Some(10))]);
+        assert_eq!((stats.added, stats.updated), (3, 0));
+        assert_eq!(index.watermark, Some(20));
+
+        let stats = index.insert(vec![item("c", Some(15))]);
+        assert_eq!((stats.added, stats.updated), (0, 1));
+        assert_eq!(index.watermark, Some(20), "le watermark ne recule jamais");
+    }
+
+    #[test]
+    fn stale_skips_items_already_at_watermark() {
+        let mut index = LibraryIndex::new("1");
+        index.insert(vec![item("a", Some(20)), item("b", Some(10))]);
+
+        // ">>=" renvoie l'élément au watermark exact : déjà à jour, il est ignoré
+        let changed = index.stale(vec![item("a", Some(20)), item("b", Some(25)), item("c", Some(30))]);
+        assert_eq!(keys(&changed), ["b", "c"]);
+    }
+
+    #[test]
+    fn reconcile_prunes_removed_and_keeps_changed() {
+        let mut index = LibraryIndex::new("1");
+        index.insert(vec![item("a", Some(1)), item("b", Some(2)), item("c", Some(3))]);
+
+        let listing = vec![item("a", Some(1)), item("c", Some(4)), item("d", Some(5))];
+        assert_eq!(index.prune(&listing), 1);
+        assert!(index.get("b").is_none());
+
+        let changed = index.stale(listing);
+        assert_eq!(keys(&changed), ["c", "d"]);
+
+        let stats = index.insert(changed);
+        assert_eq!((stats.added, stats.updated), (1, 1));
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.watermark, Some(5));
+    }
+}
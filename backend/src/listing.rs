@@ -0,0 +1,190 @@
+// backend/src/listing.rs
+// Filtrage, tri et pagination des listes renvoyées par /api/library et /api/shows
+use backend::plex::{PlexMovie, PlexShow};
+use serde::Deserialize;
+
+/// En-tête portant le nombre d'éléments après filtrage (avant pagination)
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// Ordre alphabétique
+    #[default]
+    Title,
+    /// Derniers ajouts en premier
+    Added,
+    /// Meilleure note en premier
+    Rating,
+}
+
+/// Paramètres de requête communs aux listes (tous optionnels)
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct ListQuery {
+    /// Avec ou sans le label "Rustizarr"
+    pub processed: Option<bool>,
+    /// Recherche dans le titre (insensible à la casse)
+    pub q: Option<String>,
+    pub year: Option<u32>,
+    /// Résolution Plex ("4k", "1080", "720"...)
+    pub resolution: Option<String>,
+    #[serde(default)]
+    pub sort: SortKey,
+    /// Page (à partir de 1). Sans `page` ni `page_size`, toute la liste est renvoyée
+    pub page: Option<usize>,
+    pub page_size: Option<usize>,
+}
+
+/// Champs utilisés pour filtrer et trier
+pub trait Listable {
+    fn title(&self) -> &str;
+    fn year(&self) -> Option<u32>;
+    fn added_at(&self) -> Option<u64>;
+    fn rating(&self) -> Option<f64>;
+    /// Résolutions de toutes les versions (Media)
+    fn resolutions(&self) -> Vec<&str>;
+    fn is_processed(&self) -> bool;
+}
+
+impl Listable for PlexMovie {
+    fn title(&self) -> &str { &self.title }
+    fn year(&self) -> Option<u32> { self.year.map(u32::from) }
+    fn added_at(&self) -> Option<u64> { self.added_at }
+    fn rating(&self) -> Option<f64> { self.audience_rating }
+    fn resolutions(&self) -> Vec<&str> {
+        self.media.iter().flatten().filter_map(|m| m.video_resolution.as_deref()).collect()
+    }
+    fn is_processed(&self) -> bool { self.has_label("Rustizarr") }
+}
+
+impl Listable for PlexShow {
+    fn title(&self) -> &str { &self.title }
+    fn year(&self) -> Option<u32> { self.year }
+    fn added_at(&self) -> Option<u64> { self.added_at }
+    fn rating(&self) -> Option<f64> { self.audience_rating }
+    fn resolutions(&self) -> Vec<&str> {
+        self.media.iter().flatten().filter_map(|m| m.video_resolution.as_deref()).collect()
+    }
+    fn is_processed(&self) -> bool { self.has_label("Rustizarr") }
+}
+
+impl ListQuery {
+    fn matches<T: Listable>(&self, item: &T, needle: Option<&str>) -> bool {
+        if self.processed.is_some_and(|p| item.is_processed() != p) {
+            return false;
+        }
+        if needle.is_some_and(|n| !item.title().to_lowercase().contains(n)) {
+            return false;
+        }
+        if self.year.is_some_and(|y| item.year() != Some(y)) {
+            return false;
+        }
+        if let Some(resolution) = &self.resolution {
+            if !item.resolutions().iter().any(|r| r.eq_ignore_ascii_case(resolution)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Filtre, trie et découpe la liste. Renvoie la page et le nombre total après filtrage
+    pub fn apply<T: Listable>(&self, items: Vec<T>) -> (Vec<T>, usize) {
+        let needle = self.q.as_ref().map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty());
+        let mut items: Vec<T> = items.into_iter()
+            .filter(|item| self.matches(item, needle.as_deref()))
+            .collect();
+
+        match self.sort {
+            SortKey::Title => items.sort_by_cached_key(|i| i.title().to_lowercase()),
+            SortKey::Added => items.sort_by_key(|i| std::cmp::Reverse(i.added_at().unwrap_or(0))),
+            SortKey::Rating => items.sort_by(|a, b| {
+                b.rating().unwrap_or(0.0).total_cmp(&a.rating().unwrap_or(0.0))
+            }),
+        }
+
+        let total = items.len();
+        if self.page.is_none() && self.page_size.is_none() {
+            return (items, total);
+        }
+
+        let page_size = self.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let start = self.page.unwrap_or(1).max(1).saturating_sub(1).saturating_mul(page_size);
+        let page = items.into_iter().skip(start).take(page_size).collect();
+        (page, total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        title: &'static str,
+        year: u32,
+        added_at: Option<u64>,
+        rating: Option<f64>,
+        resolution: &'static str,
+        processed: bool,
+    }
+
+    impl Listable for Item {
+        fn title(&self) -> &str { self.title }
+        fn year(&self) -> Option<u32> { Some(self.year) }
+        fn added_at(&self) -> Option<u64> { self.added_at }
+        fn rating(&self) -> Option<f64> { self.rating }
+        fn resolutions(&self) -> Vec<&str> { vec![self.resolution] }
+        fn is_processed(&self) -> bool { self.processed }
+    }
+
+    fn catalog() -> Vec<Item> {
+        vec![
+            Item { title: "Dune", year: 2021, added_at: Some(30), rating: Some(8.0), resolution: "4k", processed: true },
+            Item { title: "alien", year: 1979, added_at: Some(10), rating: Some(8.5), resolution: "1080", processed: false },
+            Item { title: "Blade Runner", year: 1982, added_at: None, rating: None, resolution: "4K", processed: true },
+            Item { title: "Dune Part Two", year: 2024, added_at: Some(40), rating: Some(8.6), resolution: "1080", processed: false },
+        ]
+    }
+
+    fn titles(items: &[Item]) -> Vec<&'static str> {
+        items.iter().map(|i| i.title).collect()
+    }
+
+    #[test]
+    fn filters_combine() {
+        let query = ListQuery { q: Some("  DUNE ".into()), processed: Some(false), ..ListQuery::default() };
+        let (items, total) = query.apply(catalog());
+        assert_eq!(titles(&items), ["Dune Part Two"]);
+        assert_eq!(total, 1);
+
+        let query = ListQuery { resolution: Some("4k".into()), year: Some(1982), ..ListQuery::default() };
+        assert_eq!(titles(&query.apply(catalog()).0), ["Blade Runner"]);
+    }
+
+    #[test]
+    fn sorts_by_title_added_and_rating() {
+        let by = |sort| titles(&ListQuery { sort, ..ListQuery::default() }.apply(catalog()).0);
+        assert_eq!(by(SortKey::Title), ["alien", "Blade Runner", "Dune", "Dune Part Two"]);
+        assert_eq!(by(SortKey::Added), ["Dune Part Two", "Dune", "alien", "Blade Runner"]);
+        assert_eq!(by(SortKey::Rating), ["Dune Part Two", "alien", "Dune", "Blade Runner"]);
+    }
+
+    #[test]
+    fn pages_after_filtering() {
+        let query = ListQuery { page: Some(2), page_size: Some(3), ..ListQuery::default() };
+        let (items, total) = query.apply(catalog());
+        assert_eq!(titles(&items), ["Dune Part Two"]);
+        assert_eq!(total, 4);
+
+        // page 0 ramenée à 1, page_size 0 ramenée à 1
+        let query = ListQuery { page: Some(0), page_size: Some(0), ..ListQuery::default() };
+        let (items, total) = query.apply(catalog());
+        assert_eq!(titles(&items), ["alien"]);
+        assert_eq!(total, 4);
+
+        let query = ListQuery { page: Some(9), ..ListQuery::default() };
+        assert!(query.apply(catalog()).0.is_empty());
+    }
+}
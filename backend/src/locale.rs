@@ -0,0 +1,184 @@
+// backend/src/locale.rs
+// Localisation : catalogues de messages (textes des posters, sortie CLI) et langue TMDB
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tracing::{debug, warn};
+
+/// Langue par défaut (comportement historique)
+const DEFAULT_LOCALE: &str = "fr";
+
+/// Catalogues embarqués dans le binaire
+const BUILTIN: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.json")),
+    ("fr", include_str!("../locales/fr.json")),
+];
+
+static CURRENT: OnceLock<Locale> = OnceLock::new();
+
+pub struct Locale {
+    /// Code demandé (ex: "fr", "en", "pt-BR")
+    code: String,
+    messages: HashMap<String, String>,
+    /// Catalogue anglais : repli pour les clés absentes
+    fallback: HashMap<String, String>,
+}
+
+impl Locale {
+    /// Charge une langue : catalogue embarqué, surchargé par `LOCALES_DIR/<code>.json`
+    pub fn load(code: &str) -> Self {
+        let language = primary_subtag(code);
+        let mut locale = Self {
+            code: code.to_string(),
+            messages: builtin(code).or_else(|| builtin(&language)).unwrap_or_default(),
+            fallback: builtin("en").unwrap_or_default(),
+        };
+
+        // Les messages ci-dessous passent par le catalogue embarqué (la surcharge n'est pas encore lue)
+        match read_catalog_file(code).or_else(|| read_catalog_file(&language)) {
+            Some((path, Ok(extra))) => {
+                debug!(path = %path.display(), "Catalogue de langue chargé");
+                locale.messages.extend(extra);
+            }
+            Some((path, Err(e))) => {
+                warn!(path = %path.display(), error = %e, "{}", locale.text("log.locale.invalid_file"));
+            }
+            None if locale.messages.is_empty() => {
+                warn!(locale = code, "{}", locale.text("log.locale.missing"));
+            }
+            None => {}
+        }
+        locale
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Code ISO 639-1 (ex: "pt" pour "pt-BR"), utilisé pour les images TMDB
+    pub fn language(&self) -> String {
+        primary_subtag(&self.code)
+    }
+
+    /// Message brut ; la clé elle-même si elle n'existe dans aucun catalogue
+    pub fn text(&self, key: &str) -> String {
+        self.messages.get(key)
+            .or_else(|| self.fallback.get(key))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Message avec substitution des paramètres `{nom}`
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        args.iter().fold(self.text(key), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+
+    /// Nombre décimal avec le séparateur de la langue (7.5 → "7,5" en français)
+    pub fn decimal(&self, value: f64, precision: usize) -> String {
+        let formatted = format!("{:.*}", precision, value);
+        let separator = self.text("number.decimal_separator");
+        if separator == "." || separator == "number.decimal_separator" {
+            formatted
+        } else {
+            formatted.replace('.', &separator)
+        }
+    }
+}
+
+/// Initialise la langue globale (LOCALE si `code` est absent). Sans effet après le premier appel
+pub fn init(code: Option<&str>) -> &'static Locale {
+    CURRENT.get_or_init(|| {
+        let code = code.map(str::to_string)
+            .or_else(|| std::env::var("LOCALE").ok())
+            .filter(|c| !c.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+        debug!(locale = %code, "Langue chargée");
+        Locale::load(code.trim())
+    })
+}
+
+/// Langue courante (initialisée depuis LOCALE au premier accès)
+pub fn current() -> &'static Locale {
+    init(None)
+}
+
+/// Raccourci : message de la langue courante
+pub fn t(key: &str) -> String {
+    current().text(key)
+}
+
+/// Raccourci : message paramétré de la langue courante
+pub fn tf(key: &str, args: &[(&str, &str)]) -> String {
+    current().format(key, args)
+}
+
+fn primary_subtag(code: &str) -> String {
+    code.split(['-', '_']).next().unwrap_or(code).to_lowercase()
+}
+
+fn builtin(code: &str) -> Option<HashMap<String, String>> {
+    let (_, raw) = BUILTIN.iter().find(|(c, _)| c.eq_ignore_ascii_case(code))?;
+    serde_json::from_str(raw).ok()
+}
+
+/// Catalogue additionnel : `LOCALES_DIR/<code>.json` (nouvelle langue ou surcharge)
+fn read_catalog_file(code: &str) -> Option<(PathBuf, serde_json::Result<HashMap<String, String>>)> {
+    let dir = PathBuf::from(std::env::var("LOCALES_DIR").ok()?);
+    let path = dir.join(format!("{}.json", code));
+    let raw = std::fs::read_to_string(&path).ok()?;
+    let catalog = serde_json::from_str(&raw);
+    Some((path, catalog))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn keys(code: &str) -> BTreeSet<String> {
+        builtin(code).unwrap().into_keys().collect()
+    }
+
+    /// Clés littérales passées à `t("…")` / `tf("…", …)` dans les sources
+    fn used_keys() -> BTreeSet<String> {
+        let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut used = BTreeSet::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let source = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            for call in ["locale::t(\"", "locale::tf(\""] {
+                for (start, _) in source.match_indices(call) {
+                    let rest = &source[start + call.len()..];
+                    let key = &rest[..rest.find('"').unwrap()];
+                    if !key.contains('{') {
+                        used.insert(key.to_string());
+                    }
+                }
+            }
+        }
+        used
+    }
+
+    #[test]
+    fn catalogs_share_the_same_keys() {
+        let en = keys("en");
+        let fr = keys("fr");
+        assert_eq!(en.difference(&fr).collect::<Vec<_>>(), Vec::<&String>::new(), "absentes de fr.json");
+        assert_eq!(fr.difference(&en).collect::<Vec<_>>(), Vec::<&String>::new(), "absentes de en.json");
+    }
+
+    #[test]
+    fn every_used_key_exists() {
+        let en = keys("en");
+        let missing: Vec<String> = used_keys().into_iter().filter(|k| !en.contains(k)).collect();
+        assert!(missing.is_empty(), "clés absentes du catalogue : {:?}", missing);
+    }
+
+    #[test]
+    fn format_replaces_named_arguments() {
+        let locale = Locale::load("en");
+        assert_eq!(locale.format("season.title", &[("show", "Dark"), ("n", "2")]), "Dark - Season 2");
+        assert_eq!(locale.text("missing.key"), "missing.key");
+    }
+}
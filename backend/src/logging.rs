@@ -0,0 +1,55 @@
+// backend/src/logging.rs
+// Initialisation des logs (tracing) : niveau via RUST_LOG ou verbosité, format texte ou JSON
+use tracing_subscriber::{fmt, EnvFilter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Texte lisible (console)
+    #[default]
+    Pretty,
+    /// Une ligne JSON par événement (agrégateurs de logs)
+    Json,
+}
+
+impl LogFormat {
+    /// Lit le format depuis la variable LOG_FORMAT ("json" ou "pretty")
+    pub fn from_env() -> Self {
+        match std::env::var("LOG_FORMAT").unwrap_or_default().to_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Pretty,
+        }
+    }
+}
+
+/// Convertit les flags -v / -q en niveau de log
+pub fn level_from_verbosity(verbose: u8, quiet: u8) -> Option<&'static str> {
+    match (verbose, quiet) {
+        (0, 0) => None,
+        (v, 0) if v >= 2 => Some("trace"),
+        (1, 0) => Some("debug"),
+        (_, q) if q >= 2 => Some("error"),
+        _ => Some("warn"),
+    }
+}
+
+/// Construit le filtre : niveau explicite (flags CLI) > RUST_LOG > "info"
+///
+/// Un niveau explicite ne s'applique qu'aux crates du projet, les dépendances restent en "warn".
+fn build_filter(level: Option<&str>) -> EnvFilter {
+    if let Some(level) = level {
+        return EnvFilter::new(format!("warn,backend={0},server={0},rustizarr={0}", level));
+    }
+
+    EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("warn,backend=info,server=info,rustizarr=info,tower_http=info"))
+}
+
+/// Installe le subscriber global (à appeler une seule fois au démarrage)
+pub fn init(level: Option<&str>, format: LogFormat) {
+    let filter = build_filter(level);
+
+    match format {
+        LogFormat::Pretty => fmt().with_env_filter(filter).with_target(false).init(),
+        LogFormat::Json => fmt().json().with_env_filter(filter).with_current_span(true).with_span_list(false).init(),
+    }
+}
@@ -1,24 +1,41 @@
-mod plex;
-mod tmdb;
-mod image_ops;
-mod processor;
+mod auth;
+mod cache;
+mod listing;
+mod scans;
+mod webhooks;
 
 use axum::{
     routing::{get, post},
+    middleware,
     Json, Router, Extension,
-    extract::{Path as AxumPath, Multipart},
+    extract::{Path as AxumPath, Multipart, Query},
     body::Body,
-    response::IntoResponse,
+    response::{IntoResponse, sse::{KeepAlive, Sse}},
     http::{HeaderMap, header, StatusCode},
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use std::time::{Duration, Instant};
 use std::env;
-use tower_http::cors::CorsLayer;
-use plex::{PlexClient, PlexMovie, PlexShow};
-use tmdb::TmdbClient;
+use tower_http::services::ServeDir;
+use tower_http::trace::TraceLayer;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use backend::plex::{ExternalId, PlexClient, PlexGuidMatch, PlexMovie, PlexSeason, PlexShow};
+use backend::tmdb::TmdbClient;
+use backend::library_index::LibraryIndex;
+use backend::locale;
+use backend::logging::{self, LogFormat};
+use backend::metrics;
+use backend::processor::{self, ShowSeasons};
+use backend::report::{MediaKind, ProcessReport};
+use auth::AuthConfig;
+use cache::LibraryCache;
+use listing::ListQuery;
+use scans::{ScanHandle, ScanKind, ScanRegistry, ScanSnapshot};
+use webhooks::{
+    ExternalItem, ExternalKind, PendingRenders, PlexWebhookPayload, PollResult, PollSettings,
+    RadarrPayload, RenderTarget, SonarrPayload, TautulliPayload, WebhookAction, WebhookRoutes,
+};
 
 #[derive(Clone, Serialize, Deserialize)]
 struct AppConfig {
@@ -30,306 +47,629 @@ struct AppConfig {
 
 struct AppState {
     config: Mutex<AppConfig>,
-    library_cache: Mutex<LibraryCache>,
+    library_cache: Mutex<LibraryCache<PlexMovie>>,
+    shows_cache: Mutex<LibraryCache<PlexShow>>,
+    seasons_cache: Mutex<LibraryCache<PlexSeason>>,
+    scans: ScanRegistry,
+    webhook_routes: WebhookRoutes,
+    pending_renders: PendingRenders,
+    webhook_poll: PollSettings,
+    /// Traitement des saisons après celui de la série (CASCADE_SEASONS)
+    cascade_seasons: bool,
 }
 
-struct LibraryCache {
-    movies: Vec<PlexMovie>,
-    last_update: Option<Instant>,
-    cache_duration: Duration,
-}
+// ==================================================================================
+// HANDLERS - WEBHOOK
+// ==================================================================================
+
+async fn handle_plex_webhook(
+    Extension(state): Extension<Arc<AppState>>,
+    mut multipart: Multipart,
+) {
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name().unwrap_or("") != "payload" {
+            continue;
+        }
+        let Ok(text) = field.text().await else { continue };
+        let Ok(payload) = serde_json::from_str::<PlexWebhookPayload>(&text) else {
+            warn!("{}", locale::t("log.webhook.unreadable"));
+            continue;
+        };
 
-impl LibraryCache {
-    fn new() -> Self {
-        Self {
-            movies: Vec::new(),
-            last_update: None,
-            cache_duration: Duration::from_secs(300),
+        let media_type = payload.metadata.as_ref().map(|m| m.media_type.as_str()).unwrap_or("none");
+        metrics::record_webhook_event(state.webhook_routes.metric_event(&payload.event), media_type);
+
+        if state.webhook_routes.action_for(&payload.event) == WebhookAction::Ignore {
+            debug!(event = %payload.event, media_type, "Événement webhook ignoré");
+            continue;
         }
-    }
 
-    fn is_valid(&self) -> bool {
-        if let Some(last) = self.last_update {
-            last.elapsed() < self.cache_duration
-        } else {
-            false
+        let Some(meta) = payload.metadata else { continue };
+        for target in webhooks::targets_for(&meta) {
+            schedule_render(&state, target, &payload.event);
         }
     }
+}
 
-    fn update(&mut self, movies: Vec<PlexMovie>) {
-        self.movies = movies;
-        self.last_update = Some(Instant::now());
+/// Planifie le rendu d'un élément (ignoré s'il est déjà en attente)
+fn schedule_render(state: &Arc<AppState>, target: RenderTarget, event: &str) {
+    if !state.pending_renders.claim(&target) {
+        debug!(%target, "Rendu déjà planifié, ignoré");
+        return;
     }
 
-    fn get(&self) -> Option<Vec<PlexMovie>> {
-        if self.is_valid() {
-            Some(self.movies.clone())
-        } else {
-            None
+    info!(%target, event, "{}", locale::t("log.webhook.scheduled"));
+    let state_clone = state.clone();
+    let span = info_span!("webhook", rating_key = %target.rating_key(), kind = target.kind());
+    tokio::spawn(run_webhook_render(state_clone, target).instrument(span));
+}
+
+/// Attend la fin de l'analyse Plex puis génère le poster, avec nouvelles tentatives
+async fn run_webhook_render(state: Arc<AppState>, target: RenderTarget) {
+    let config = state.config.lock().await;
+    let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
+    let tmdb = TmdbClient::new(config.tmdb_key.clone());
+    drop(config);
+
+    let poll = &state.webhook_poll;
+
+    for attempt in 1..=poll.max_attempts {
+        debug!(attempt, "Attente de l'analyse Plex");
+
+        let ready = match &target {
+            RenderTarget::Movie(key) => {
+                match webhooks::poll_until_ready(poll, || plex.get_item_details(key), PlexMovie::is_analyzed).await {
+                    PollResult::Ready(movie) => Some(WebhookItem::Movie(movie)),
+                    PollResult::Incomplete => None,
+                }
+            },
+            RenderTarget::Show(key) => {
+                match webhooks::poll_until_ready(poll, || plex.get_show_details(key), PlexShow::is_matched).await {
+                    PollResult::Ready(show) => Some(WebhookItem::Show(show)),
+                    PollResult::Incomplete => None,
+                }
+            },
+            RenderTarget::Season(key) => {
+                match webhooks::poll_until_ready(poll, || plex.get_season_details(key), |_| true).await {
+                    PollResult::Ready(season) => Some(WebhookItem::Season(season)),
+                    PollResult::Incomplete => None,
+                }
+            },
+        };
+
+        if let Some(item) = ready {
+            // Les événements reçus à partir d'ici déclencheront un nouveau rendu
+            state.pending_renders.release(&target);
+            render_webhook_item(&state, &plex, &tmdb, item).await;
+            return;
         }
-    }
 
-    fn invalidate(&mut self) {
-        self.last_update = None;
+        if attempt < poll.max_attempts {
+            warn!(attempt, retry_in_secs = poll.retry_delay.as_secs(), "{}", locale::t("log.webhook.retry"));
+            tokio::time::sleep(poll.retry_delay).await;
+        }
     }
+
+    state.pending_renders.release(&target);
+    warn!(attempts = poll.max_attempts, "{}", locale::t("log.webhook.gave_up"));
 }
 
-#[derive(Deserialize, Debug)]
-struct PlexWebhookPayload {
-    event: String,
-    #[serde(rename = "Metadata")]
-    metadata: Option<WebhookMetadata>,
+/// Élément dont les métadonnées Plex sont complètes
+enum WebhookItem {
+    Movie(PlexMovie),
+    Show(PlexShow),
+    Season(PlexSeason),
 }
 
-#[derive(Deserialize, Debug)]
-struct WebhookMetadata {
-    #[serde(rename = "ratingKey")]
-    rating_key: String,
-    #[serde(rename = "type")]
-    media_type: String,
+async fn render_webhook_item(state: &Arc<AppState>, plex: &PlexClient, tmdb: &TmdbClient, item: WebhookItem) {
+    let render_config = processor::RenderConfig::load();
+    let report = match item {
+        WebhookItem::Movie(movie) => {
+            let report = processor::process_movie(plex, tmdb, &render_config, movie).await;
+            if report.outcome.is_rendered() {
+                debug!("Invalidation du cache suite au traitement");
+                let mut cache = state.library_cache.lock().await;
+                cache.invalidate();
+            }
+            report
+        },
+        WebhookItem::Show(show) => {
+            let report = processor::process_show(plex, tmdb, &render_config, show.clone()).await;
+            if report.outcome.is_rendered() {
+                state.shows_cache.lock().await.invalidate();
+            }
+            if state.cascade_seasons {
+                match processor::process_show_seasons(plex, tmdb, &render_config, &show, false).await {
+                    Ok(reports) => {
+                        let rendered = reports.iter().filter(|r| r.outcome.is_rendered()).count();
+                        info!(title = %show.title, total = reports.len(), rendered, "{}", locale::t("log.webhook.seasons_done"));
+                        state.seasons_cache.lock().await.invalidate();
+                    },
+                    Err(e) => warn!(title = %show.title, error = ?e, "{}", locale::t("log.webhook.seasons_failed")),
+                }
+            }
+            report
+        },
+        WebhookItem::Season(season) => {
+            let show = match plex.get_show_details(&season.show_rating_key).await {
+                Ok(show) => show,
+                Err(e) => {
+                    error!(show_rating_key = %season.show_rating_key, error = ?e, "{}", locale::t("log.webhook.parent_show_error"));
+                    return;
+                }
+            };
+
+            let Some(show_tmdb_id) = PlexClient::extract_tmdb_id_from_show(&show) else {
+                warn!(title = %show.title, "{}", locale::t("log.webhook.parent_show_no_tmdb"));
+                return;
+            };
+            let show_status = tmdb.get_show_details(&show_tmdb_id).await.ok().and_then(|details| details.status);
+
+            let report = processor::process_season(plex, tmdb, &render_config, season, &show_tmdb_id, show_status).await;
+            if report.outcome.is_rendered() {
+                state.seasons_cache.lock().await.invalidate();
+            }
+            report
+        },
+    };
+
+    info!(outcome = report.outcome.code(), total_ms = report.timings.total_ms, "{}", locale::t("log.webhook.done"));
 }
 
 // ==================================================================================
-// HANDLERS - WEBHOOK
+// HANDLERS - WEBHOOKS RADARR / SONARR / TAUTULLI
 // ==================================================================================
 
-async fn handle_plex_webhook(
+async fn handle_radarr_webhook(
     Extension(state): Extension<Arc<AppState>>,
-    mut multipart: Multipart,
-) {
-    while let Ok(Some(field)) = multipart.next_field().await {
-        if field.name().unwrap_or("") == "payload" {
-            if let Ok(text) = field.text().await {
-                if let Ok(payload) = serde_json::from_str::<PlexWebhookPayload>(&text) {
-                    if payload.event == "library.new" {
-                        if let Some(meta) = payload.metadata {
-                            if meta.media_type == "movie" {
-                                println!("🔔 Webhook : Nouveau film détecté (ID: {})", meta.rating_key);
-                                let state_clone = state.clone();
-                                tokio::spawn(async move {
-                                    process_single_movie_by_id(state_clone, meta.rating_key).await;
-                                });
-                            } else if meta.media_type == "show" {
-                                println!("🔔 Webhook : Nouvelle série détectée (ID: {})", meta.rating_key);
-                                let state_clone = state.clone();
-                                tokio::spawn(async move {
-                                    process_single_show_by_id(state_clone, meta.rating_key).await;
-                                });
-                            }
-                        }
-                    }
-                }
-            }
+    Json(payload): Json<RadarrPayload>,
+) -> StatusCode {
+    dispatch_external_event(&state, &payload.event_name(), payload.item())
+}
+
+async fn handle_sonarr_webhook(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(payload): Json<SonarrPayload>,
+) -> StatusCode {
+    dispatch_external_event(&state, &payload.event_name(), payload.item())
+}
+
+async fn handle_tautulli_webhook(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(payload): Json<TautulliPayload>,
+) -> StatusCode {
+    let event = payload.event_name();
+
+    // Rating keys fournis par Tautulli : même routage que les webhooks Plex
+    if let Some(meta) = payload.metadata() {
+        metrics::record_webhook_event(state.webhook_routes.metric_event(&event), &meta.media_type);
+        if state.webhook_routes.action_for(&event) == WebhookAction::Ignore {
+            debug!(event, "Événement webhook ignoré");
+            return StatusCode::OK;
+        }
+        for target in webhooks::targets_for(&meta) {
+            schedule_render(&state, target, &event);
         }
+        return StatusCode::ACCEPTED;
     }
+
+    dispatch_external_event(&state, &event, payload.item())
 }
 
-async fn process_single_movie_by_id(state: Arc<AppState>, rating_key: String) {
+/// Recherche l'élément dans Plex par guid (en tâche de fond) puis planifie son rendu
+fn dispatch_external_event(state: &Arc<AppState>, event: &str, item: Option<ExternalItem>) -> StatusCode {
+    let media_type = match item.as_ref().map(|i| i.kind) {
+        Some(ExternalKind::Movie) => "movie",
+        Some(ExternalKind::Show) => "show",
+        None => "none",
+    };
+    metrics::record_webhook_event(state.webhook_routes.metric_event(event), media_type);
+
+    if state.webhook_routes.action_for(event) == WebhookAction::Ignore {
+        debug!(event, "Événement webhook ignoré");
+        return StatusCode::OK;
+    }
+
+    let Some(item) = item.filter(|i| !i.guids.is_empty()) else {
+        warn!(event, "{}", locale::t("log.webhook.no_ids"));
+        return StatusCode::OK;
+    };
+
+    info!(event, title = %item.title, guids = ?item.guids, "{}", locale::t("log.webhook.external"));
+    let span = info_span!("webhook_lookup", title = %item.title);
+    tokio::spawn(resolve_external_item(state.clone(), item, event.to_string()).instrument(span));
+
+    StatusCode::ACCEPTED
+}
+
+/// Attend que l'élément apparaisse dans Plex (le scan de la bibliothèque peut prendre du temps)
+async fn resolve_external_item(state: Arc<AppState>, item: ExternalItem, event: String) {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
-    let tmdb = TmdbClient::new(config.tmdb_key.clone());
+    let library_id = match item.kind {
+        ExternalKind::Movie => config.library_id.clone(),
+        ExternalKind::Show => env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string()),
+    };
     drop(config);
 
-    println!("⏳ Attente de 10s pour l'analyse Plex...");
-    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-
-    match plex.get_item_details(&rating_key).await {
-        Ok(movie) => {
-            if let Ok(_) = processor::process_movie(&plex, &tmdb, movie).await {
-                println!("🔄 Invalidation du cache suite au traitement...");
-                let mut cache = state.library_cache.lock().await;
-                cache.invalidate();
+    let resolve = || webhooks::resolve_targets(&plex, &library_id, &item);
+    let targets = match webhooks::poll_until_ready(&state.webhook_poll, resolve, |t| webhooks::is_fully_resolved(&item, t)).await {
+        PollResult::Ready(targets) => targets,
+        PollResult::Incomplete => {
+            // Dernier essai : on traite ce qui a été trouvé (ex. série présente, saison pas encore scannée)
+            match webhooks::resolve_targets(&plex, &library_id, &item).await {
+                Ok(targets) if !targets.is_empty() => {
+                    warn!(found = targets.len(), "{}", locale::t("log.webhook.partial"));
+                    targets
+                },
+                Ok(_) => {
+                    warn!(guids = ?item.guids, "{}", locale::t("log.webhook.not_found"));
+                    return;
+                },
+                Err(e) => {
+                    error!(error = ?e, "{}", locale::t("log.plex.guid_lookup_error"));
+                    return;
+                },
             }
         },
-        Err(e) => println!("❌ Erreur Webhook (Détails film) : {:?}", e),
+    };
+
+    for target in targets {
+        schedule_render(&state, target, &event);
+    }
+}
+
+// ==================================================================================
+// SCANS ASYNCHRONES
+// ==================================================================================
+
+#[derive(Deserialize)]
+struct StartScanRequest {
+    kind: ScanKind,
+    #[serde(default)]
+    force: bool,
+    library_id: Option<String>,
+    /// Scan de séries : traiter aussi leurs saisons (défaut : CASCADE_SEASONS)
+    seasons: Option<bool>,
+    /// Scan de saisons : rating key Plex de la série
+    show_id: Option<String>,
+}
+
+impl StartScanRequest {
+    fn new(kind: ScanKind) -> Self {
+        Self { kind, force: false, library_id: None, seasons: None, show_id: None }
     }
 }
 
-async fn process_single_show_by_id(state: Arc<AppState>, rating_key: String) {
+/// Démarre un scan en tâche de fond et renvoie immédiatement son état initial
+fn start_scan(state: Arc<AppState>, request: StartScanRequest) -> axum::response::Response {
+    let StartScanRequest { kind, force, library_id, seasons, show_id } = request;
+    if kind == ScanKind::Seasons && show_id.is_none() {
+        return (StatusCode::BAD_REQUEST, locale::t("api.error.show_id_required")).into_response();
+    }
+    let seasons = seasons.unwrap_or(state.cascade_seasons);
+
+    let scan = match state.scans.create(kind) {
+        Ok(scan) => scan,
+        Err(active) => {
+            warn!(?kind, scan_id = %active.id, "{}", locale::t("log.scan.already_running"));
+            return (StatusCode::CONFLICT, Json(active.snapshot())).into_response();
+        }
+    };
+
+    info!(?kind, scan_id = %scan.id, force, "{}", locale::t("log.scan.started"));
+    let snapshot = scan.snapshot();
+
+    let span = info_span!("scan", scan_id = %scan.id, ?kind);
+    tokio::spawn(async move {
+        match kind {
+            ScanKind::Movies => run_movies_scan(state, scan, force, library_id).await,
+            ScanKind::Shows => run_shows_scan(state, scan, force, library_id, seasons).await,
+            ScanKind::Seasons => run_seasons_scan(state, scan, force, show_id.unwrap_or_default()).await,
+        }
+    }.instrument(span));
+
+    (StatusCode::ACCEPTED, Json(snapshot)).into_response()
+}
+
+async fn run_movies_scan(state: Arc<AppState>, scan: Arc<ScanHandle>, force: bool, library_id: Option<String>) {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
     let tmdb = TmdbClient::new(config.tmdb_key.clone());
+    let library_id = library_id.unwrap_or_else(|| config.library_id.clone());
     drop(config);
 
-    println!("⏳ Attente de 10s pour l'analyse Plex...");
-    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    // L'index ne relit en détail que les films nouveaux ou modifiés depuis la dernière synchro
+    let synced = {
+        let mut cache = state.library_cache.lock().await;
+        if cache.index.library_id() == library_id {
+            cache.sync(&plex).await.map(|_| cache.index.items())
+        } else {
+            let mut index = LibraryIndex::<PlexMovie>::new(&library_id);
+            index.sync(&plex).await.map(|_| index.items())
+        }
+    };
 
-    match plex.get_show_details(&rating_key).await {
-        Ok(show) => {
-            if let Ok(_) = processor::process_show(&plex, &tmdb, show).await {
-                println!("✅ Série traitée avec succès");
-            }
-        },
-        Err(e) => println!("❌ Erreur Webhook (Détails série) : {:?}", e),
+    let movies = match synced {
+        Ok(movies) => movies,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.plex.error"));
+            scan.fail(format!("Erreur Plex: {:#}", e));
+            return;
+        }
+    };
+
+    let total = movies.len();
+    info!(total, "{}", locale::t("log.scan.movies"));
+    let render_config = processor::RenderConfig::load();
+    scan.start(total);
+
+    for (index, movie) in movies.into_iter().enumerate() {
+        if scan.is_cancelled() {
+            info!("{}", locale::t("log.scan.cancelled"));
+            break;
+        }
+
+        info!(index = index + 1, total, title = %movie.title, "{}", locale::t("log.scan.item"));
+        scan.begin_item(&movie.title);
+
+        let report = if !force && movie.has_label("Rustizarr") {
+            debug!("Film déjà traité (label 'Rustizarr'), ignoré");
+            ProcessReport::already_processed(MediaKind::Movie, &movie.rating_key, &movie.title)
+        } else {
+            debug!("Nouveau film détecté, lancement du traitement");
+            processor::process_movie(&plex, &tmdb, &render_config, movie).await
+        };
+
+        scan.finish_item(report);
     }
-}
 
-// ==================================================================================
-// HANDLERS - SCAN MANUEL FILMS
-// ==================================================================================
+    scan.finish();
 
-async fn run_full_library_scan(Extension(state): Extension<Arc<AppState>>) -> Json<String> {
+    info!("{}", locale::t("log.scan.done_invalidate"));
+    let mut cache = state.library_cache.lock().await;
+    cache.invalidate();
+}
+
+async fn run_shows_scan(state: Arc<AppState>, scan: Arc<ScanHandle>, force: bool, library_id: Option<String>, seasons: bool) {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
     let tmdb = TmdbClient::new(config.tmdb_key.clone());
-    let library_id = config.library_id.clone();
+    let shows_library_id = library_id
+        .unwrap_or_else(|| env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string()));
     drop(config);
 
-    println!("Connexion Plex...");
-
-    match plex.get_library_items(&library_id).await {
-        Ok(movies) => {
-            let total = movies.len();
-            println!("🔍 Analyse de la bibliothèque : {} films trouvés.", total);
-            let mut report = String::new();
-
-            for (index, summary_movie) in movies.iter().enumerate() {
-                println!("---------------------------------------------------");
-                println!("🔎 Analyse ({}/{}) : {}", index + 1, total, summary_movie.title);
-
-                match plex.get_item_details(&summary_movie.rating_key).await {
-                    Ok(movie) => {
-                        if movie.has_label("Rustizarr") {
-                            println!("   ⏭️  SKIP : Film déjà traité (Label 'Rustizarr' trouvé).");
-                            continue;
-                        }
-
-                        println!("   ✨ Nouveau film détecté, lancement du traitement...");
-                        
-                        if let Ok(msg) = processor::process_movie(&plex, &tmdb, movie).await {
-                            report.push_str(&msg);
-                        }
-                    },
-                    Err(e) => println!("   ⚠️ Erreur récupération détails: {:?}, passage au suivant.", e),
-                };
+    let synced = {
+        let mut cache = state.shows_cache.lock().await;
+        if cache.index.library_id() == shows_library_id {
+            cache.sync(&plex).await.map(|_| cache.index.items())
+        } else {
+            let mut index = LibraryIndex::<PlexShow>::new(&shows_library_id);
+            index.sync(&plex).await.map(|_| index.items())
+        }
+    };
+
+    let shows = match synced {
+        Ok(shows) => shows,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.plex.error"));
+            scan.fail(format!("Erreur Plex: {:#}", e));
+            return;
+        }
+    };
+
+    let total = shows.len();
+    info!(total, seasons, "{}", locale::t("log.scan.shows"));
+    let render_config = processor::RenderConfig::load();
+    scan.start(total);
+
+    for (index, show) in shows.iter().enumerate() {
+        if scan.is_cancelled() {
+            info!("{}", locale::t("log.scan.cancelled"));
+            break;
+        }
+
+        info!(index = index + 1, total, title = %show.title, "{}", locale::t("log.scan.item"));
+        scan.begin_item(&show.title);
+
+        let report = if !force && show.has_label("Rustizarr") {
+            debug!("Série déjà traitée, ignorée");
+            ProcessReport::already_processed(MediaKind::Show, &show.rating_key, &show.title)
+        } else {
+            debug!("Nouvelle série détectée, lancement du traitement");
+            processor::process_show(&plex, &tmdb, &render_config, show.clone()).await
+        };
+
+        scan.finish_item(report);
+
+        // Les saisons sont comptées au fil de l'eau : leur nombre n'est connu qu'ici
+        if seasons && !scan.is_cancelled() {
+            match processor::process_show_seasons(&plex, &tmdb, &render_config, show, force).await {
+                Ok(reports) => {
+                    scan.add_total(reports.len());
+                    for report in reports {
+                        scan.finish_item(report);
+                    }
+                },
+                Err(e) => warn!(title = %show.title, error = ?e, "{}", locale::t("log.seasons.failed")),
             }
-            
-            println!("🔄 Scan terminé, invalidation du cache...");
-            let mut cache = state.library_cache.lock().await;
-            cache.invalidate();
-            
-            Json(report)
         }
-        Err(e) => Json(format!("Erreur Plex: {:?}", e))
     }
-}
 
-// ==================================================================================
-// HANDLERS - SCAN MANUEL SÉRIES
-// ==================================================================================
+    scan.finish();
+
+    info!("{}", locale::t("log.scan.done_invalidate"));
+    state.shows_cache.lock().await.invalidate();
+    state.seasons_cache.lock().await.invalidate();
+}
 
-async fn run_full_shows_scan(Extension(state): Extension<Arc<AppState>>) -> Json<String> {
+async fn run_seasons_scan(state: Arc<AppState>, scan: Arc<ScanHandle>, force: bool, show_id: String) {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
     let tmdb = TmdbClient::new(config.tmdb_key.clone());
-    let shows_library_id = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
     drop(config);
 
-    println!("🔍 Scan des séries...");
-
-    match plex.get_shows_library_items(&shows_library_id).await {
-        Ok(shows) => {
-            let total = shows.len();
-            println!("🔍 {} séries trouvées.", total);
-            let mut report = String::new();
+    let show = match plex.get_show_details(&show_id).await {
+        Ok(show) => show,
+        Err(e) => {
+            error!(show_id, error = ?e, "{}", locale::t("log.plex.show_error"));
+            scan.fail(format!("Erreur Plex: {:#}", e));
+            return;
+        }
+    };
 
-            for (index, show) in shows.iter().enumerate() {
-                println!("---------------------------------------------------");
-                println!("🔎 Analyse ({}/{}) : {}", index + 1, total, show.title);
+    let show_seasons = match ShowSeasons::load(&plex, &tmdb, &show).await {
+        Ok(show_seasons) => show_seasons,
+        Err(e) => {
+            warn!(title = %show.title, error = ?e, "{}", locale::t("log.seasons.failed"));
+            scan.fail(format!("{:#}", e));
+            return;
+        }
+    };
 
-                if show.has_label("Rustizarr") {
-                    println!("   ⏭️  SKIP : Série déjà traitée");
-                    continue;
-                }
+    let total = show_seasons.seasons.len();
+    info!(title = %show.title, total, "{}", locale::t("log.scan.seasons"));
+    let render_config = processor::RenderConfig::load();
+    scan.start(total);
 
-                println!("   ✨ Nouvelle série détectée, traitement...");
-                
-                if let Ok(msg) = processor::process_show(&plex, &tmdb, show.clone()).await {
-                    report.push_str(&msg);
-                    report.push('\n');
-                }
-            }
-            
-            Json(report)
+    let mut rendered = false;
+    for season in &show_seasons.seasons {
+        if scan.is_cancelled() {
+            info!("{}", locale::t("log.scan.cancelled"));
+            break;
         }
-        Err(e) => Json(format!("Erreur Plex: {:?}", e))
+
+        scan.begin_item(&show_seasons.title(season));
+        let report = show_seasons.process(&plex, &tmdb, &render_config, season, force).await;
+        rendered |= report.outcome.is_rendered();
+        scan.finish_item(report);
+    }
+
+    scan.finish();
+
+    if rendered {
+        state.seasons_cache.lock().await.invalidate();
     }
 }
 
 // ==================================================================================
-// HANDLERS - API FILMS
+// HANDLERS - SCANS
 // ==================================================================================
 
-async fn get_library_json(Extension(state): Extension<Arc<AppState>>) -> Json<Vec<PlexMovie>> {
-    {
-        let cache = state.library_cache.lock().await;
-        if let Some(cached_movies) = cache.get() {
-            let count_processed = cached_movies.iter()
-                .filter(|m| m.has_label("Rustizarr"))
-                .count();
-            
-            println!("💾 Cache HIT : {} films (dont {} traités)", 
-                cached_movies.len(), count_processed);
-            
-            return Json(cached_movies);
-        }
+async fn create_scan(
+    Extension(state): Extension<Arc<AppState>>,
+    Json(request): Json<StartScanRequest>,
+) -> axum::response::Response {
+    start_scan(state, request)
+}
+
+async fn list_scans(Extension(state): Extension<Arc<AppState>>) -> Json<Vec<ScanSnapshot>> {
+    Json(state.scans.list())
+}
+
+async fn get_scan(
+    AxumPath(id): AxumPath<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    match state.scans.get(&id) {
+        Some(scan) => Json(serde_json::json!({
+            "scan": scan.snapshot(),
+            "results": scan.results(),
+        })).into_response(),
+        None => (StatusCode::NOT_FOUND, locale::t("api.error.scan_not_found")).into_response(),
+    }
+}
+
+async fn stream_scan_events(
+    AxumPath(id): AxumPath<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    match state.scans.get(&id) {
+        Some(scan) => Sse::new(scan.event_stream())
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+        None => (StatusCode::NOT_FOUND, locale::t("api.error.scan_not_found")).into_response(),
     }
+}
+
+async fn cancel_scan(
+    AxumPath(id): AxumPath<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    match state.scans.get(&id) {
+        Some(scan) => {
+            info!(scan_id = %id, "{}", locale::t("log.scan.cancel_requested"));
+            scan.cancel();
+            (StatusCode::ACCEPTED, Json(scan.snapshot())).into_response()
+        },
+        None => (StatusCode::NOT_FOUND, locale::t("api.error.scan_not_found")).into_response(),
+    }
+}
+
+/// Ancienne route `/scan` : lance désormais un scan asynchrone
+async fn run_full_library_scan(Extension(state): Extension<Arc<AppState>>) -> axum::response::Response {
+    start_scan(state, StartScanRequest::new(ScanKind::Movies))
+}
+
+/// Ancienne route `/scan-shows` : lance désormais un scan asynchrone
+async fn run_full_shows_scan(Extension(state): Extension<Arc<AppState>>) -> axum::response::Response {
+    start_scan(state, StartScanRequest::new(ScanKind::Shows))
+}
 
-    println!("🔄 Cache MISS : Rechargement des données...");
+// ==================================================================================
+// HANDLERS - API FILMS
+// ==================================================================================
 
+/// Réponse paginée : la liste reste un tableau JSON, le total filtré passe dans `X-Total-Count`
+fn list_response<T: Serialize>(items: Vec<T>, total: usize) -> axum::response::Response {
+    ([(listing::TOTAL_COUNT_HEADER, total.to_string())], Json(items)).into_response()
+}
+
+async fn get_library_json(
+    Query(query): Query<ListQuery>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
-    let library_id = config.library_id.clone();
     drop(config);
 
-    match plex.get_library_items_with_labels(&library_id).await {
-        Ok(movies) => {
-            let count_processed = movies.iter()
-                .filter(|m| m.has_label("Rustizarr"))
-                .count();
-            
-            println!("✅ Données chargées : {} films (dont {} traités)", 
-                movies.len(), count_processed);
-            
-            let mut cache = state.library_cache.lock().await;
-            cache.update(movies.clone());
-            
-            Json(movies)
-        },
+    let mut cache = state.library_cache.lock().await;
+    let movies = match cache.items(&plex).await {
+        Ok(movies) => movies,
         Err(e) => {
-            println!("❌ Erreur récupération librairie : {:?}", e);
-            Json(vec![])
+            // L'index (éventuellement périmé) reste plus utile qu'une liste vide
+            error!(error = ?e, "{}", locale::t("log.cache.library_sync_error"));
+            cache.index.items()
         }
-    }
+    };
+    drop(cache);
+
+    let (page, total) = query.apply(movies);
+    debug!(total, served = page.len(), "Films servis depuis l'index");
+    list_response(page, total)
 }
 
 async fn refresh_library_cache(Extension(state): Extension<Arc<AppState>>) -> Json<serde_json::Value> {
-    println!("🔄 Rafraîchissement manuel du cache demandé...");
-    
-    {
-        let mut cache = state.library_cache.lock().await;
-        cache.invalidate();
-    }
+    info!("{}", locale::t("log.cache.refresh"));
     
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
-    let library_id = config.library_id.clone();
     drop(config);
 
-    match plex.get_library_items_with_labels(&library_id).await {
-        Ok(movies) => {
+    let mut cache = state.library_cache.lock().await;
+    match cache.full_sync(&plex).await {
+        Ok(stats) => {
+            let movies = cache.index.items();
             let count_processed = movies.iter()
                 .filter(|m| m.has_label("Rustizarr"))
                 .count();
             
-            let mut cache = state.library_cache.lock().await;
-            cache.update(movies.clone());
-            
             Json(serde_json::json!({
                 "success": true,
                 "total": movies.len(),
                 "processed": count_processed,
-                "message": "Cache rafraîchi avec succès"
+                "sync": stats,
+                "message": locale::t("api.message.cache_refreshed")
             }))
         },
         Err(e) => {
@@ -345,40 +685,46 @@ async fn refresh_library_cache(Extension(state): Extension<Arc<AppState>>) -> Js
 // HANDLERS - API SÉRIES
 // ==================================================================================
 
-async fn get_shows_json(Extension(state): Extension<Arc<AppState>>) -> Json<Vec<PlexShow>> {
+async fn get_shows_json(
+    Query(query): Query<ListQuery>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
-    let shows_library_id = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
     drop(config);
 
-    match plex.get_shows_library_items(&shows_library_id).await {
-        Ok(shows) => {
-            let count_processed = shows.iter()
-                .filter(|s| s.has_label("Rustizarr"))
-                .count();
-            
-            println!("✅ Séries chargées : {} séries (dont {} traitées)", 
-                shows.len(), count_processed);
-            
-            Json(shows)
-        },
+    let mut cache = state.shows_cache.lock().await;
+    let shows = match cache.items(&plex).await {
+        Ok(shows) => shows,
         Err(e) => {
-            println!("❌ Erreur récupération séries : {:?}", e);
-            Json(vec![])
+            error!(error = ?e, "{}", locale::t("log.plex.shows_error"));
+            cache.index.items()
         }
-    }
+    };
+    drop(cache);
+
+    let (page, total) = query.apply(shows);
+    debug!(total, served = page.len(), "Séries servies depuis l'index");
+    list_response(page, total)
 }
 
 async fn refresh_shows_cache(Extension(state): Extension<Arc<AppState>>) -> Json<serde_json::Value> {
-    println!("🔄 Rafraîchissement séries demandé...");
+    info!("{}", locale::t("log.cache.refresh_shows"));
     
     let config = state.config.lock().await;
     let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
-    let shows_library_id = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
     drop(config);
 
-    match plex.get_shows_library_items(&shows_library_id).await {
-        Ok(shows) => {
+    let mut shows_cache = state.shows_cache.lock().await;
+    let mut seasons_cache = state.seasons_cache.lock().await;
+    let synced = match shows_cache.full_sync(&plex).await {
+        Ok(shows) => seasons_cache.full_sync(&plex).await.map(|seasons| (shows, seasons)),
+        Err(e) => Err(e),
+    };
+
+    match synced {
+        Ok((shows_stats, seasons_stats)) => {
+            let shows = shows_cache.index.items();
             let count_processed = shows.iter()
                 .filter(|s| s.has_label("Rustizarr"))
                 .count();
@@ -387,7 +733,10 @@ async fn refresh_shows_cache(Extension(state): Extension<Arc<AppState>>) -> Json
                 "success": true,
                 "total": shows.len(),
                 "processed": count_processed,
-                "message": "Séries rafraîchies avec succès"
+                "seasons": seasons_cache.index.len(),
+                "sync": shows_stats,
+                "seasons_sync": seasons_stats,
+                "message": locale::t("api.message.shows_refreshed")
             }))
         },
         Err(e) => {
@@ -399,6 +748,127 @@ async fn refresh_shows_cache(Extension(state): Extension<Arc<AppState>>) -> Json
     }
 }
 
+/// `GET /api/shows/:id/seasons` : saisons d'une série, par numéro
+async fn get_show_seasons_json(
+    AxumPath(show_id): AxumPath<String>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    let config = state.config.lock().await;
+    let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
+    drop(config);
+
+    let cached: Vec<PlexSeason> = {
+        let mut cache = state.seasons_cache.lock().await;
+        match cache.items(&plex).await {
+            Ok(seasons) => seasons,
+            Err(e) => {
+                error!(error = ?e, "{}", locale::t("log.cache.seasons_sync_error"));
+                cache.index.items()
+            }
+        }
+    };
+
+    let mut seasons: Vec<PlexSeason> = cached.into_iter()
+        .filter(|s| s.show_rating_key == show_id)
+        .collect();
+
+    // Série hors de la bibliothèque indexée (ou pas encore indexée) : lecture directe
+    if seasons.is_empty() {
+        seasons = match plex.get_show_seasons(&show_id).await {
+            Ok(seasons) => seasons,
+            Err(e) => {
+                error!(show_id, error = ?e, "{}", locale::t("log.plex.seasons_error"));
+                return (StatusCode::BAD_GATEWAY, locale::tf("api.error.plex", &[("error", &format!("{:#}", e))])).into_response();
+            }
+        };
+    }
+
+    seasons.sort_by_key(|s| s.season_number);
+    Json(seasons).into_response()
+}
+
+#[derive(Deserialize)]
+struct ProcessSeasonsQuery {
+    #[serde(default)]
+    force: bool,
+}
+
+/// `POST /api/shows/:id/seasons/process` : lance un scan des saisons d'une série (202 + état du scan)
+///
+/// Progression et annulation via `/api/scans/:id`, comme les autres scans.
+async fn process_show_seasons(
+    AxumPath(show_id): AxumPath<String>,
+    Query(query): Query<ProcessSeasonsQuery>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    info!(show_id, force = query.force, "{}", locale::t("log.scan.seasons_requested"));
+    start_scan(state, StartScanRequest {
+        force: query.force,
+        show_id: Some(show_id),
+        ..StartScanRequest::new(ScanKind::Seasons)
+    })
+}
+
+// ==================================================================================
+// HANDLERS - RECHERCHE PAR ID EXTERNE
+// ==================================================================================
+
+#[derive(Deserialize)]
+struct LookupQuery {
+    tmdb: Option<String>,
+    imdb: Option<String>,
+    tvdb: Option<String>,
+    /// Limite la recherche à une bibliothèque : "movie" ou "show"
+    kind: Option<String>,
+}
+
+/// `GET /api/lookup?tmdb=603` : rating keys Plex correspondant à un ID TMDB / IMDb / TVDB
+async fn lookup_by_guid(
+    Query(query): Query<LookupQuery>,
+    Extension(state): Extension<Arc<AppState>>,
+) -> axum::response::Response {
+    let external = match (query.tmdb, query.imdb, query.tvdb) {
+        (Some(id), None, None) => ExternalId::Tmdb(id),
+        (None, Some(id), None) => ExternalId::Imdb(id),
+        (None, None, Some(id)) => ExternalId::Tvdb(id),
+        _ => return (StatusCode::BAD_REQUEST, locale::t("api.error.single_guid")).into_response(),
+    };
+
+    let config = state.config.lock().await;
+    let plex = PlexClient::new(config.plex_url.clone(), config.plex_token.clone());
+    let movies_library_id = config.library_id.clone();
+    drop(config);
+    let shows_library_id = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
+
+    let libraries = match query.kind.as_deref() {
+        None => vec![(movies_library_id, "movie"), (shows_library_id, "show")],
+        Some("movie") => vec![(movies_library_id, "movie")],
+        Some("show") => vec![(shows_library_id, "show")],
+        Some(_) => return (StatusCode::BAD_REQUEST, locale::t("api.error.invalid_kind")).into_response(),
+    };
+
+    let mut matches: Vec<PlexGuidMatch> = Vec::new();
+    for (library_id, kind) in libraries {
+        let found = match kind {
+            "movie" => plex.find_by_guid::<PlexMovie>(&library_id, &external).await,
+            _ => plex.find_by_guid::<PlexShow>(&library_id, &external).await,
+        };
+        match found {
+            Ok(found) => matches.extend(found),
+            Err(e) => {
+                error!(library_id, error = ?e, "{}", locale::t("log.plex.guid_lookup_error"));
+                return (StatusCode::BAD_GATEWAY, locale::tf("api.error.plex", &[("error", &format!("{:#}", e))])).into_response();
+            }
+        }
+    }
+
+    debug!(guid = %external.to_guid(), found = matches.len(), "Recherche par ID externe");
+    Json(serde_json::json!({
+        "guid": external.to_guid(),
+        "matches": matches,
+    })).into_response()
+}
+
 // ==================================================================================
 // HANDLERS - IMAGES
 // ==================================================================================
@@ -424,7 +894,7 @@ async fn get_plex_image(
     
     let resp = match client.get(&url).send().await {
         Ok(r) => r,
-        Err(_) => return (StatusCode::NOT_FOUND, "Plex inaccessible").into_response(),
+        Err(_) => return (StatusCode::NOT_FOUND, locale::t("api.error.plex_unreachable")).into_response(),
     };
     
     if resp.status().is_redirection() {
@@ -441,19 +911,19 @@ async fn get_plex_image(
                 }
             }
         }
-        return (StatusCode::INTERNAL_SERVER_ERROR, "Erreur redirection").into_response();
+        return (StatusCode::INTERNAL_SERVER_ERROR, locale::t("api.error.redirect")).into_response();
     }
     
     if resp.status().is_success() {
         return process_image_response(resp).await;
     }
     
-    (StatusCode::from_u16(resp.status().as_u16()).unwrap(), "Echec").into_response()
+    (StatusCode::from_u16(resp.status().as_u16()).unwrap(), locale::t("api.error.upstream")).into_response()
 }
 
 async fn process_image_response(resp: reqwest::Response) -> axum::response::Response {
     if !resp.status().is_success() {
-        return (StatusCode::NOT_FOUND, "Image introuvable").into_response();
+        return (StatusCode::NOT_FOUND, locale::t("api.error.image_not_found")).into_response();
     }
 
     let content_type = resp.headers()
@@ -469,50 +939,131 @@ async fn process_image_response(resp: reqwest::Response) -> axum::response::Resp
             headers.insert(header::CACHE_CONTROL, "public, max-age=31536000".parse().unwrap());
             (StatusCode::OK, headers, Body::from(image_bytes)).into_response()
         },
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Erreur flux").into_response()
+        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, locale::t("api.error.stream")).into_response()
     }
 }
 
+// ==================================================================================
+// HANDLERS - MÉTRIQUES
+// ==================================================================================
+
+async fn get_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::metrics().render(),
+    )
+}
+
 // ==================================================================================
 // MAIN
 // ==================================================================================
 
+/// Variable obligatoire du .env : arrêt avec un message localisé si absente
+fn required_env(name: &str) -> String {
+    env::var(name).unwrap_or_else(|_| panic!("{}", locale::tf("cli.env.required", &[("name", name)])))
+}
+
 #[tokio::main]
 async fn main() {
     dotenv::dotenv().ok();
+    logging::init(None, LogFormat::from_env());
+    let locale = locale::init(None);
+    info!(locale = locale.code(), "{}", locale::t("log.server.locale"));
 
     let config = AppConfig {
-        plex_url: env::var("PLEX_URL").expect("❌ PLEX_URL manquant dans .env"),
-        plex_token: env::var("PLEX_TOKEN").expect("❌ PLEX_TOKEN manquant dans .env"),
-        tmdb_key: env::var("TMDB_KEY").expect("❌ TMDB_KEY manquant dans .env"),
+        plex_url: required_env("PLEX_URL"),
+        plex_token: required_env("PLEX_TOKEN"),
+        tmdb_key: required_env("TMDB_KEY"),
         library_id: env::var("LIBRARY_ID").unwrap_or("1".to_string()),
     };
 
+    let shows_library_id = env::var("SHOWS_LIBRARY_ID").unwrap_or("2".to_string());
+
     let app_state = Arc::new(AppState {
+        library_cache: Mutex::new(LibraryCache::new("movies", &config.library_id)),
+        shows_cache: Mutex::new(LibraryCache::new("shows", &shows_library_id)),
+        seasons_cache: Mutex::new(LibraryCache::new("seasons", &shows_library_id)),
         config: Mutex::new(config),
-        library_cache: Mutex::new(LibraryCache::new()),
+        scans: ScanRegistry::new(),
+        webhook_routes: WebhookRoutes::from_env(),
+        pending_renders: PendingRenders::default(),
+        webhook_poll: PollSettings::from_env(),
+        cascade_seasons: env::var("CASCADE_SEASONS").is_ok_and(|v| v == "true" || v == "1"),
     });
+    info!(events = ?app_state.webhook_routes.rendered_events(), "{}", locale::t("log.server.webhook_events"));
+
+    let auth = Arc::new(AuthConfig::from_env());
+    auth.log_summary();
 
-    let app = Router::new()
-        .route("/", get(|| async { "RustOverlay Backend Running 🚀" }))
+    // Routes protégées par clé API / Basic auth
+    let api = Router::new()
         .route("/scan", get(run_full_library_scan))
-        .route("/webhook", post(handle_plex_webhook))
         .route("/api/library", get(get_library_json))
         .route("/api/library/refresh", post(refresh_library_cache)) 
         .route("/api/image/:id", get(get_plex_image))
+        .route("/api/lookup", get(lookup_by_guid))
         .route("/api/shows", get(get_shows_json))
         .route("/api/shows/refresh", post(refresh_shows_cache))
+        .route("/api/shows/:id/seasons", get(get_show_seasons_json))
+        .route("/api/shows/:id/seasons/process", post(process_show_seasons))
         .route("/scan-shows", get(run_full_shows_scan))
-        .layer(CorsLayer::permissive())
+        .route("/api/scans", get(list_scans).post(create_scan))
+        .route("/api/scans/:id", get(get_scan).delete(cancel_scan))
+        .route("/api/scans/:id/events", get(stream_scan_events))
+        .route_layer(middleware::from_fn_with_state(auth.clone(), auth::require_api_auth));
+
+    // Webhooks (Plex, Radarr, Sonarr, Tautulli) protégés par secret partagé (?token=...)
+    let webhook = Router::new()
+        .route("/webhook", post(handle_plex_webhook))
+        .route("/webhook/radarr", post(handle_radarr_webhook))
+        .route("/webhook/sonarr", post(handle_sonarr_webhook))
+        .route("/webhook/tautulli", post(handle_tautulli_webhook))
+        .route_layer(middleware::from_fn_with_state(auth.clone(), auth::require_webhook_secret));
+
+    // Métriques : mêmes identifiants que l'API (bearer_token côté Prometheus), sauf METRICS_PUBLIC=true
+    let mut metrics = Router::new().route("/metrics", get(get_metrics));
+    if !auth.metrics_public {
+        metrics = metrics.route_layer(middleware::from_fn_with_state(auth.clone(), auth::require_api_auth));
+    }
+
+    let mut app = Router::new()
+        .merge(metrics)
+        .merge(api)
+        .merge(webhook);
+
+    // Frontend compilé servi par le backend (optionnel), protégé par Basic auth si configurée
+    match env::var("FRONTEND_DIR") {
+        Ok(dir) => {
+            info!(%dir, "{}", locale::t("log.server.frontend"));
+            let frontend = Router::new()
+                .fallback_service(ServeDir::new(dir))
+                .layer(middleware::from_fn_with_state(auth.clone(), auth::require_basic_auth));
+            app = app.merge(frontend);
+        },
+        Err(_) => {
+            app = app.route("/", get(|| async { "RustOverlay Backend Running 🚀" }));
+        }
+    }
+
+    let app = app
+        // Span sans la query string : elle peut contenir api_key / token
+        .layer(TraceLayer::new_for_http().make_span_with(|request: &axum::extract::Request| {
+            info_span!("request", method = %request.method(), path = %request.uri().path())
+        }))
+        .layer(auth.cors_layer())
         .layer(Extension(app_state));
 
     let port = env::var("PORT").unwrap_or("3000".to_string());
     let addr = format!("0.0.0.0:{}", port);
 
-    println!("🚀 Serveur lancé sur http://{}", addr);
-    println!("👉 Endpoint Webhook : http://TON_IP_LOCALE:{}/webhook", port);
+    info!("{}", locale::tf("server.started", &[("url", &format!("http://{}", addr))]));
+    if auth.webhook_secret.is_some() {
+        info!("{}", locale::tf("server.webhook_endpoint", &[("url", &format!("http://<IP>:{}/webhook?token=<WEBHOOK_SECRET>", port))]));
+    } else {
+        info!("{}", locale::tf("server.webhook_endpoint", &[("url", &format!("http://<IP>:{}/webhook", port))]));
+    }
     if let Ok(cwd) = std::env::current_dir() { 
-        println!("📂 Dossier d'exécution (CWD) : {:?}", cwd); 
+        info!(?cwd, "{}", locale::t("log.server.cwd")); 
     }
 
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
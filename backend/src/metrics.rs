@@ -0,0 +1,195 @@
+// backend/src/metrics.rs
+// Métriques Prometheus (exposées par le serveur sur /metrics)
+use prometheus::{
+    Encoder, HistogramOpts, HistogramTimer, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::report::{MediaKind, ProcessOutcome};
+
+pub struct Metrics {
+    registry: Registry,
+    /// Éléments traités par type et résultat
+    pub items: IntCounterVec,
+    /// Requêtes sortantes (Plex / TMDB) par service, endpoint et statut HTTP
+    pub upstream_requests: IntCounterVec,
+    /// Latence des requêtes sortantes
+    pub upstream_latency: HistogramVec,
+    /// Octets téléchargés pour les posters sources
+    pub download_bytes: IntCounter,
+    /// Octets des posters rendus envoyés à Plex
+    pub upload_bytes: IntCounter,
+    /// Durée de chaque étape de rendu de l'ImageProcessor
+    pub render_stage: HistogramVec,
+    /// Événements webhook reçus par type
+    pub webhook_events: IntCounterVec,
+    /// Accès au cache de bibliothèque (hit / miss)
+    pub cache_requests: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("rustizarr".to_string()), None)
+            .expect("registre Prometheus");
+
+        let items = IntCounterVec::new(
+            Opts::new("items_total", "Éléments traités par type et résultat"),
+            &["kind", "outcome"],
+        ).unwrap();
+
+        let upstream_requests = IntCounterVec::new(
+            Opts::new("upstream_requests_total", "Requêtes vers Plex/TMDB par statut"),
+            &["service", "endpoint", "status"],
+        ).unwrap();
+
+        let upstream_latency = HistogramVec::new(
+            HistogramOpts::new("upstream_request_duration_seconds", "Latence des requêtes vers Plex/TMDB")
+                .buckets(vec![0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]),
+            &["service", "endpoint"],
+        ).unwrap();
+
+        let download_bytes = IntCounter::new(
+            "image_download_bytes_total", "Octets téléchargés pour les posters sources",
+        ).unwrap();
+
+        let upload_bytes = IntCounter::new(
+            "image_upload_bytes_total", "Octets des posters rendus envoyés à Plex",
+        ).unwrap();
+
+        let render_stage = HistogramVec::new(
+            HistogramOpts::new("render_stage_duration_seconds", "Durée des étapes de rendu")
+                .buckets(vec![0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0]),
+            &["stage"],
+        ).unwrap();
+
+        let webhook_events = IntCounterVec::new(
+            Opts::new("webhook_events_total", "Événements webhook reçus"),
+            &["event", "media_type"],
+        ).unwrap();
+
+        let cache_requests = IntCounterVec::new(
+            Opts::new("library_cache_requests_total", "Accès au cache de bibliothèque"),
+            &["cache", "result"],
+        ).unwrap();
+
+        registry.register(Box::new(items.clone())).unwrap();
+        registry.register(Box::new(upstream_requests.clone())).unwrap();
+        registry.register(Box::new(upstream_latency.clone())).unwrap();
+        registry.register(Box::new(download_bytes.clone())).unwrap();
+        registry.register(Box::new(upload_bytes.clone())).unwrap();
+        registry.register(Box::new(render_stage.clone())).unwrap();
+        registry.register(Box::new(webhook_events.clone())).unwrap();
+        registry.register(Box::new(cache_requests.clone())).unwrap();
+
+        Self {
+            registry,
+            items,
+            upstream_requests,
+            upstream_latency,
+            download_bytes,
+            upload_bytes,
+            render_stage,
+            webhook_events,
+            cache_requests,
+        }
+    }
+
+    /// Sérialise toutes les métriques au format texte Prometheus
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        let encoder = TextEncoder::new();
+        if encoder.encode(&self.registry.gather(), &mut buffer).is_err() {
+            return String::new();
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Instance globale (initialisée au premier accès)
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+// ==================== HELPERS ====================
+
+pub fn record_outcome(kind: MediaKind, outcome: &ProcessOutcome) {
+    let kind = match kind {
+        MediaKind::Movie => "movie",
+        MediaKind::Show => "show",
+        MediaKind::Season => "season",
+    };
+    metrics().items.with_label_values(&[kind, outcome.code()]).inc();
+}
+
+/// Chronomètre d'une étape de rendu, enregistré automatiquement à la fin du scope
+pub fn render_stage_timer(stage: &str) -> HistogramTimer {
+    metrics().render_stage.with_label_values(&[stage]).start_timer()
+}
+
+/// Envoie une requête vers Plex/TMDB en enregistrant latence et statut
+pub async fn timed_send(
+    service: &str,
+    endpoint: &str,
+    request: reqwest::RequestBuilder,
+) -> reqwest::Result<reqwest::Response> {
+    let start = Instant::now();
+    let result = request.send().await;
+
+    let m = metrics();
+    m.upstream_latency
+        .with_label_values(&[service, endpoint])
+        .observe(start.elapsed().as_secs_f64());
+
+    let status = match &result {
+        Ok(resp) => resp.status().as_u16().to_string(),
+        Err(_) => "error".to_string(),
+    };
+    m.upstream_requests.with_label_values(&[service, endpoint, &status]).inc();
+
+    result
+}
+
+pub fn record_download_bytes(bytes: usize) {
+    metrics().download_bytes.inc_by(bytes as u64);
+}
+
+pub fn record_upload_bytes(bytes: usize) {
+    metrics().upload_bytes.inc_by(bytes as u64);
+}
+
+/// `event` doit déjà être borné (voir `WebhookRoutes::metric_event`) : les labels viennent de requêtes externes
+pub fn record_webhook_event(event: &str, media_type: &str) {
+    metrics().webhook_events.with_label_values(&[event, webhook_media_type(media_type)]).inc();
+}
+
+/// Types de médias connus ; tout le reste (musique, photos, valeur libre) est regroupé sous "other"
+fn webhook_media_type(media_type: &str) -> &'static str {
+    match media_type {
+        "movie" => "movie",
+        "show" => "show",
+        "season" => "season",
+        "episode" => "episode",
+        "none" | "" => "none",
+        _ => "other",
+    }
+}
+
+pub fn record_cache_access(cache: &str, hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    metrics().cache_requests.with_label_values(&[cache, result]).inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn webhook_media_type_is_bounded() {
+        assert_eq!(webhook_media_type("episode"), "episode");
+        assert_eq!(webhook_media_type(""), "none");
+        assert_eq!(webhook_media_type("track"), "other");
+        assert_eq!(webhook_media_type("{media_type}"), "other");
+    }
+}
@@ -0,0 +1,345 @@
+// backend/src/output.rs
+// Poster de sortie : dimensions du canvas (recadrage ou marges au lieu d'un étirement)
+// et encodage envoyé à Plex (JPEG à qualité réglable, PNG, WebP sans perte, taille maximale)
+use crate::badges::parse_color;
+use crate::locale;
+use anyhow::Result;
+use image::codecs::jpeg::JpegEncoder;
+use image::{imageops, DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::env;
+use std::io::Cursor;
+use tracing::{debug, warn};
+
+/// Qualité JPEG par défaut de la crate `image` (rendu historique)
+const DEFAULT_QUALITY: u8 = 75;
+/// Plancher de la qualité adaptative
+const MIN_QUALITY: u8 = 40;
+/// Réduction du fond flouté des marges avant flou
+const PAD_BLUR_DOWNSCALE: u32 = 8;
+
+// ==================== CONFIGURATION ====================
+
+/// Adaptation d'un poster source aux proportions du canvas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// Remplit le canvas, l'excédent est rogné au centre
+    Cover,
+    /// Poster entier, marges unies ou floutées
+    Pad,
+}
+
+impl Fit {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "cover" => Some(Fit::Cover),
+            "pad" => Some(Fit::Pad),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Jpeg,
+    Png,
+    /// WebP sans perte (seul encodeur WebP disponible en Rust pur)
+    Webp,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "jpeg" | "jpg" => Some(OutputFormat::Jpeg),
+            "png" => Some(OutputFormat::Png),
+            "webp" => Some(OutputFormat::Webp),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "image/jpeg",
+            OutputFormat::Png => "image/png",
+            OutputFormat::Webp => "image/webp",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Jpeg => "jpeg",
+            OutputFormat::Png => "png",
+            OutputFormat::Webp => "webp",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutputSettings {
+    pub width: u32,
+    pub height: u32,
+    pub fit: Fit,
+    /// Couleur des marges en mode `Pad`, `None` pour un fond flouté du poster
+    pub pad_color: Option<Rgba<u8>>,
+    pub format: OutputFormat,
+    /// Qualité JPEG (1-100)
+    pub quality: u8,
+    /// Taille maximale du fichier, JPEG uniquement : la qualité baisse jusqu'à `MIN_QUALITY` pour la respecter
+    pub max_bytes: Option<usize>,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        Self {
+            width: 2000,
+            height: 3000,
+            fit: Fit::Cover,
+            pad_color: None,
+            format: OutputFormat::Jpeg,
+            quality: DEFAULT_QUALITY,
+            max_bytes: None,
+        }
+    }
+}
+
+impl OutputSettings {
+    /// OUTPUT_SIZE (2000x3000), OUTPUT_FIT (cover, pad), OUTPUT_PAD (blur, #RRGGBB),
+    /// OUTPUT_FORMAT (jpeg, png, webp), JPEG_QUALITY, OUTPUT_MAX_BYTES (800000, 800k, 1.5m ; JPEG uniquement)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let (width, height) = env::var("OUTPUT_SIZE").ok()
+            .and_then(|v| parse_size(&v))
+            .unwrap_or((defaults.width, defaults.height));
+        let fit = env::var("OUTPUT_FIT").ok()
+            .map(|v| Fit::parse(&v).unwrap_or_else(|| {
+                warn!(value = %v, "{}", locale::t("log.output.unknown_fit"));
+                defaults.fit
+            }))
+            .unwrap_or(defaults.fit);
+        let pad_color = env::var("OUTPUT_PAD").ok()
+            .filter(|v| !v.trim().eq_ignore_ascii_case("blur"))
+            .and_then(|v| parse_color(&v));
+        let format = env::var("OUTPUT_FORMAT").ok()
+            .map(|v| OutputFormat::parse(&v).unwrap_or_else(|| {
+                warn!(value = %v, "{}", locale::t("log.output.unknown_format"));
+                defaults.format
+            }))
+            .unwrap_or(defaults.format);
+        let quality = env::var("JPEG_QUALITY").ok()
+            .and_then(|v| v.trim().parse::<u8>().ok())
+            .map(|q| q.clamp(1, 100))
+            .unwrap_or(defaults.quality);
+        let max_bytes = env::var("OUTPUT_MAX_BYTES").ok().and_then(|v| parse_bytes(&v));
+        if max_bytes.is_some() && format != OutputFormat::Jpeg {
+            warn!(format = format.name(), "{}", locale::t("log.output.max_bytes_jpeg_only"));
+        }
+        let max_bytes = max_bytes.filter(|_| format == OutputFormat::Jpeg);
+
+        Self { width, height, fit, pad_color, format, quality, max_bytes }
+    }
+}
+
+/// "2000x3000" → (2000, 3000)
+fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let (w, h) = value.trim().to_lowercase().split_once('x').map(|(w, h)| (w.trim().parse().ok(), h.trim().parse().ok()))?;
+    match (w?, h?) {
+        (0, _) | (_, 0) => None,
+        size => Some(size),
+    }
+}
+
+/// "800000", "800k", "1.5m" → octets
+fn parse_bytes(value: &str) -> Option<usize> {
+    let value = value.trim().to_lowercase();
+    let (number, factor) = match value.chars().last()? {
+        'k' => (&value[..value.len() - 1], 1_000.0),
+        'm' => (&value[..value.len() - 1], 1_000_000.0),
+        _ => (value.as_str(), 1.0),
+    };
+    let bytes = (number.trim().parse::<f64>().ok()? * factor) as usize;
+    (bytes > 0).then_some(bytes)
+}
+
+// ==================== CANVAS ====================
+
+/// Poster source aux dimensions du canvas, sans déformation
+pub fn fit_canvas(image: DynamicImage, settings: &OutputSettings) -> DynamicImage {
+    let (width, height) = (settings.width, settings.height);
+    if (image.width(), image.height()) == (width, height) {
+        return image;
+    }
+
+    match settings.fit {
+        Fit::Cover => image.resize_to_fill(width, height, imageops::FilterType::Lanczos3),
+        Fit::Pad => {
+            let mut canvas = match settings.pad_color {
+                Some(color) => RgbaImage::from_pixel(width, height, color),
+                None => blurred_background(&image, width, height),
+            };
+            let poster = image.resize(width, height, imageops::FilterType::Lanczos3);
+            let x = (width - poster.width()) / 2;
+            let y = (height - poster.height()) / 2;
+            imageops::overlay(&mut canvas, &poster.to_rgba8(), x as i64, y as i64);
+            DynamicImage::ImageRgba8(canvas)
+        }
+    }
+}
+
+/// Poster recadré au canvas, flouté à basse résolution puis agrandi
+fn blurred_background(image: &DynamicImage, width: u32, height: u32) -> RgbaImage {
+    let small = image.resize_to_fill(
+        (width / PAD_BLUR_DOWNSCALE).max(1),
+        (height / PAD_BLUR_DOWNSCALE).max(1),
+        imageops::FilterType::Triangle,
+    );
+    let blurred = imageops::blur(&small.to_rgba8(), 6.0);
+    imageops::resize(&blurred, width, height, imageops::FilterType::Triangle)
+}
+
+// ==================== ENCODAGE ====================
+
+/// Poster encodé, prêt pour l'upload
+#[derive(Debug, Clone)]
+pub struct EncodedPoster {
+    pub bytes: Vec<u8>,
+    pub format: OutputFormat,
+    /// Qualité JPEG effectivement utilisée
+    pub quality: Option<u8>,
+}
+
+/// Encode au format configuré ; en JPEG au-delà de `max_bytes`, plus haute qualité qui tient
+///
+/// PNG et WebP sont sans perte : `max_bytes` ne les concerne pas.
+pub fn encode(poster: &DynamicImage, settings: &OutputSettings) -> Result<EncodedPoster> {
+    if settings.format != OutputFormat::Jpeg {
+        let bytes = encode_lossless(poster, settings.format)?;
+        return Ok(EncodedPoster { bytes, format: settings.format, quality: None });
+    }
+
+    let bytes = encode_jpeg(poster, settings.quality)?;
+    match settings.max_bytes {
+        Some(max_bytes) if bytes.len() > max_bytes => {
+            adaptive_jpeg(poster, settings.quality.saturating_sub(1), max_bytes)
+        }
+        _ => Ok(EncodedPoster { bytes, format: OutputFormat::Jpeg, quality: Some(settings.quality) }),
+    }
+}
+
+/// Recherche dichotomique de la plus haute qualité dans [MIN_QUALITY, start] respectant `max_bytes`
+fn adaptive_jpeg(poster: &DynamicImage, start: u8, max_bytes: usize) -> Result<EncodedPoster> {
+    let (mut low, mut high) = (MIN_QUALITY, start.max(MIN_QUALITY));
+    let mut best: Option<(u8, Vec<u8>)> = None;
+    // Dernier essai trop lourd : celui à MIN_QUALITY si aucune qualité ne tient
+    let mut last: Option<(u8, Vec<u8>)> = None;
+
+    while low <= high {
+        let quality = low + (high - low) / 2;
+        let bytes = encode_jpeg(poster, quality)?;
+        debug!(quality, bytes = bytes.len(), max_bytes, "Essai de qualité JPEG");
+        if bytes.len() <= max_bytes {
+            best = Some((quality, bytes));
+            low = quality + 1;
+        } else {
+            last = Some((quality, bytes));
+            if quality == MIN_QUALITY {
+                break;
+            }
+            high = quality - 1;
+        }
+    }
+
+    let (quality, bytes) = match (best, last) {
+        (Some(found), _) => found,
+        (None, Some((quality, bytes))) => {
+            warn!(bytes = bytes.len(), max_bytes, quality, "{}", locale::t("log.output.max_bytes_unreachable"));
+            (quality, bytes)
+        }
+        (None, None) => anyhow::bail!("Aucun encodage JPEG produit"),
+    };
+    Ok(EncodedPoster { bytes, format: OutputFormat::Jpeg, quality: Some(quality) })
+}
+
+fn encode_jpeg(poster: &DynamicImage, quality: u8) -> Result<Vec<u8>> {
+    let rgb_poster = poster.to_rgb8();
+    let mut bytes: Vec<u8> = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, quality).encode_image(&rgb_poster)?;
+    Ok(bytes)
+}
+
+fn encode_lossless(poster: &DynamicImage, format: OutputFormat) -> Result<Vec<u8>> {
+    let rgb_poster = poster.to_rgb8();
+    let mut bytes: Vec<u8> = Vec::new();
+    match format {
+        OutputFormat::Webp => {
+            image_webp::WebPEncoder::new(&mut bytes).encode(
+                rgb_poster.as_raw(),
+                rgb_poster.width(),
+                rgb_poster.height(),
+                image_webp::ColorType::Rgb8,
+            )?;
+        }
+        _ => rgb_poster.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)?,
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poster(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+            Rgba([(x * 7 % 256) as u8, (y * 13 % 256) as u8, ((x + y) % 256) as u8, 255])
+        }))
+    }
+
+    #[test]
+    fn parse_size_accepts_width_x_height() {
+        assert_eq!(parse_size("2000x3000"), Some((2000, 3000)));
+        assert_eq!(parse_size(" 1000 X 1500 "), Some((1000, 1500)));
+        assert_eq!(parse_size("0x3000"), None);
+        assert_eq!(parse_size("2000"), None);
+        assert_eq!(parse_size("axb"), None);
+    }
+
+    #[test]
+    fn parse_bytes_handles_units() {
+        assert_eq!(parse_bytes("800000"), Some(800_000));
+        assert_eq!(parse_bytes("800k"), Some(800_000));
+        assert_eq!(parse_bytes("1.5M"), Some(1_500_000));
+        assert_eq!(parse_bytes("0"), None);
+        assert_eq!(parse_bytes("k"), None);
+        assert_eq!(parse_bytes(""), None);
+    }
+
+    #[test]
+    fn fit_parse_is_lenient() {
+        assert_eq!(Fit::parse(" PAD "), Some(Fit::Pad));
+        assert_eq!(Fit::parse("cover"), Some(Fit::Cover));
+        assert_eq!(Fit::parse("stretch"), None);
+    }
+
+    #[test]
+    fn pad_keeps_canvas_size() {
+        let settings = OutputSettings { width: 300, height: 200, fit: Fit::Pad, ..OutputSettings::default() };
+        let fitted = fit_canvas(poster(200, 300), &settings);
+        assert_eq!((fitted.width(), fitted.height()), (300, 200));
+    }
+
+    #[test]
+    fn max_bytes_is_ignored_for_lossless_formats() {
+        let settings = OutputSettings { format: OutputFormat::Png, max_bytes: Some(1), ..OutputSettings::default() };
+        let encoded = encode(&poster(64, 96), &settings).unwrap();
+        assert_eq!(encoded.format, OutputFormat::Png);
+        assert_eq!(encoded.quality, None);
+    }
+
+    #[test]
+    fn unreachable_max_bytes_keeps_minimum_quality_attempt() {
+        let image = poster(64, 96);
+        let settings = OutputSettings { max_bytes: Some(1), ..OutputSettings::default() };
+        let encoded = encode(&image, &settings).unwrap();
+        assert_eq!(encoded.quality, Some(MIN_QUALITY));
+        assert_eq!(encoded.bytes, encode_jpeg(&image, MIN_QUALITY).unwrap());
+    }
+}
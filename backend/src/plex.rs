@@ -1,6 +1,19 @@
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use anyhow::Result;
+use crate::metrics;
+use crate::locale;
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::env;
+use tracing::{debug, info, warn};
+
+/// Taille d'une page de bibliothèque (X-Plex-Container-Size)
+const DEFAULT_PAGE_SIZE: usize = 500;
+/// Nombre d'éléments par requête `/library/metadata/1,2,3`
+const DEFAULT_BATCH_SIZE: usize = 50;
+/// Requêtes de métadonnées simultanées
+const DEFAULT_CONCURRENCY: usize = 4;
 
 // --- Structures ---
 
@@ -9,6 +22,9 @@ pub struct PlexClient {
     client: reqwest::Client,
     base_url: String,
     token: String,
+    page_size: usize,
+    batch_size: usize,
+    concurrency: usize,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -18,6 +34,9 @@ pub struct PlexMovie {
     pub rating_key: String,
     #[serde(rename = "audienceRating")]
     pub audience_rating: Option<f64>,
+    /// Notes des agents (IMDb, Rotten Tomatoes, TMDB)
+    #[serde(rename = "Rating", default)]
+    pub ratings: Option<Vec<PlexRating>>,
     #[serde(rename = "Guid")]
     pub guids: Option<Vec<PlexGuid>>, 
     #[serde(rename = "guid")]
@@ -28,9 +47,14 @@ pub struct PlexMovie {
     pub media: Option<Vec<PlexMedia>>,
     #[serde(rename = "addedAt")]
     pub added_at: Option<u64>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<u64>,
     
     #[serde(rename = "Label")]
     pub labels: Option<serde_json::Value>,
+    /// Section Plex de l'élément (renseignée aussi pour les listings de section)
+    #[serde(rename = "librarySectionID", default, deserialize_with = "string_or_number")]
+    pub library_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -41,8 +65,13 @@ pub struct PlexShow {
     pub year: Option<u32>,
     #[serde(rename = "audienceRating")]
     pub audience_rating: Option<f64>,
+    /// Notes des agents (IMDb, Rotten Tomatoes, TMDB)
+    #[serde(rename = "Rating", default)]
+    pub ratings: Option<Vec<PlexRating>>,
     #[serde(rename = "addedAt")]
     pub added_at: Option<u64>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<u64>,
     
     #[serde(rename = "Guid")]
     pub guid: Option<Vec<PlexGuid>>,
@@ -52,9 +81,12 @@ pub struct PlexShow {
     
     #[serde(rename = "Media")]
     pub media: Option<Vec<PlexMedia>>,
+    /// Section Plex de l'élément (renseignée aussi pour les listings de section)
+    #[serde(rename = "librarySectionID", default, deserialize_with = "string_or_number")]
+    pub library_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PlexSeason {
     pub title: String,
     #[serde(rename = "ratingKey")]
@@ -69,10 +101,15 @@ pub struct PlexSeason {
     pub audience_rating: Option<f64>,
     #[serde(rename = "addedAt")]
     pub added_at: Option<u64>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: Option<u64>,
     #[serde(rename = "Media")]
     pub media: Option<Vec<PlexMedia>>,
     #[serde(rename = "Label")]
     pub label: Option<serde_json::Value>,
+    /// Section Plex de l'élément (renseignée aussi pour les listings de section)
+    #[serde(rename = "librarySectionID", default, deserialize_with = "string_or_number")]
+    pub library_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -85,6 +122,17 @@ pub struct PlexGuid {
     pub id: String 
 }
 
+/// Note d'un agent : `image` identifie la source ("imdb://image.rating",
+/// "rottentomatoes://image.rating.ripe"), `type` distingue critiques et public
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PlexRating {
+    pub image: String,
+    /// Sur 10, y compris pour Rotten Tomatoes (9.1 = 91 %)
+    pub value: f64,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PlexMedia {
     #[serde(rename = "videoResolution")]
@@ -102,21 +150,112 @@ pub struct PlexPart {
     pub streams: Option<serde_json::Value>, 
 }
 
-#[derive(Debug, Deserialize)]
-struct PlexResponse {
-    #[serde(rename = "MediaContainer")]
-    media_container: MediaContainer,
+/// Identifiant externe d'un élément (TMDB, IMDb, TVDB)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalId {
+    Tmdb(String),
+    Imdb(String),
+    Tvdb(String),
+}
+
+impl ExternalId {
+    /// Guid au format Plex ("tmdb://603", "imdb://tt0133093", "tvdb://81189")
+    pub fn to_guid(&self) -> String {
+        match self {
+            ExternalId::Tmdb(id) => format!("tmdb://{}", id.trim()),
+            ExternalId::Imdb(id) => format!("imdb://{}", id.trim()),
+            ExternalId::Tvdb(id) => format!("tvdb://{}", id.trim()),
+        }
+    }
+}
+
+/// Élément trouvé par une recherche de guid
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PlexGuidMatch {
+    #[serde(rename = "ratingKey")]
+    pub rating_key: String,
+    pub title: String,
+    #[serde(default, rename = "type")]
+    pub media_type: String,
+    pub year: Option<u32>,
+    /// Section Plex où l'élément a été trouvé
+    #[serde(skip_deserializing, rename = "librarySectionID")]
+    pub library_id: String,
+}
+
+/// `librarySectionID` est un nombre dans les métadonnées, une chaîne ailleurs
+fn string_or_number<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::String(s)) => Some(s),
+        Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    })
 }
 
-#[derive(Debug, Deserialize)]
-struct MediaContainer {
-    #[serde(rename = "Metadata")]
-    metadata: Option<Vec<serde_json::Value>>,
+/// Élément de bibliothèque Plex (film, série, saison)
+pub trait PlexItem: Clone + DeserializeOwned + Send + Sync + 'static {
+    /// Type Plex utilisé dans les filtres de section (1 = film, 2 = série, 3 = saison)
+    const PLEX_TYPE: u8;
+    /// Le listing de section ne contient pas tout (labels, flux) : détails à récupérer
+    const NEEDS_DETAILS: bool;
+
+    fn rating_key(&self) -> &str;
+    fn title(&self) -> &str;
+    fn updated_at(&self) -> Option<u64>;
+}
+
+impl PlexItem for PlexMovie {
+    const PLEX_TYPE: u8 = 1;
+    const NEEDS_DETAILS: bool = true;
+
+    fn rating_key(&self) -> &str { &self.rating_key }
+    fn title(&self) -> &str { &self.title }
+    fn updated_at(&self) -> Option<u64> { self.updated_at.or(self.added_at) }
+}
+
+impl PlexItem for PlexShow {
+    const PLEX_TYPE: u8 = 2;
+    const NEEDS_DETAILS: bool = false;
+
+    fn rating_key(&self) -> &str { &self.rating_key }
+    fn title(&self) -> &str { &self.title }
+    fn updated_at(&self) -> Option<u64> { self.updated_at.or(self.added_at) }
+}
+
+impl PlexItem for PlexSeason {
+    const PLEX_TYPE: u8 = 3;
+    const NEEDS_DETAILS: bool = false;
+
+    fn rating_key(&self) -> &str { &self.rating_key }
+    fn title(&self) -> &str { &self.title }
+    fn updated_at(&self) -> Option<u64> { self.updated_at.or(self.added_at) }
 }
 
 // --- Implémentations ---
 
+/// Types de flux Plex (`streamType`)
+pub const STREAM_VIDEO: u64 = 1;
+pub const STREAM_AUDIO: u64 = 2;
+pub const STREAM_SUBTITLE: u64 = 3;
+
+fn one_or_many(value: &serde_json::Value) -> &[serde_json::Value] {
+    match value.as_array() {
+        Some(arr) => arr.as_slice(),
+        None => std::slice::from_ref(value),
+    }
+}
+
 impl PlexMovie {
+    /// Tags des labels Plex du film
+    pub fn label_tags(&self) -> Vec<String> {
+        self.labels.as_ref()
+            .map(one_or_many)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|obj| obj.get("tag").and_then(|v| v.as_str()).map(str::to_string))
+            .collect()
+    }
+
     /// Vérifie si le film contient un label spécifique (insensible à la casse)
     pub fn has_label(&self, tag_to_find: &str) -> bool {
         let target = tag_to_find.to_lowercase();
@@ -140,6 +279,15 @@ impl PlexMovie {
         false
     }
     
+    /// Vrai quand Plex a terminé l'analyse : guids, résolution et flux (Stream) de chaque version
+    pub fn is_analyzed(&self) -> bool {
+        let has_guids = self.guids.as_ref().is_some_and(|g| !g.is_empty());
+        let media_ready = self.media.as_ref().is_some_and(|media| {
+            !media.is_empty() && media.iter().all(|m| m.video_resolution.is_some() && m.has_streams())
+        });
+        has_guids && media_ready
+    }
+
     pub fn is_recently_added(&self) -> bool {
         if let Some(added_at) = self.added_at {
             let now = std::time::SystemTime::now()
@@ -154,6 +302,40 @@ impl PlexMovie {
     }
 }
 
+impl PlexMedia {
+    /// Flux de toutes les parties (`Part` et `Stream` peuvent être un objet seul ou un tableau)
+    pub fn streams(&self) -> Vec<&serde_json::Value> {
+        let Some(parts) = &self.parts else {
+            return Vec::new();
+        };
+
+        one_or_many(parts).iter()
+            .filter_map(|part| part.get("Stream").or_else(|| part.get("stream")))
+            .flat_map(one_or_many)
+            .collect()
+    }
+
+    /// Flux d'un type donné (`STREAM_VIDEO`, `STREAM_AUDIO`, `STREAM_SUBTITLE`)
+    pub fn streams_of(&self, stream_type: u64) -> Vec<&serde_json::Value> {
+        self.streams().into_iter()
+            .filter(|s| s.get("streamType").and_then(|v| v.as_u64()) == Some(stream_type))
+            .collect()
+    }
+
+    /// Vrai si au moins une Part expose ses flux (audio / vidéo)
+    pub fn has_streams(&self) -> bool {
+        let Some(parts_value) = &self.parts else {
+            return false;
+        };
+
+        one_or_many(parts_value).iter().any(|part| match part.get("Stream") {
+            Some(serde_json::Value::Array(streams)) => !streams.is_empty(),
+            Some(serde_json::Value::Object(_)) => true,
+            _ => false,
+        })
+    }
+}
+
 impl PlexShow {
     /// Vérifie si la série contient un label spécifique (insensible à la casse)
     pub fn has_label(&self, tag_to_find: &str) -> bool {
@@ -178,6 +360,11 @@ impl PlexShow {
         false
     }
     
+    /// Vrai quand Plex a associé la série (guids disponibles)
+    pub fn is_matched(&self) -> bool {
+        self.guid.as_ref().is_some_and(|g| !g.is_empty())
+    }
+
     pub fn is_recently_added(&self) -> bool {
         if let Some(added_at) = self.added_at {
             let now = std::time::SystemTime::now()
@@ -236,18 +423,35 @@ impl PlexClient {
             .danger_accept_invalid_certs(true)
             .build()
             .unwrap();
-        Self { client, base_url, token }
+
+        // Réglages optionnels : PLEX_PAGE_SIZE, PLEX_BATCH_SIZE, PLEX_CONCURRENCY
+        let setting = |name: &str, default: usize| {
+            env::var(name).ok().and_then(|v| v.parse().ok()).filter(|v| *v > 0).unwrap_or(default)
+        };
+
+        Self {
+            client,
+            base_url,
+            token,
+            page_size: setting("PLEX_PAGE_SIZE", DEFAULT_PAGE_SIZE),
+            batch_size: setting("PLEX_BATCH_SIZE", DEFAULT_BATCH_SIZE),
+            concurrency: setting("PLEX_CONCURRENCY", DEFAULT_CONCURRENCY),
+        }
+    }
+
+    /// Envoie une requête vers Plex (latence et statut enregistrés dans les métriques)
+    async fn send(&self, endpoint: &str, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+        metrics::timed_send("plex", endpoint, request).await
     }
 
     pub async fn add_label(&self, rating_key: &str, label: &str) -> Result<()> {
         let url = format!("{}/library/metadata/{}?label%5B0%5D.tag.tag={}", self.base_url, rating_key, label);
         
-        let response = self.client
+        let request = self.client
             .put(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let response = self.send("label", request).await?;
 
         if response.status().is_success() {
             Ok(())
@@ -262,12 +466,11 @@ impl PlexClient {
             self.base_url, rating_key
         );
         
-        let resp = self.client
+        let request = self.client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = self.send("metadata", request).await?;
         
         if !resp.status().is_success() {
             return Ok(Vec::new());
@@ -298,73 +501,171 @@ impl PlexClient {
     // ========== FILMS ==========
 
     pub async fn get_library_items(&self, library_id: &str) -> Result<Vec<PlexMovie>> {
-        let url = format!(
-            "{}/library/sections/{}/all?type=1&includeGuids=1",
-            self.base_url, 
-            library_id
-        );
-        
-        let response = self.client
+        self.fetch_section(library_id, "type=1&includeGuids=1", "library_items").await
+    }
+
+    /// Parcourt une section page par page (X-Plex-Container-Start / X-Plex-Container-Size)
+    async fn fetch_section<T: DeserializeOwned>(&self, library_id: &str, query: &str, endpoint: &str) -> Result<Vec<T>> {
+        let url = format!("{}/library/sections/{}/all?{}", self.base_url, library_id, query);
+        let mut items = Vec::new();
+        let mut start = 0;
+
+        loop {
+            let request = self.client
+                .get(&url)
+                .header("Accept", "application/json")
+                .header("X-Plex-Token", &self.token)
+                .header("X-Plex-Container-Start", start.to_string())
+                .header("X-Plex-Container-Size", self.page_size.to_string());
+            let response = self.send(endpoint, request).await?;
+
+            if !response.status().is_success() {
+                return Err(anyhow::anyhow!("Erreur Plex HTTP {}", response.status()));
+            }
+
+            let mut json: serde_json::Value = response.json().await?;
+            let total = json["MediaContainer"]["totalSize"].as_u64().map(|t| t as usize);
+            let page = match json["MediaContainer"]["Metadata"].take() {
+                serde_json::Value::Array(page) => page,
+                _ => Vec::new(),
+            };
+
+            let page_len = page.len();
+            items.extend(page.into_iter().filter_map(|mut item| {
+                // Le listing ne répète pas la section sur chaque élément
+                if let Some(object) = item.as_object_mut() {
+                    object.entry("librarySectionID").or_insert_with(|| library_id.into());
+                }
+                let rating_key = item["ratingKey"].as_str().unwrap_or("?").to_string();
+                serde_json::from_value(item)
+                    .map_err(|e| warn!(library_id, rating_key, error = %e, "{}", locale::t("log.plex.unreadable_item")))
+                    .ok()
+            }));
+            start += page_len;
+
+            debug!(library_id, fetched = start, ?total, "Page de bibliothèque récupérée");
+
+            // Sans totalSize (anciens serveurs), une page incomplète marque la fin
+            let done = match total {
+                Some(total) => start >= total,
+                None => page_len < self.page_size,
+            };
+            if done || page_len == 0 {
+                break;
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Détails complets de plusieurs éléments en une requête (`/library/metadata/1,2,3`)
+    pub async fn get_metadata_batch<T: DeserializeOwned>(&self, rating_keys: &[&str]) -> Result<Vec<T>> {
+        let url = format!("{}/library/metadata/{}", self.base_url, rating_keys.join(","));
+
+        let request = self.client
             .get(&url)
             .header("Accept", "application/json")
-            .header("X-Plex-Token", &self.token)
-            .send()
-            .await?;
-        
+            .header("X-Plex-Token", &self.token);
+        let response = self.send("metadata_batch", request).await?;
+
         if !response.status().is_success() {
             return Err(anyhow::anyhow!("Erreur Plex HTTP {}", response.status()));
         }
 
-        let json: serde_json::Value = response.json().await?;
-        let mut movies = Vec::new();
-        
-        if let Some(metadata) = json["MediaContainer"]["Metadata"].as_array() {
-            for item in metadata {
-                if let Ok(movie) = serde_json::from_value(item.clone()) {
-                    movies.push(movie);
-                }
-            }
-        }
-        
-        Ok(movies)
+        let mut json: serde_json::Value = response.json().await?;
+        let items = match json["MediaContainer"]["Metadata"].take() {
+            serde_json::Value::Array(items) => items.into_iter().filter_map(|item| serde_json::from_value(item).ok()).collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(items)
     }
 
+    /// Films avec labels et média, récupérés par lots de `/library/metadata` en parallèle
     pub async fn get_library_items_with_labels(&self, library_id: &str) -> Result<Vec<PlexMovie>> {
         let movies = self.get_library_items(library_id).await?;
-        let total = movies.len();
-        
-        println!("📚 Chargement des labels pour {} films...", total);
-        
-        let mut detailed_movies = Vec::new();
-        
-        for (i, movie) in movies.into_iter().enumerate() {
-            match self.get_item_details(&movie.rating_key).await {
-                Ok(detailed) => {
-                    if (i + 1) % 20 == 0 {
-                        println!("   ⏳ Progression: {}/{}", i + 1, total);
+
+        info!(total = movies.len(), batch_size = self.batch_size, concurrency = self.concurrency, "{}", locale::t("log.plex.loading_labels"));
+        let detailed_movies = self.fetch_details(movies).await;
+
+        info!(count = detailed_movies.len(), "{}", locale::t("log.plex.labels_loaded"));
+        Ok(detailed_movies)
+    }
+
+    /// Éléments d'une section, éventuellement limités à ceux modifiés depuis `since` (timestamp Unix)
+    ///
+    /// `updatedAt` est renseigné dès l'ajout : le filtre couvre aussi les nouveaux éléments.
+    pub async fn get_section_items<T: PlexItem>(&self, library_id: &str, since: Option<u64>) -> Result<Vec<T>> {
+        let mut query = format!("type={}&includeGuids=1", T::PLEX_TYPE);
+        if let Some(since) = since {
+            // updatedAt>>=since (">>=" : supérieur ou égal pour les dates)
+            query.push_str(&format!("&updatedAt%3E%3E={}", since));
+        }
+        self.fetch_section(library_id, &query, "library_items").await
+    }
+
+    /// Nombre d'éléments d'une section (page vide, seul totalSize est lu)
+    pub async fn count_section_items(&self, library_id: &str, plex_type: u8) -> Result<usize> {
+        let url = format!("{}/library/sections/{}/all?type={}", self.base_url, library_id, plex_type);
+
+        let request = self.client
+            .get(&url)
+            .header("Accept", "application/json")
+            .header("X-Plex-Token", &self.token)
+            .header("X-Plex-Container-Start", "0")
+            .header("X-Plex-Container-Size", "0");
+        let response = self.send("library_count", request).await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Erreur Plex HTTP {}", response.status()));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+        let container = &json["MediaContainer"];
+        container["totalSize"].as_u64()
+            .or_else(|| container["size"].as_u64())
+            .map(|n| n as usize)
+            .ok_or_else(|| anyhow::anyhow!("totalSize absent de la réponse Plex"))
+    }
+
+    /// Remplace des éléments résumés par leurs détails (labels, média), par lots en parallèle
+    ///
+    /// Un élément absent de la réponse ou d'un lot en erreur garde sa version résumée.
+    pub async fn fetch_details<T: PlexItem>(&self, items: Vec<T>) -> Vec<T> {
+        let batches: Vec<Vec<T>> = items.chunks(self.batch_size).map(|c| c.to_vec()).collect();
+
+        stream::iter(batches)
+            .map(|batch| async move {
+                let keys: Vec<&str> = batch.iter().map(|i| i.rating_key()).collect();
+                match self.get_metadata_batch::<T>(&keys).await {
+                    Ok(detailed) => {
+                        let mut by_key: HashMap<String, T> = detailed.into_iter()
+                            .map(|i| (i.rating_key().to_string(), i))
+                            .collect();
+                        batch.into_iter()
+                            .map(|i| by_key.remove(i.rating_key()).unwrap_or(i))
+                            .collect::<Vec<_>>()
+                    },
+                    Err(e) => {
+                        warn!(count = batch.len(), error = ?e, "{}", locale::t("log.plex.batch_error"));
+                        batch
                     }
-                    detailed_movies.push(detailed);
-                },
-                Err(e) => {
-                    println!("   ⚠️  Erreur détails pour {}: {:?}", movie.title, e);
-                    detailed_movies.push(movie);
                 }
-            }
-        }
-        
-        println!("✅ Labels chargés pour {} films", detailed_movies.len());
-        Ok(detailed_movies)
+            })
+            .buffered(self.concurrency)
+            .flat_map(stream::iter)
+            .collect()
+            .await
     }
 
     pub async fn get_item_details(&self, rating_key: &str) -> Result<PlexMovie> {
         let url = format!("{}/library/metadata/{}", self.base_url, rating_key);
 
-        let response = self.client
+        let request = self.client
             .get(&url)
             .header("Accept", "application/json")
-            .header("X-Plex-Token", &self.token)
-            .send()
-            .await?;
+            .header("X-Plex-Token", &self.token);
+        let response = self.send("metadata", request).await?;
 
         let json: serde_json::Value = response.json().await?;
         
@@ -395,50 +696,63 @@ impl PlexClient {
         None
     }
 
-    // ========== SÉRIES ==========
+    /// ID IMDb ("tt0133093") parmi les guids d'un film ou d'une série
+    pub fn extract_imdb_id(guids: Option<&Vec<PlexGuid>>) -> Option<String> {
+        guids?.iter()
+            .find_map(|guid| guid.id.strip_prefix("imdb://"))
+            .map(str::to_string)
+    }
 
-    /// Récupère la liste des séries d'une bibliothèque (avec JSON API comme les films)
-    pub async fn get_shows_library_items(&self, library_id: &str) -> Result<Vec<PlexShow>> {
-        let url = format!(
-            "{}/library/sections/{}/all?type=2&includeGuids=1",
-            self.base_url, 
-            library_id
-        );
-        
-        println!("🔗 Récupération séries : library_id={}", library_id);
-        
-        let response = self.client
-            .get(&url)
-            .header("Accept", "application/json")
-            .header("X-Plex-Token", &self.token)
-            .send()
-            .await?;
-        
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!("Erreur Plex HTTP {}", response.status()));
-        }
+    // ========== RECHERCHE PAR GUID ==========
 
-        let json: serde_json::Value = response.json().await?;
-        let mut shows: Vec<PlexShow> = Vec::new();
-        
-        if let Some(metadata) = json["MediaContainer"]["Metadata"].as_array() {
-            for item in metadata {
-                if let Ok(show) = serde_json::from_value(item.clone()) {
-                    shows.push(show);
+    /// Éléments d'une section dont un guid correspond (ex : "tmdb://603", "tvdb://81189")
+    ///
+    /// Filtre `guid=` côté Plex, une requête par guid ; les guids renvoyés sont revérifiés
+    /// car les anciens serveurs ignorent le filtre et listent toute la section.
+    pub async fn find_by_guids<T: PlexItem>(&self, library_id: &str, guids: &[String]) -> Result<Vec<PlexGuidMatch>> {
+        let mut matches: Vec<PlexGuidMatch> = Vec::new();
+
+        for guid in guids {
+            let encoded: String = url::form_urlencoded::byte_serialize(guid.as_bytes()).collect();
+            let query = format!("type={}&guid={}&includeGuids=1", T::PLEX_TYPE, encoded);
+            let items: Vec<serde_json::Value> = self.fetch_section(library_id, &query, "find_by_guid").await?;
+
+            for item in items {
+                if !item_guids(&item).contains(guid) {
+                    continue;
+                }
+                if let Ok(found) = serde_json::from_value::<PlexGuidMatch>(item) {
+                    if !matches.iter().any(|m| m.rating_key == found.rating_key) {
+                        matches.push(PlexGuidMatch { library_id: library_id.to_string(), ..found });
+                    }
                 }
             }
         }
-        
-        println!("✅ {} séries parsées", shows.len());
-        
+
+        debug!(?guids, found = matches.len(), "Recherche par guid");
+        Ok(matches)
+    }
+
+    /// Éléments d'une section correspondant à un identifiant TMDB / IMDb / TVDB
+    pub async fn find_by_guid<T: PlexItem>(&self, library_id: &str, id: &ExternalId) -> Result<Vec<PlexGuidMatch>> {
+        self.find_by_guids::<T>(library_id, &[id.to_guid()]).await
+    }
+
+    // ========== SÉRIES ==========
+
+    /// Récupère la liste des séries d'une bibliothèque (avec JSON API comme les films)
+    pub async fn get_shows_library_items(&self, library_id: &str) -> Result<Vec<PlexShow>> {
+        debug!(library_id, "Récupération des séries");
+
+        let shows: Vec<PlexShow> = self.fetch_section(library_id, "type=2&includeGuids=1", "library_items").await?;
+
+        debug!(count = shows.len(), "Séries parsées");
+
         // Debug première série
         if let Some(first) = shows.first() {
-            println!("🔍 Exemple : {} - Has Label: {}", 
-                first.title,
-                first.has_label("Rustizarr")
-            );
+            debug!(title = %first.title, has_label = first.has_label("Rustizarr"), "Exemple de série");
         }
-        
+
         Ok(shows)
     }
 
@@ -446,12 +760,11 @@ impl PlexClient {
     pub async fn get_show_details(&self, rating_key: &str) -> Result<PlexShow> {
         let url = format!("{}/library/metadata/{}", self.base_url, rating_key);
 
-        let response = self.client
+        let request = self.client
             .get(&url)
             .header("Accept", "application/json")
-            .header("X-Plex-Token", &self.token)
-            .send()
-            .await?;
+            .header("X-Plex-Token", &self.token);
+        let response = self.send("metadata", request).await?;
 
         let json: serde_json::Value = response.json().await?;
         
@@ -483,12 +796,11 @@ impl PlexClient {
             self.base_url, show_rating_key
         );
         
-        let resp = self.client
+        let request = self.client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = self.send("children", request).await?;
         
         let json: serde_json::Value = resp.json().await?;
         let mut seasons = Vec::new();
@@ -504,19 +816,38 @@ impl PlexClient {
         Ok(seasons)
     }
 
+    /// Récupère les détails d'une saison
+    pub async fn get_season_details(&self, rating_key: &str) -> Result<PlexSeason> {
+        let url = format!("{}/library/metadata/{}", self.base_url, rating_key);
+
+        let request = self.client
+            .get(&url)
+            .header("Accept", "application/json")
+            .header("X-Plex-Token", &self.token);
+        let response = self.send("metadata", request).await?;
+
+        let json: serde_json::Value = response.json().await?;
+
+        if let Some(metadata) = json["MediaContainer"]["Metadata"].get(0) {
+            let season: PlexSeason = serde_json::from_value(metadata.clone())?;
+            return Ok(season);
+        }
+
+        Err(anyhow::anyhow!("Saison introuvable"))
+    }
+
     // ========== COMMUN ==========
 
-    pub async fn upload_poster(&self, rating_key: &str, image_data: Vec<u8>) -> Result<()> {
+    pub async fn upload_poster(&self, rating_key: &str, image_data: Vec<u8>, content_type: &str) -> Result<()> {
         let url = format!("{}/library/metadata/{}/posters", self.base_url, rating_key);
 
-        let response = self.client
+        let request = self.client
             .post(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Content-Type", "image/jpeg") 
+            .header("Content-Type", content_type) 
             .header("Accept", "application/json")
-            .body(image_data) 
-            .send()
-            .await?;
+            .body(image_data) ;
+        let response = self.send("upload_poster", request).await?;
 
         if response.status().is_success() {
             Ok(())
@@ -526,3 +857,54 @@ impl PlexClient {
         }
     }
 }
+
+/// Guids d'un élément, y compris le guid des anciens agents normalisé
+/// ("com.plexapp.agents.themoviedb://603?lang=fr" → "tmdb://603")
+fn item_guids(item: &serde_json::Value) -> Vec<String> {
+    let mut guids: Vec<String> = item["Guid"].as_array()
+        .map(|arr| arr.iter().filter_map(|g| g["id"].as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    if let Some(legacy) = item["guid"].as_str() {
+        let legacy = legacy.split('?').next().unwrap_or(legacy);
+        for (agent, scheme) in [("themoviedb://", "tmdb://"), ("imdb://", "imdb://"), ("thetvdb://", "tvdb://")] {
+            if let Some((_, id)) = legacy.split_once(agent) {
+                guids.push(format!("{}{}", scheme, id.split('/').next().unwrap_or(id)));
+            }
+        }
+    }
+
+    guids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn movie(media: serde_json::Value) -> PlexMovie {
+        serde_json::from_value(json!({
+            "title": "Dune",
+            "ratingKey": "1",
+            "Guid": [{ "id": "tmdb://438631" }],
+            "Media": media,
+        })).unwrap()
+    }
+
+    fn analyzed_version() -> serde_json::Value {
+        json!({ "videoResolution": "4k", "Part": [{ "Stream": [{ "streamType": 1 }] }] })
+    }
+
+    #[test]
+    fn analyzed_when_every_version_is_ready() {
+        assert!(movie(json!([analyzed_version(), analyzed_version()])).is_analyzed());
+    }
+
+    #[test]
+    fn not_analyzed_while_a_version_is_pending() {
+        let pending = json!({ "videoResolution": "1080", "Part": [{}] });
+        assert!(!movie(json!([analyzed_version(), pending])).is_analyzed());
+        assert!(!movie(json!([{ "Part": [{ "Stream": [{ "streamType": 1 }] }] }])).is_analyzed());
+        assert!(!movie(json!([])).is_analyzed());
+    }
+}
@@ -1,12 +1,21 @@
 // backend/src/processor.rs
-use crate::plex::{PlexClient, PlexMovie, PlexMedia, PlexShow, PlexSeason};
-use crate::tmdb::TmdbClient;
-use crate::image_ops::ImageProcessor;
+use crate::plex::{PlexClient, PlexGuid, PlexMovie, PlexMedia, PlexRating, PlexShow, PlexSeason, STREAM_AUDIO};
+use crate::ratings::{RatingLevel, RatingSource, Ratings, RatingsConfig};
+use crate::theme::{PosterTheme, ThemeConfig};
+use crate::tmdb::{Details, SeasonInfo, TmdbClient, Votes};
+use crate::badges::{AudioBadges, BadgeConfig, BadgePosition, BadgeSpec, MediaFacts};
+use crate::dynamic_range::DynamicRange;
+use crate::image_ops::{BadgeRow, ImageProcessor};
+use crate::locale;
+use crate::metrics;
+use crate::output::{self, EncodedPoster, OutputSettings};
+use crate::report::{MediaKind, ProcessOutcome, ProcessReport, Stage};
 use anyhow::Result;
+use image::DynamicImage;
 use std::path::Path;
-use std::io::Cursor;
 use std::env;
 use futures::stream::{self, StreamExt};
+use tracing::{debug, error, info, warn, Instrument};
 
 // ==================== FILMS ====================
 
@@ -14,143 +23,224 @@ use futures::stream::{self, StreamExt};
 pub async fn process_movie(
     plex: &PlexClient,
     tmdb: &TmdbClient,
+    render_config: &RenderConfig,
     movie: PlexMovie
-) -> Result<String> {
-    
+) -> ProcessReport {
+    let span = tracing::info_span!("movie", rating_key = %movie.rating_key, title = %movie.title);
+    let mut report = ProcessReport::new(MediaKind::Movie, &movie.rating_key, &movie.title);
+    let outcome = render_movie(plex, tmdb, render_config, &movie, &mut report).instrument(span).await;
+    report.finish(outcome)
+}
+
+async fn render_movie(
+    plex: &PlexClient,
+    tmdb: &TmdbClient,
+    render_config: &RenderConfig,
+    movie: &PlexMovie,
+    report: &mut ProcessReport
+) -> ProcessOutcome {
     let tmdb_id_opt = if let Some(forced_id) = get_forced_tmdb_id(&movie.title) {
-        println!("   🔧 OVERRIDE MANUEL ACTIVÉ : Utilisation de l'ID {}", forced_id);
+        info!(tmdb_id = %forced_id, "{}", locale::t("log.process.tmdb_override"));
         Some(forced_id)
     } else {
-        PlexClient::extract_tmdb_id(&movie)
+        PlexClient::extract_tmdb_id(movie)
     };
 
-    if let Some(tmdb_id) = tmdb_id_opt {
-        let mut final_url = None;
-        
-        match tmdb.get_textless_poster(&tmdb_id).await {
-            Ok(Some(url)) => final_url = Some(url),
-            Ok(None) => {
-                println!("   ⚠️ Pas de poster textless. Tentative poster standard...");
-                if let Ok(Some(std_url)) = tmdb.get_standard_poster(&tmdb_id).await {
-                    final_url = Some(std_url);
-                }
-            }
-            Err(e) => println!("   ❌ Erreur API TMDB : {:?}", e),
+    let Some(tmdb_id) = tmdb_id_opt else {
+        warn!("{}", locale::t("log.process.no_tmdb_id"));
+        return ProcessOutcome::NoTmdbId;
+    };
+
+    let lookup = Stage::start();
+    let textless = match tmdb.get_textless_poster(&tmdb_id).await {
+        Ok(url) => url,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.tmdb_error"));
+            return ProcessOutcome::LookupFailed { error: format!("{:?}", e) };
+        }
+    };
+    let logo_url = match TitleMode::for_library(movie.library_id.as_deref()) {
+        TitleMode::Logo => lookup_logo(tmdb.get_movie_logo(&tmdb_id).await),
+        TitleMode::Text => None,
+    };
+    let mut ratings = collect_ratings(movie.audience_rating, movie.ratings.as_ref(), movie.guids.as_ref());
+    let needs_votes = needs_tmdb_votes(&render_config.badges.ratings, &ratings);
+
+    // Poster standard et note TMDB : une seule requête de détails, seulement si utile
+    let details = if textless.is_none() || needs_votes {
+        tmdb_details(tmdb.get_movie_details(&tmdb_id).await)
+    } else {
+        Details::default()
+    };
+    if needs_votes {
+        add_tmdb_votes(&mut ratings, details.votes);
+    }
+    report.timings.lookup_ms = lookup.ms();
+
+    let final_url = textless.or_else(|| {
+        debug!("Pas de poster textless, tentative poster standard");
+        details.poster_url
+    });
+    let Some(url) = final_url else {
+        warn!("{}", locale::t("log.process.no_poster"));
+        return ProcessOutcome::NoPoster;
+    };
+
+    let poster = match download_poster(&url, report).await {
+        Ok(poster) => poster,
+        Err(outcome) => return outcome,
+    };
+    let logo = download_logo(logo_url).await;
+
+    let render = Stage::start();
+    let rendered = apply_movie_overlays(poster, movie, logo.as_ref(), render_config, &ratings, report).and_then(|p| encode_poster(&p));
+    report.timings.render_ms = render.ms();
+
+    match rendered {
+        Ok(encoded) => upload_and_label(plex, &movie.rating_key, &movie.title, encoded, report).await,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.render_error"));
+            ProcessOutcome::RenderFailed { error: format!("{:?}", e) }
         }
+    }
+}
+
+fn apply_movie_overlays(
+    mut poster: DynamicImage,
+    movie: &PlexMovie,
+    logo: Option<&DynamicImage>,
+    render_config: &RenderConfig,
+    ratings: &Ratings,
+    report: &mut ProcessReport
+) -> Result<DynamicImage> {
+    let overlays_base = get_overlays_path();
+    let theme = poster_theme(&poster, &render_config.theme, report);
+    let badges = render_config.badges.clone().with_theme(&theme);
+
+    // Effets de base
+    poster = ImageProcessor::add_gradient_masks(poster, &overlays_base, theme.variant.gradient_opacity)?;
+    report.add_overlay("gradients");
+    debug!("Gradients appliqués");
+
+    poster = apply_title(poster, &movie.title, logo, &theme, &overlays_base, report)?;
+
+    let base_path = Path::new(&overlays_base).join("media_info");
+    let versions = movie.media.as_deref().unwrap_or_default();
+    let media = VersionPolicy::from_env().select(versions);
+    let audio_badges = AudioBadges::from_env();
+    let facts = MediaFacts::from_movie(movie, media);
+
+    let mut top_left = BadgeRow::top_left(0.065);
+    let mut bottom_left = BadgeRow::bottom_left(0.050);
 
-        if let Some(url) = final_url {
-            println!("   📸 Poster trouvé, téléchargement...");
-            
-            match ImageProcessor::download_image(&url).await {
-                Ok(mut poster) => {
-                    println!("   ✅ Image téléchargée : {}x{}", poster.width(), poster.height());
-                    
-                    let overlays_base = get_overlays_path();
-                    
-                    // Effets de base
-                    poster = ImageProcessor::add_gradient_masks(poster, &overlays_base)?;
-                    println!("   ✅ Gradients appliqués");
-                    
-                    poster = ImageProcessor::add_movie_title(poster, &movie.title, &overlays_base)?;
-                    println!("   ✅ Titre ajouté");
-
-                    let base_path = Path::new(&overlays_base).join("media_info");
-                    let audience_path = Path::new(&overlays_base).join("audience_score");
-                    
-                    let mut top_left_index = 0;
-
-                    // Overlay RÉSOLUTION (haut-gauche)
-                    if let Some(media_list) = &movie.media {
-                        if let Some(media) = media_list.first() {
-                            if let Some(res_file) = get_resolution_filename(media) {
-                                let path = base_path.join("resolution").join(res_file);
-                                if let Ok(img) = ImageProcessor::add_overlay(poster.clone(), &path, top_left_index, false, 0.065) {
-                                    poster = img;
-                                    top_left_index += 1;
-                                    println!("   ✅ Overlay résolution ajouté");
-                                }
-                            }
-                        }
-                    }
-
-                    // Overlay ÉDITION (haut-gauche)
-                    if let Some(edition_file) = get_edition_filename(&movie) {
-                        let path = base_path.join("edition").join(edition_file);
-                        if let Ok(img) = ImageProcessor::add_overlay(poster.clone(), &path, top_left_index, false, 0.065) {
-                            poster = img;
-                            println!("   ✅ Overlay édition ajouté");
-                        }
-                    }
-
-                    // Overlay CODEC AUDIO (bas-gauche)
-                    if let Some(media_list) = &movie.media {
-                        if let Some(media) = media_list.first() {
-                            if let Some(audio_file) = get_codec_combo_filename(media) {
-                                let path = base_path.join("codec").join(audio_file);
-                                if let Ok(img) = ImageProcessor::add_overlay(poster.clone(), &path, 0, true, 0.050) {
-                                    poster = img;
-                                    println!("   ✅ Overlay codec ajouté");
-                                }
-                            }
-                        }
-                    }
-
-                    // Overlay AUDIENCE SCORE (bas-droite)
-                    if let Some(rating) = movie.audience_rating {
-                        println!("   🎯 Score audience détecté : {}/10", rating);
-                        let badge_file = get_audience_badge_filename(rating);
-                        let full_path = audience_path.join(badge_file);
-                        
-                        if let Ok(img) = ImageProcessor::add_overlay_bottom_right(poster.clone(), &full_path, 0.065, Some(rating), &overlays_base) {
-                            poster = img;
-                            println!("   ✅ Badge audience ajouté avec note {:.1}", rating);
-                        }
-                    }
-
-                    // ✅ BORDURE : Recently Added OU Inner Glow
-                    if movie.is_recently_added() {
-                        poster = ImageProcessor::add_status_border(poster, &overlays_base, "recently_added.png")?;
-                        println!("   ✅ Bordure 'Recently Added' appliquée");
-                    } else {
-                        poster = ImageProcessor::add_inner_glow_border(poster, &overlays_base)?;
-                        println!("   ✅ Inner glow appliqué");
-                    }
-
-                    // Upload vers Plex
-                    let rgb_poster = poster.to_rgb8(); 
-                    let mut bytes: Vec<u8> = Vec::new();
-                    rgb_poster.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Jpeg)?;
-
-                    match plex.upload_poster(&movie.rating_key, bytes).await {
-                        Ok(_) => {
-                            let msg = format!("✅ SUCCÈS : '{}'", movie.title);
-                            println!("{}", msg);
-                            println!("   🏷️ Ajout du label 'Rustizarr'...");
-                            
-                            if let Err(e) = plex.add_label(&movie.rating_key, "Rustizarr").await {
-                                println!("      ⚠️ Echec ajout label : {:?}", e);
-                            }
-                            return Ok(msg);
-                        },
-                        Err(e) => {
-                            println!("❌ Erreur upload Plex : {:?}", e);
-                            return Err(anyhow::anyhow!("Erreur upload"));
-                        },
-                    }
-                },
-                Err(e) => {
-                    println!("   ❌ ERREUR TÉLÉCHARGEMENT : {:?}", e);
-                    return Ok("Échec téléchargement image".to_string());
+    // Overlay RÉSOLUTION (haut-gauche)
+    if let Some(res_file) = media.and_then(get_resolution_filename) {
+        let path = base_path.join("resolution").join(&res_file);
+        if let Some(badge) = load_file_badge(&path, &poster, &top_left) {
+            poster = ImageProcessor::add_badge(poster, &badge, &mut top_left);
+            report.add_overlay(format!("resolution:{}", res_file));
+            debug!(overlay = %res_file, "Overlay résolution ajouté");
+        }
+    }
+
+    // Overlay ÉDITION (haut-gauche)
+    if let Some(edition_file) = get_edition_filename(movie) {
+        let path = base_path.join("edition").join(edition_file);
+        if let Some(badge) = load_file_badge(&path, &poster, &top_left) {
+            poster = ImageProcessor::add_badge(poster, &badge, &mut top_left);
+            report.add_overlay(format!("edition:{}", edition_file));
+            debug!(overlay = edition_file, "Overlay édition ajouté");
+        }
+    }
+
+    // Overlay CODEC AUDIO (bas-gauche) : PNG de la combinaison, sinon badge généré
+    if let Some(media) = media {
+        let file_badge = get_codec_combo_filename(media).and_then(|audio_file| {
+            let path = base_path.join("codec").join(&audio_file);
+            load_file_badge(&path, &poster, &bottom_left).map(|badge| (badge, audio_file))
+        });
+
+        if let Some((badge, audio_file)) = file_badge {
+            // Plage dynamique sans visuel (HLG) : badge généré avant le PNG audio
+            let range_label = DynamicRange::from_media(media)
+                .filter(|range| range.asset_token().is_none())
+                .and_then(|range| range.label());
+            if let Some(label) = range_label {
+                if let Some(range_badge) = render_generated_badge(&badges.generic(&label), &poster, &bottom_left, &overlays_base) {
+                    poster = ImageProcessor::add_badge(poster, &range_badge, &mut bottom_left);
+                    report.add_overlay(format!("range:generated:{}", label));
+                    debug!(label = %label, "Badge plage dynamique généré");
                 }
             }
-        } else {
-            println!("   ❌ ABANDON : Aucune image trouvée sur TMDB.");
+
+            poster = ImageProcessor::add_badge(poster, &badge, &mut bottom_left);
+            report.add_overlay(format!("codec:{}", audio_file));
+            debug!(overlay = %audio_file, "Overlay codec ajouté");
+        } else if let Some(text) = get_codec_badge_text(media) {
+            if let Some(badge) = render_generated_badge(&badges.generic(&text), &poster, &bottom_left, &overlays_base) {
+                poster = ImageProcessor::add_badge(poster, &badge, &mut bottom_left);
+                report.add_overlay(format!("codec:generated:{}", text));
+                debug!(text = %text, "Badge codec généré");
+            }
         }
+    }
+
+    // Badge CANAUX (bas-gauche, optionnel)
+    if let Some(layout) = facts.channels.as_ref().filter(|_| audio_badges.channels) {
+        if let Some(badge) = render_generated_badge(&badges.generic(layout), &poster, &bottom_left, &overlays_base) {
+            poster = ImageProcessor::add_badge(poster, &badge, &mut bottom_left);
+            report.add_overlay(format!("channels:{}", layout));
+            debug!(layout = %layout, "Badge canaux ajouté");
+        }
+    }
+
+    // Badge LANGUE (haut-gauche, optionnel) : VF, VF/VO, MULTI, VOSTFR, VO
+    if let Some(text) = audio_badges.language.as_deref().and_then(|local| facts.language_badge(local)) {
+        if let Some(badge) = render_generated_badge(&badges.generic(&text), &poster, &top_left, &overlays_base) {
+            poster = ImageProcessor::add_badge(poster, &badge, &mut top_left);
+            report.add_overlay(format!("language:{}", text));
+            debug!(text = %text, "Badge langue ajouté");
+        }
+    }
+
+    // Badge VERSIONS (haut-gauche, optionnel) : "2 versions"
+    if versions.len() > 1 && env_flag("VERSIONS_BADGE") {
+        let text = locale::tf("badge.versions", &[("n", &versions.len().to_string())]);
+        if let Some(badge) = render_generated_badge(&badges.generic(&text), &poster, &top_left, &overlays_base) {
+            poster = ImageProcessor::add_badge(poster, &badge, &mut top_left);
+            report.add_overlay(format!("versions:{}", versions.len()));
+            debug!(versions = versions.len(), "Badge versions ajouté");
+        }
+    }
+
+    // Badges PERSONNALISÉS (badges.json)
+    for custom in badges.matching(&facts) {
+        let row = match custom.position {
+            BadgePosition::TopLeft => &mut top_left,
+            BadgePosition::BottomLeft => &mut bottom_left,
+        };
+        if let Some(badge) = render_generated_badge(&badges.spec(custom), &poster, row, &overlays_base) {
+            poster = ImageProcessor::add_badge(poster, &badge, row);
+            report.add_overlay(format!("badge:{}", custom.text));
+            debug!(text = %custom.text, "Badge personnalisé ajouté");
+        }
+    }
+
+    // Notes (bas-droite)
+    poster = apply_rating_badges(poster, ratings, &badges.ratings, &badges, &theme, &overlays_base, report);
+
+    // ✅ BORDURE : Recently Added OU Inner Glow
+    if movie.is_recently_added() {
+        poster = ImageProcessor::add_status_border(poster, &overlays_base, "recently_added.png", theme.border_tint)?;
+        report.add_overlay("border:recently_added");
+        debug!("Bordure 'Recently Added' appliquée");
     } else {
-        println!("   ⚠️ Pas d'ID TMDB trouvé.");
+        poster = ImageProcessor::add_inner_glow_border(poster, &overlays_base, theme.border_tint)?;
+        report.add_overlay("border:inner_glow");
+        debug!("Inner glow appliqué");
     }
-    
-    Ok("Film ignoré ou échec partiel".to_string())
+
+    Ok(poster)
 }
 
 // ==================== SÉRIES ====================
@@ -159,101 +249,106 @@ pub async fn process_movie(
 pub async fn process_show(
     plex: &PlexClient,
     tmdb: &TmdbClient,
+    render_config: &RenderConfig,
     show: PlexShow
-) -> Result<String> {
-    
-    let tmdb_id_opt = PlexClient::extract_tmdb_id_from_show(&show);
-
-    if let Some(tmdb_id) = tmdb_id_opt {
-        let mut final_url = None;
-        
-        // Récupération du poster
-        match tmdb.get_show_textless_poster(&tmdb_id).await {
-            Ok(Some(url)) => final_url = Some(url),
-            Ok(None) => {
-                println!("   ⚠️ Pas de poster textless. Tentative poster standard...");
-                if let Ok(Some(std_url)) = tmdb.get_show_standard_poster(&tmdb_id).await {
-                    final_url = Some(std_url);
-                }
-            }
-            Err(e) => println!("   ❌ Erreur API TMDB : {:?}", e),
+) -> ProcessReport {
+    let span = tracing::info_span!("show", rating_key = %show.rating_key, title = %show.title);
+    let mut report = ProcessReport::new(MediaKind::Show, &show.rating_key, &show.title);
+    let outcome = render_show(plex, tmdb, render_config, &show, &mut report).instrument(span).await;
+    report.finish(outcome)
+}
+
+async fn render_show(
+    plex: &PlexClient,
+    tmdb: &TmdbClient,
+    render_config: &RenderConfig,
+    show: &PlexShow,
+    report: &mut ProcessReport
+) -> ProcessOutcome {
+    let Some(tmdb_id) = PlexClient::extract_tmdb_id_from_show(show) else {
+        warn!("{}", locale::t("log.process.no_tmdb_id"));
+        return ProcessOutcome::NoTmdbId;
+    };
+
+    // Récupération du poster
+    let lookup = Stage::start();
+    let textless = match tmdb.get_show_textless_poster(&tmdb_id).await {
+        Ok(url) => url,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.tmdb_error"));
+            return ProcessOutcome::LookupFailed { error: format!("{:?}", e) };
         }
+    };
 
-        // Récupération du status
-        let show_status = tmdb.get_show_status(&tmdb_id).await.ok().flatten();
-
-        if let Some(url) = final_url {
-            println!("   📸 Poster trouvé, téléchargement...");
-            
-            match ImageProcessor::download_image(&url).await {
-                Ok(mut poster) => {
-                    println!("   ✅ Image téléchargée : {}x{}", poster.width(), poster.height());
-                    
-                    let overlays_base = get_overlays_path();
-                    
-                    // Effets de base
-                    poster = ImageProcessor::add_gradient_masks(poster, &overlays_base)?;
-                    println!("   ✅ Gradients appliqués");
-
-                    poster = ImageProcessor::add_movie_title(poster, &show.title, &overlays_base)?;
-                    println!("   ✅ Titre ajouté");
-
-                    let audience_path = Path::new(&overlays_base).join("audience_score");
-
-                    // ❌ PAS d'overlay résolution pour les séries
-                    // ❌ PAS d'overlay codec pour les séries
-
-                    // Overlay AUDIENCE SCORE (bas-droite)
-                    if let Some(rating) = show.audience_rating {
-                        println!("   🎯 Score audience détecté : {}/10", rating);
-                        let badge_file = get_audience_badge_filename(rating);
-                        let full_path = audience_path.join(badge_file);
-                        
-                        if let Ok(img) = ImageProcessor::add_overlay_bottom_right(poster.clone(), &full_path, 0.065, Some(rating), &overlays_base) {
-                            poster = img;
-                            println!("   ✅ Badge audience ajouté avec note {:.1}", rating);
-                        }
-                    }
-
-                    // ✅ BORDURE : Status > Recently Added > Inner Glow
-                    if let Some(ref status) = show_status {
-                        println!("   🔍 Status de la série : '{}'", status);
-                        let status_file = get_status_filename(status);
-                        println!("   📂 Fichier status : {}", status_file);
-                        poster = ImageProcessor::add_status_border(poster, &overlays_base, status_file)?;
-                    } else if show.is_recently_added() {
-                        println!("   📅 Série récente (< 15j), bordure 'Recently Added'");
-                        poster = ImageProcessor::add_status_border(poster, &overlays_base, "recently_added.png")?;
-                    } else {
-                        println!("   ✅ Pas de status ni récent, application inner glow");
-                        poster = ImageProcessor::add_inner_glow_border(poster, &overlays_base)?;
-                    }
-
-                    // Upload vers Plex
-                    let rgb_poster = poster.to_rgb8(); 
-                    let mut bytes: Vec<u8> = Vec::new();
-                    rgb_poster.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Jpeg)?;
-
-                    plex.upload_poster(&show.rating_key, bytes).await?;
-                    plex.add_label(&show.rating_key, "Rustizarr").await.ok();
-                    
-                    let msg = format!("✅ SUCCÈS : '{}'", show.title);
-                    println!("{}", msg);
-                    return Ok(msg);
-                },
-                Err(e) => {
-                    println!("   ❌ ERREUR TÉLÉCHARGEMENT : {:?}", e);
-                    return Ok("Échec téléchargement image".to_string());
-                }
-            }
-        } else {
-            println!("   ❌ ABANDON : Aucune image trouvée sur TMDB.");
+    // Status, poster standard et note TMDB : une seule requête de détails
+    let details = tmdb_details(tmdb.get_show_details(&tmdb_id).await);
+    let show_status = details.status;
+    let logo_url = match TitleMode::for_library(show.library_id.as_deref()) {
+        TitleMode::Logo => lookup_logo(tmdb.get_show_logo(&tmdb_id).await),
+        TitleMode::Text => None,
+    };
+    let mut ratings = collect_ratings(show.audience_rating, show.ratings.as_ref(), show.guid.as_ref());
+    if needs_tmdb_votes(&render_config.badges.ratings, &ratings) {
+        add_tmdb_votes(&mut ratings, details.votes);
+    }
+    report.timings.lookup_ms = lookup.ms();
+
+    let final_url = textless.or_else(|| {
+        debug!("Pas de poster textless, tentative poster standard");
+        details.poster_url
+    });
+    let Some(url) = final_url else {
+        warn!("{}", locale::t("log.process.no_poster"));
+        return ProcessOutcome::NoPoster;
+    };
+
+    let poster = match download_poster(&url, report).await {
+        Ok(poster) => poster,
+        Err(outcome) => return outcome,
+    };
+    let logo = download_logo(logo_url).await;
+
+    let render = Stage::start();
+    let rendered = apply_show_overlays(poster, show, show_status.as_deref(), logo.as_ref(), render_config, &ratings, report).and_then(|p| encode_poster(&p));
+    report.timings.render_ms = render.ms();
+
+    match rendered {
+        Ok(encoded) => upload_and_label(plex, &show.rating_key, &show.title, encoded, report).await,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.render_error"));
+            ProcessOutcome::RenderFailed { error: format!("{:?}", e) }
         }
-    } else {
-        println!("   ⚠️ Pas d'ID TMDB trouvé.");
     }
-    
-    Ok("Série ignorée ou échec partiel".to_string())
+}
+
+fn apply_show_overlays(
+    mut poster: DynamicImage,
+    show: &PlexShow,
+    show_status: Option<&str>,
+    logo: Option<&DynamicImage>,
+    render_config: &RenderConfig,
+    ratings: &Ratings,
+    report: &mut ProcessReport
+) -> Result<DynamicImage> {
+    let overlays_base = get_overlays_path();
+    let theme = poster_theme(&poster, &render_config.theme, report);
+    let badges = render_config.badges.clone().with_theme(&theme);
+
+    // Effets de base
+    poster = ImageProcessor::add_gradient_masks(poster, &overlays_base, theme.variant.gradient_opacity)?;
+    report.add_overlay("gradients");
+    debug!("Gradients appliqués");
+
+    poster = apply_title(poster, &show.title, logo, &theme, &overlays_base, report)?;
+
+    // ❌ PAS d'overlay résolution pour les séries
+    // ❌ PAS d'overlay codec pour les séries
+
+    // Notes (bas-droite)
+    poster = apply_rating_badges(poster, ratings, &badges.ratings, &badges, &theme, &overlays_base, report);
+
+    // ✅ BORDURE : Status > Recently Added > Inner Glow
+    apply_status_border(poster, show_status, show.is_recently_added(), &theme, &overlays_base, report)
 }
 
 // ==================== SAISONS ====================
@@ -262,144 +357,487 @@ pub async fn process_show(
 pub async fn process_season(
     plex: &PlexClient,
     tmdb: &TmdbClient,
+    render_config: &RenderConfig,
     season: PlexSeason,
     show_tmdb_id: &str,
     show_status: Option<String>
-) -> Result<String> {
-    
-    let poster_url = tmdb.get_season_poster(show_tmdb_id, season.season_number).await?;
-    
-    if let Some(url) = poster_url {
-        println!("   📸 Poster saison {} trouvé, téléchargement...", season.season_number);
-        
-        match ImageProcessor::download_image(&url).await {
-            Ok(mut poster) => {
-                println!("   ✅ Image téléchargée : {}x{}", poster.width(), poster.height());
-                
-                let overlays_base = get_overlays_path();
-                
-                // Effets de base
-                poster = ImageProcessor::add_gradient_masks(poster, &overlays_base)?;
-                println!("   ✅ Gradients appliqués");
-                
-                // Titre : "NOM SÉRIE - Saison X"
-                let title_text = format!("{} - Saison {}", season.show_title, season.season_number);
-                poster = ImageProcessor::add_movie_title(poster, &title_text, &overlays_base)?;
-                println!("   ✅ Titre ajouté");
-
-                let audience_path = Path::new(&overlays_base).join("audience_score");
-
-                // ❌ PAS d'overlay résolution pour les saisons
-                // ❌ PAS d'overlay codec pour les saisons
-
-                // Audience Score
-                if let Some(rating) = season.audience_rating {
-                    println!("   🎯 Score audience saison : {}/10", rating);
-                    let badge_file = get_audience_badge_filename(rating);
-                    let full_path = audience_path.join(badge_file);
-                    
-                    if let Ok(img) = ImageProcessor::add_overlay_bottom_right(poster.clone(), &full_path, 0.065, Some(rating), &overlays_base) {
-                        poster = img;
-                        println!("   ✅ Badge audience ajouté");
-                    }
-                }
+) -> ProcessReport {
+    let title = SeasonTitles::from_env().format(&season);
+    let span = tracing::info_span!("season", rating_key = %season.rating_key, title = %title, season = season.season_number);
+    let mut report = ProcessReport::new(MediaKind::Season, &season.rating_key, &title);
+    let outcome = render_season(plex, tmdb, render_config, &season, show_tmdb_id, show_status.as_deref(), &mut report)
+        .instrument(span)
+        .await;
+    report.finish(outcome)
+}
 
-                // ✅ BORDURE : Status (du show) > Recently Added (de la saison) > Inner Glow
-                if let Some(status) = show_status {
-                    println!("   🔍 Status du show (pour saison) : '{}'", status);
-                    let status_file = get_status_filename(&status);
-                    poster = ImageProcessor::add_status_border(poster, &overlays_base, status_file)?;
-                } else if season.is_recently_added() {
-                    println!("   📅 Saison récente (< 15j), bordure 'Recently Added'");
-                    poster = ImageProcessor::add_status_border(poster, &overlays_base, "recently_added.png")?;
-                } else {
-                    println!("   ✅ Pas de status ni récent, application inner glow");
-                    poster = ImageProcessor::add_inner_glow_border(poster, &overlays_base)?;
-                }
+async fn render_season(
+    plex: &PlexClient,
+    tmdb: &TmdbClient,
+    render_config: &RenderConfig,
+    season: &PlexSeason,
+    show_tmdb_id: &str,
+    show_status: Option<&str>,
+    report: &mut ProcessReport
+) -> ProcessOutcome {
+    // Titre formaté (SeasonTitles), déjà porté par le rapport
+    let title = report.title.clone();
+    let lookup = Stage::start();
+    let textless = match tmdb.get_season_textless_poster(show_tmdb_id, season.season_number).await {
+        Ok(url) => url,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.tmdb_error"));
+            return ProcessOutcome::LookupFailed { error: format!("{:?}", e) };
+        }
+    };
 
-                // Upload vers Plex
-                let rgb_poster = poster.to_rgb8(); 
-                let mut bytes: Vec<u8> = Vec::new();
-                rgb_poster.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Jpeg)?;
+    // Poster standard et note TMDB : la note sert quand Plex n'en a pas
+    let info = if textless.is_none() || season.audience_rating.is_none() {
+        tmdb.get_season_info(show_tmdb_id, season.season_number).await.unwrap_or_default()
+    } else {
+        SeasonInfo::default()
+    };
+    report.timings.lookup_ms = lookup.ms();
 
-                plex.upload_poster(&season.rating_key, bytes).await?;
-                plex.add_label(&season.rating_key, "Rustizarr").await.ok();
-                
-                Ok(format!("✅ Saison {} traitée", season.season_number))
-            },
-            Err(e) => Err(anyhow::anyhow!("Erreur téléchargement: {:?}", e))
+    let poster_url = textless.or_else(|| {
+        debug!("Pas de poster textless, tentative poster standard");
+        info.poster_url
+    });
+    let Some(url) = poster_url else {
+        warn!("{}", locale::t("log.process.no_season_poster"));
+        return ProcessOutcome::NoPoster;
+    };
+
+    debug!("Poster saison trouvé");
+
+    let poster = match download_poster(&url, report).await {
+        Ok(poster) => poster,
+        Err(outcome) => return outcome,
+    };
+
+    let rating = season.audience_rating.or(info.vote_average);
+    let render = Stage::start();
+    let rendered = apply_season_overlays(poster, season, &title, rating, show_status, render_config, report).and_then(|p| encode_poster(&p));
+    report.timings.render_ms = render.ms();
+
+    match rendered {
+        Ok(encoded) => upload_and_label(plex, &season.rating_key, &title, encoded, report).await,
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.render_error"));
+            ProcessOutcome::RenderFailed { error: format!("{:?}", e) }
+        }
+    }
+}
+
+fn apply_season_overlays(
+    mut poster: DynamicImage,
+    season: &PlexSeason,
+    title: &str,
+    rating: Option<f64>,
+    show_status: Option<&str>,
+    render_config: &RenderConfig,
+    report: &mut ProcessReport
+) -> Result<DynamicImage> {
+    let overlays_base = get_overlays_path();
+    let theme = poster_theme(&poster, &render_config.theme, report);
+
+    // Effets de base
+    poster = ImageProcessor::add_gradient_masks(poster, &overlays_base, theme.variant.gradient_opacity)?;
+    report.add_overlay("gradients");
+    debug!("Gradients appliqués");
+
+    // Titre selon le modèle configuré (SEASON_TITLE_TEMPLATE)
+    poster = ImageProcessor::add_movie_title(poster, title, &overlays_base, &theme.variant)?;
+    report.add_overlay("title");
+    debug!("Titre ajouté");
+
+    // ❌ PAS d'overlay résolution pour les saisons
+    // ❌ PAS d'overlay codec pour les saisons
+
+    // Audience Score (Plex, sinon note TMDB de la saison) : seule note connue pour une saison
+    let badges = render_config.badges.clone().with_theme(&theme);
+    let ratings = Ratings::from_plex(rating, None);
+    poster = apply_rating_badges(poster, &ratings, &badges.ratings.plex_only(), &badges, &theme, &overlays_base, report);
+
+    // ✅ BORDURE : Status (du show) > Recently Added (de la saison) > Inner Glow
+    apply_status_border(poster, show_status, season.is_recently_added(), &theme, &overlays_base, report)
+}
+
+/// Traite toutes les saisons d'une série (ID TMDB et status récupérés une seule fois)
+pub async fn process_show_seasons(
+    plex: &PlexClient,
+    tmdb: &TmdbClient,
+    render_config: &RenderConfig,
+    show: &PlexShow,
+    force: bool
+) -> Result<Vec<ProcessReport>> {
+    let show_seasons = ShowSeasons::load(plex, tmdb, show).await?;
+
+    let mut reports = Vec::with_capacity(show_seasons.seasons.len());
+    for season in &show_seasons.seasons {
+        reports.push(show_seasons.process(plex, tmdb, render_config, season, force).await);
+    }
+
+    Ok(reports)
+}
+
+/// Saisons d'une série avec le contexte commun (ID TMDB, statut, titres), chargé une fois par série
+pub struct ShowSeasons {
+    pub seasons: Vec<PlexSeason>,
+    tmdb_id: String,
+    show_status: Option<String>,
+    titles: SeasonTitles,
+}
+
+impl ShowSeasons {
+    pub async fn load(plex: &PlexClient, tmdb: &TmdbClient, show: &PlexShow) -> Result<Self> {
+        let tmdb_id = PlexClient::extract_tmdb_id_from_show(show)
+            .ok_or_else(|| anyhow::anyhow!("Pas d'ID TMDB trouvé pour la série {}", show.title))?;
+        let show_status = tmdb.get_show_details(&tmdb_id).await.ok().and_then(|details| details.status);
+
+        let seasons = plex.get_show_seasons(&show.rating_key).await?;
+        info!(title = %show.title, count = seasons.len(), "{}", locale::t("log.process.seasons_found"));
+
+        Ok(Self { seasons, tmdb_id, show_status, titles: SeasonTitles::from_env() })
+    }
+
+    /// Titre de la saison tel qu'il est écrit sur le poster
+    pub fn title(&self, season: &PlexSeason) -> String {
+        self.titles.format(season)
+    }
+
+    /// Traite une saison, ignorée si elle porte déjà le label (sauf `force`)
+    pub async fn process(
+        &self,
+        plex: &PlexClient,
+        tmdb: &TmdbClient,
+        render_config: &RenderConfig,
+        season: &PlexSeason,
+        force: bool
+    ) -> ProcessReport {
+        if !force && season.has_label("Rustizarr") {
+            debug!(season = season.season_number, "Saison déjà traitée");
+            return ProcessReport::already_processed(MediaKind::Season, &season.rating_key, &self.title(season));
+        }
+
+        process_season(plex, tmdb, render_config, season.clone(), &self.tmdb_id, self.show_status.clone()).await
+    }
+}
+
+// ==================== ÉTAPES COMMUNES ====================
+
+/// Télécharge le poster source (durée enregistrée dans le rapport)
+async fn download_poster(url: &str, report: &mut ProcessReport) -> Result<DynamicImage, ProcessOutcome> {
+    debug!(url, "Poster trouvé, téléchargement");
+
+    let download = Stage::start();
+    let result = ImageProcessor::download_image(url, &OutputSettings::from_env()).await;
+    report.timings.download_ms = download.ms();
+
+    match result {
+        Ok(poster) => {
+            debug!(width = poster.width(), height = poster.height(), "Image téléchargée");
+            Ok(poster)
+        },
+        Err(e) => {
+            error!(error = ?e, "{}", locale::t("log.process.download_error"));
+            Err(ProcessOutcome::DownloadFailed { error: format!("{:?}", e) })
+        }
+    }
+}
+
+/// Résultat de la recherche de logo : une erreur TMDB ne bloque pas le rendu (repli sur le texte)
+fn lookup_logo(result: Result<Option<String>>) -> Option<String> {
+    result.unwrap_or_else(|e| {
+        warn!(error = ?e, "{}", locale::t("log.process.logo_lookup_failed"));
+        None
+    })
+}
+
+/// Télécharge le logo s'il existe ; en cas d'échec le titre sera écrit en texte
+async fn download_logo(url: Option<String>) -> Option<DynamicImage> {
+    let url = url?;
+    match ImageProcessor::download_logo(&url).await {
+        Ok(logo) => {
+            debug!(url = %url, width = logo.width(), height = logo.height(), "Logo téléchargé");
+            Some(logo)
         }
+        Err(e) => {
+            warn!(url = %url, error = ?e, "{}", locale::t("log.process.logo_download_failed"));
+            None
+        }
+    }
+}
+
+/// Thème du poster d'après sa palette, mesurée avant les overlays
+fn poster_theme(poster: &DynamicImage, theme_config: &ThemeConfig, report: &mut ProcessReport) -> PosterTheme {
+    let palette = ImageProcessor::extract_palette(poster);
+    let theme = theme_config.resolve(&palette);
+    if theme.dark {
+        report.add_overlay(format!("theme:{}", theme.name()));
+    }
+    theme
+}
+
+/// Titre du poster : logo TMDB s'il est disponible, sinon texte
+fn apply_title(
+    poster: DynamicImage,
+    title: &str,
+    logo: Option<&DynamicImage>,
+    theme: &PosterTheme,
+    overlays_base: &str,
+    report: &mut ProcessReport
+) -> Result<DynamicImage> {
+    if let Some(logo) = logo {
+        let poster = ImageProcessor::add_logo(poster, logo)?;
+        report.add_overlay("logo");
+        debug!("Logo ajouté");
+        return Ok(poster);
+    }
+
+    let poster = ImageProcessor::add_movie_title(poster, title, overlays_base, &theme.variant)?;
+    report.add_overlay("title");
+    debug!("Titre ajouté");
+    Ok(poster)
+}
+
+/// Badge PNG/SVG d'une rangée, ignoré s'il est absent ou illisible
+fn load_file_badge(path: &Path, poster: &DynamicImage, row: &BadgeRow) -> Option<DynamicImage> {
+    match ImageProcessor::load_badge(path, poster, row) {
+        Ok(Some(badge)) => Some(badge),
+        Ok(None) => {
+            debug!(path = ?path, "Overlay introuvable");
+            None
+        }
+        Err(e) => {
+            warn!(path = ?path, error = ?e, "{}", locale::t("log.process.overlay_unreadable"));
+            None
+        }
+    }
+}
+
+/// Badge généré à la hauteur de la rangée, ignoré en cas d'erreur (police manquante...)
+fn render_generated_badge(spec: &BadgeSpec, poster: &DynamicImage, row: &BadgeRow, overlays_base: &str) -> Option<DynamicImage> {
+    ImageProcessor::render_badge(spec, row.badge_height(poster), overlays_base)
+        .inspect_err(|e| warn!(text = %spec.text, error = ?e, "{}", locale::t("log.process.badge_failed")))
+        .ok()
+}
+
+/// Notes Plex, complétées par le jeu de données hors ligne via l'ID IMDb
+fn collect_ratings(
+    audience_rating: Option<f64>,
+    plex_ratings: Option<&Vec<PlexRating>>,
+    guids: Option<&Vec<PlexGuid>>
+) -> Ratings {
+    let mut ratings = Ratings::from_plex(audience_rating, plex_ratings);
+    if let Some(imdb_id) = PlexClient::extract_imdb_id(guids) {
+        ratings.add_dataset(&imdb_id);
+    }
+    ratings
+}
+
+/// Appel TMDB seulement si la source est affichée et que Plex ne la fournit pas
+fn needs_tmdb_votes(config: &RatingsConfig, ratings: &Ratings) -> bool {
+    config.uses(RatingSource::Tmdb) && ratings.get(RatingSource::Tmdb).is_none()
+}
+
+fn add_tmdb_votes(ratings: &mut Ratings, votes: Option<Votes>) {
+    match votes {
+        Some(votes) => ratings.add(RatingSource::Tmdb, votes.average, Some(votes.count)),
+        None => debug!("Pas de note TMDB"),
+    }
+}
+
+/// Détails TMDB (poster standard, note, status) : une erreur n'empêche pas le rendu
+fn tmdb_details(result: Result<Details>) -> Details {
+    result.unwrap_or_else(|e| {
+        warn!(error = ?e, "{}", locale::t("log.process.tmdb_details_unavailable"));
+        Details::default()
+    })
+}
+
+/// Rangée des notes (bas-droite), chaque badge ignoré silencieusement en cas d'erreur
+fn apply_rating_badges(
+    mut poster: DynamicImage,
+    ratings: &Ratings,
+    config: &RatingsConfig,
+    badges: &BadgeConfig,
+    theme: &PosterTheme,
+    overlays_base: &str,
+    report: &mut ProcessReport
+) -> DynamicImage {
+    let mut row = BadgeRow::bottom_right(0.065);
+
+    for rating in config.shown(ratings) {
+        let level = config.level(rating);
+        debug!(source = rating.source.key(), value = rating.value, level = level.key(), "Note détectée");
+
+        let badge = match rating.source {
+            // Visuel historique audience_score, note à la couleur du thème
+            RatingSource::Plex => {
+                let path = Path::new(overlays_base).join("audience_score").join(get_audience_badge_filename(level));
+                let color = theme.variant.score_text;
+                ImageProcessor::render_score_badge(&path, row.badge_height(&poster), &rating.display(), color, overlays_base)
+                    .inspect_err(|e| warn!(path = ?path, error = ?e, "{}", locale::t("log.process.audience_badge_failed")))
+                    .ok()
+                    .flatten()
+            }
+            _ => {
+                let spec = config.spec(rating, badges.base_style(), Path::new(overlays_base));
+                render_generated_badge(&spec, &poster, &row, overlays_base)
+            }
+        };
+        let Some(badge) = badge else { continue };
+
+        poster = ImageProcessor::add_badge(poster, &badge, &mut row);
+        match rating.source {
+            RatingSource::Plex => report.add_overlay(format!("audience:{:.1}", rating.value)),
+            source => report.add_overlay(format!("rating:{}:{}", source.key(), rating.value)),
+        }
+        debug!(source = rating.source.key(), "Badge de note ajouté");
+    }
+
+    poster
+}
+
+/// Bordure : Status > Recently Added > Inner Glow
+fn apply_status_border(
+    poster: DynamicImage,
+    status: Option<&str>,
+    recently_added: bool,
+    theme: &PosterTheme,
+    overlays_base: &str,
+    report: &mut ProcessReport
+) -> Result<DynamicImage> {
+    if let Some(status) = status {
+        let status_file = get_status_filename(status);
+        debug!(status, border = status_file, "Bordure de status");
+        report.add_overlay(format!("border:{}", status_file));
+        ImageProcessor::add_status_border(poster, overlays_base, status_file, theme.border_tint)
+    } else if recently_added {
+        debug!("Ajout récent, bordure 'Recently Added'");
+        report.add_overlay("border:recently_added");
+        ImageProcessor::add_status_border(poster, overlays_base, "recently_added.png", theme.border_tint)
     } else {
-        Ok("❌ Pas de poster trouvé".to_string())
+        debug!("Pas de status ni récent, application inner glow");
+        report.add_overlay("border:inner_glow");
+        ImageProcessor::add_inner_glow_border(poster, overlays_base, theme.border_tint)
     }
 }
 
+/// Encode le poster final au format de sortie configuré pour l'upload
+fn encode_poster(poster: &DynamicImage) -> Result<EncodedPoster> {
+    let _timer = metrics::render_stage_timer("encode");
+    let encoded = output::encode(poster, &OutputSettings::from_env())?;
+    debug!(format = encoded.format.name(), quality = encoded.quality, bytes = encoded.bytes.len(), "Poster encodé");
+    Ok(encoded)
+}
+
+/// Upload vers Plex puis ajout du label 'Rustizarr'
+async fn upload_and_label(
+    plex: &PlexClient,
+    rating_key: &str,
+    title: &str,
+    poster: EncodedPoster,
+    report: &mut ProcessReport
+) -> ProcessOutcome {
+    let upload = Stage::start();
+    let size = poster.bytes.len();
+    let result = plex.upload_poster(rating_key, poster.bytes, poster.format.content_type()).await;
+    report.timings.upload_ms = upload.ms();
+
+    if let Err(e) = result {
+        error!(error = ?e, "{}", locale::t("log.process.upload_error"));
+        return ProcessOutcome::UploadFailed { error: format!("{:?}", e) };
+    }
+    metrics::record_upload_bytes(size);
+
+    info!(title, "{}", locale::t("log.process.uploaded"));
+
+    if let Err(e) = plex.add_label(rating_key, "Rustizarr").await {
+        warn!(error = ?e, "{}", locale::t("log.process.label_failed"));
+    }
+
+    ProcessOutcome::Rendered
+}
+
 // ==================== PARALLÉLISATION ====================
 
 pub async fn process_library_parallel(
     plex: &PlexClient,
     tmdb: &TmdbClient,
+    render_config: &RenderConfig,
     movies: Vec<PlexMovie>,
     concurrency: usize,
     force: bool
-) -> Vec<(String, anyhow::Result<String>)> {
-    println!("🚀 Traitement parallèle : {} films, {} threads", movies.len(), concurrency);
+) -> Vec<ProcessReport> {
+    info!(count = movies.len(), concurrency, "{}", locale::t("log.process.parallel_movies"));
     
-    let results = stream::iter(movies)
+    stream::iter(movies)
         .map(|movie| {
             let plex_clone = plex.clone();
             let tmdb_clone = tmdb.clone();
             async move {
-                let title = movie.title.clone();
-                
                 if !force && movie.has_label("Rustizarr") {
-                    return (title.clone(), Ok("⏭️ Déjà traité".to_string()));
+                    return ProcessReport::already_processed(MediaKind::Movie, &movie.rating_key, &movie.title);
                 }
                 
-                let result = process_movie(&plex_clone, &tmdb_clone, movie).await;
-                (title, result)
+                process_movie(&plex_clone, &tmdb_clone, render_config, movie).await
             }
         })
         .buffer_unordered(concurrency)
         .collect::<Vec<_>>()
-        .await;
-    
-    results
+        .await
 }
 
 pub async fn process_shows_parallel(
     plex: &PlexClient,
     tmdb: &TmdbClient,
+    render_config: &RenderConfig,
     shows: Vec<PlexShow>,
     concurrency: usize,
     force: bool
-) -> Vec<(String, anyhow::Result<String>)> {
-    println!("🚀 Traitement parallèle : {} séries, {} threads", shows.len(), concurrency);
+) -> Vec<ProcessReport> {
+    info!(count = shows.len(), concurrency, "{}", locale::t("log.process.parallel_shows"));
     
-    let results = stream::iter(shows)
+    stream::iter(shows)
         .map(|show| {
             let plex_clone = plex.clone();
             let tmdb_clone = tmdb.clone();
             async move {
-                let title = show.title.clone();
-                
                 if !force && show.has_label("Rustizarr") {
-                    return (title.clone(), Ok("⏭️ Déjà traité".to_string()));
+                    return ProcessReport::already_processed(MediaKind::Show, &show.rating_key, &show.title);
                 }
                 
-                let result = process_show(&plex_clone, &tmdb_clone, show).await;
-                (title, result)
+                process_show(&plex_clone, &tmdb_clone, render_config, show).await
             }
         })
         .buffer_unordered(concurrency)
         .collect::<Vec<_>>()
-        .await;
-    
-    results
+        .await
 }
 
 // ==================== FONCTIONS HELPER ====================
 
+/// Configuration du rendu lue sur disque (badges.json, theme.json + variables d'environnement)
+///
+/// À charger une fois par scan ou commande : seule la résolution du thème (palette) est faite par poster.
+#[derive(Debug, Clone)]
+pub struct RenderConfig {
+    pub badges: BadgeConfig,
+    pub theme: ThemeConfig,
+}
+
+impl RenderConfig {
+    pub fn load() -> Self {
+        let overlays_base = get_overlays_path();
+        Self {
+            badges: BadgeConfig::load(Path::new(&overlays_base)),
+            theme: ThemeConfig::load(Path::new(&overlays_base)),
+        }
+    }
+}
+
 fn get_overlays_path() -> String {
     env::var("OVERLAYS_PATH")
         .unwrap_or_else(|_| {
@@ -415,6 +853,65 @@ fn get_overlays_path() -> String {
         })
 }
 
+/// Modèles de titre des saisons : `{show}`, `{n}` (numéro), `{nn}` (numéro sur 2 chiffres)
+/// Ex: "{show} - Season {n}", "S{nn}". La saison 0 utilise le modèle des épisodes spéciaux
+pub struct SeasonTitles {
+    pub template: String,
+    pub specials: String,
+}
+
+impl SeasonTitles {
+    /// SEASON_TITLE_TEMPLATE / SEASON_SPECIALS_TITLE, défauts selon la langue (LOCALE)
+    pub fn from_env() -> Self {
+        Self {
+            template: env::var("SEASON_TITLE_TEMPLATE").unwrap_or_else(|_| locale::t("season.title")),
+            specials: env::var("SEASON_SPECIALS_TITLE").unwrap_or_else(|_| locale::t("season.specials")),
+        }
+    }
+
+    pub fn format(&self, season: &PlexSeason) -> String {
+        let template = if season.season_number == 0 { &self.specials } else { &self.template };
+        template
+            .replace("{show}", &season.show_title)
+            .replace("{nn}", &format!("{:02}", season.season_number))
+            .replace("{n}", &season.season_number.to_string())
+    }
+}
+
+/// Rendu du titre des films et séries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TitleMode {
+    /// Titre écrit avec les polices du dossier overlays
+    Text,
+    /// Logo TMDB (clearlogo), repli sur le texte s'il n'en existe pas
+    Logo,
+}
+
+impl TitleMode {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" | "texte" => Some(Self::Text),
+            "logo" => Some(Self::Logo),
+            _ => None,
+        }
+    }
+
+    /// TITLE_MODE (défaut "text"), surchargé par section via TITLE_MODE_LIBRARIES="1=logo,2=text"
+    pub fn for_library(library_id: Option<&str>) -> Self {
+        let per_library = library_id.and_then(|id| {
+            env::var("TITLE_MODE_LIBRARIES").ok()?
+                .split(',')
+                .filter_map(|entry| entry.split_once('='))
+                .find(|(section, _)| section.trim() == id)
+                .and_then(|(_, mode)| Self::parse(mode))
+        });
+
+        per_library
+            .or_else(|| env::var("TITLE_MODE").ok().and_then(|mode| Self::parse(&mode)))
+            .unwrap_or(Self::Text)
+    }
+}
+
 pub fn get_forced_tmdb_id(title: &str) -> Option<String> {
     match title.to_lowercase().as_str() {
         "abyss" => Some("1025527".to_string()), 
@@ -440,6 +937,106 @@ pub fn get_edition_filename(movie: &PlexMovie) -> Option<&str> {
     }
 }
 
+fn env_flag(name: &str) -> bool {
+    env::var(name).is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
+
+// ==================== VERSIONS ====================
+
+/// Version (Media) badgée quand Plex en expose plusieurs (ex: copie 1080p + copie 4K)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionPolicy {
+    /// Première version renvoyée par Plex (ordre arbitraire)
+    First,
+    /// Meilleure résolution, puis plage dynamique, puis audio
+    Best,
+    /// Meilleure résolution seulement
+    Resolution,
+    /// Meilleure plage dynamique (DV > HDR10+ > HDR10 > HLG > SDR)
+    Hdr,
+    /// Meilleur audio (objet, sans perte, canaux)
+    Audio,
+}
+
+impl VersionPolicy {
+    /// MEDIA_VERSION_POLICY : first, best (défaut), resolution, hdr, audio
+    pub fn from_env() -> Self {
+        match env::var("MEDIA_VERSION_POLICY").unwrap_or_default().trim().to_lowercase().as_str() {
+            "first" => Self::First,
+            "resolution" => Self::Resolution,
+            "hdr" => Self::Hdr,
+            "audio" => Self::Audio,
+            "" | "best" => Self::Best,
+            other => {
+                warn!(policy = other, "{}", locale::t("log.process.unknown_version_policy"));
+                Self::Best
+            }
+        }
+    }
+
+    /// Version retenue ; à égalité, la première dans l'ordre de Plex
+    pub fn select(self, versions: &[PlexMedia]) -> Option<&PlexMedia> {
+        let best_by = |key: fn(&PlexMedia) -> VersionScore| versions.iter().rev().max_by_key(|m| key(m));
+
+        let selected = match self {
+            Self::First => versions.first(),
+            Self::Best => best_by(|m| (resolution_rank(m), dynamic_range_rank(m), audio_rank(m))),
+            Self::Resolution => best_by(|m| (resolution_rank(m), 0, Default::default())),
+            Self::Hdr => best_by(|m| (dynamic_range_rank(m), 0, Default::default())),
+            Self::Audio => best_by(|m| (0, 0, audio_rank(m))),
+        };
+
+        if versions.len() > 1 {
+            let index = selected.and_then(|s| versions.iter().position(|m| std::ptr::eq(m, s)));
+            debug!(policy = ?self, versions = versions.len(), ?index, "Version sélectionnée pour les badges");
+        }
+        selected
+    }
+}
+
+/// (audio objet, niveau du codec, nombre de canaux)
+type AudioRank = (bool, u8, u64);
+/// (résolution, plage dynamique, audio), comparés dans cet ordre
+type VersionScore = (u8, u8, AudioRank);
+
+fn resolution_rank(media: &PlexMedia) -> u8 {
+    match media.video_resolution.as_deref().unwrap_or("").to_lowercase().as_str() {
+        "4k" | "2160" | "2160p" | "ultra hd" => 4,
+        "1080" | "1080p" | "fhd" => 3,
+        "720" | "720p" | "hd" => 2,
+        "576" | "480" | "sd" => 1,
+        _ => 0,
+    }
+}
+
+fn dynamic_range_rank(media: &PlexMedia) -> u8 {
+    DynamicRange::from_media(media).map_or(0, |range| range.rank())
+}
+
+fn audio_rank(media: &PlexMedia) -> AudioRank {
+    let facts = MediaFacts::from_media(media);
+    let tier = media.streams_of(STREAM_AUDIO).into_iter()
+        .map(|s| {
+            let codec = s.get("codec").and_then(|v| v.as_str()).unwrap_or("");
+            let profile = s.get("audioProfile").and_then(|v| v.as_str()).unwrap_or("");
+            audio_codec_tier(codec, profile)
+        })
+        .max()
+        .unwrap_or_else(|| audio_codec_tier(media.audio_codec.as_deref().unwrap_or(""), ""));
+    (facts.object_audio, tier, facts.channel_count)
+}
+
+/// Sans perte > DTS / DD+ > DD > reste
+fn audio_codec_tier(codec: &str, profile: &str) -> u8 {
+    match (codec.to_lowercase().as_str(), profile.to_lowercase().as_str()) {
+        ("truehd" | "flac" | "pcm" | "alac", _) => 3,
+        ("dca" | "dts", "ma" | "dts:x") => 3,
+        ("dca" | "dts" | "eac3", _) => 2,
+        ("ac3", _) => 1,
+        _ => 0,
+    }
+}
+
 pub fn get_resolution_filename(media: &PlexMedia) -> Option<String> {
     let raw_res = media.video_resolution.as_deref().unwrap_or("").to_lowercase();
     match raw_res.as_str() {
@@ -449,14 +1046,8 @@ pub fn get_resolution_filename(media: &PlexMedia) -> Option<String> {
     }
 }
 
-pub fn get_audience_badge_filename(rating: f64) -> &'static str {
-    if rating >= 8.0 { 
-        "audience_score_high.png" 
-    } else if rating >= 6.0 { 
-        "audience_score_mid.png" 
-    } else { 
-        "audience_score_low.png" 
-    }
+pub fn get_audience_badge_filename(level: RatingLevel) -> String {
+    format!("audience_score_{}.png", level.key())
 }
 
 pub fn get_status_filename(status: &str) -> &'static str {
@@ -472,10 +1063,7 @@ pub fn get_status_filename(status: &str) -> &'static str {
 pub fn get_codec_combo_filename(media: &PlexMedia) -> Option<String> {
     let fallback_audio = media.audio_codec.as_deref().unwrap_or("").to_lowercase();
     
-    let mut has_streams_access = false;
-    let mut is_dv = false;
-    let mut is_hdr = false;
-    let mut is_plus = false;
+    let has_streams_access = media.has_streams();
     let mut has_atmos = false;
     let mut has_truehd = false;
     let mut has_dts_hd = false;
@@ -483,83 +1071,34 @@ pub fn get_codec_combo_filename(media: &PlexMedia) -> Option<String> {
     let mut has_dd_plus = false; 
     let mut found_audio_codec = String::new();
 
-    if let Some(parts_value) = &media.parts {
-        let parts_slice: &[serde_json::Value] = if let Some(arr) = parts_value.as_array() { 
-            arr.as_slice() 
-        } else { 
-            std::slice::from_ref(parts_value) 
-        };
+    for stream in media.streams_of(STREAM_AUDIO) {
+        let display = stream.get("displayTitle").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+        let title = stream.get("title").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+        let codec = stream.get("codec").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+        let profile = stream.get("audioProfile").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
+        found_audio_codec = codec.clone();
 
-        for part in parts_slice {
-            let maybe_streams = part.get("Stream").or_else(|| part.get("stream"));
-            if let Some(streams_value) = maybe_streams {
-                has_streams_access = true;
-                let streams_slice: &[serde_json::Value] = if let Some(arr) = streams_value.as_array() { 
-                    arr.as_slice() 
-                } else { 
-                    std::slice::from_ref(streams_value) 
-                };
-
-                for stream in streams_slice {
-                    let stream_type = stream.get("streamType").and_then(|v| v.as_u64()).unwrap_or(0);
-                    
-                    if stream_type == 1 { // VIDEO
-                        let display = stream.get("displayTitle").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        let title = stream.get("title").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        if stream.get("doviprofile").is_some() || stream.get("DOVIProfile").is_some() || stream.get("DOVIPresent").is_some() { 
-                            is_dv = true; 
-                        }
-                        if display.contains("dolby vision") || title.contains("dolby vision") || display.contains("dovi") || title.contains("dovi") { 
-                            is_dv = true; 
-                        }
-                        if display.contains("hdr10+") || title.contains("hdr10+") { 
-                            is_plus = true; 
-                        } else if display.contains("hdr") || title.contains("hdr") { 
-                            is_hdr = true; 
-                        }
-                    }
-
-                    if stream_type == 2 { // AUDIO
-                        let display = stream.get("displayTitle").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        let title = stream.get("title").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        let codec = stream.get("codec").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        let profile = stream.get("audioProfile").and_then(|v| v.as_str()).unwrap_or("").to_lowercase();
-                        found_audio_codec = codec.clone();
-
-                        if title.contains("atmos") || display.contains("atmos") { 
-                            has_atmos = true; 
-                        }
-                        match codec.as_str() {
-                            "truehd" => has_truehd = true,
-                            "dca" | "dts" => {
-                                if profile == "dts:x" { 
-                                    has_dts_x = true; 
-                                }
-                                has_dts_hd = true; 
-                            },
-                            "eac3" | "ac3" => has_dd_plus = true,
-                            _ => {}
-                        }
-                    }
+        if title.contains("atmos") || display.contains("atmos") { 
+            has_atmos = true; 
+        }
+        match codec.as_str() {
+            "truehd" => has_truehd = true,
+            "dca" | "dts" => {
+                if profile == "dts:x" { 
+                    has_dts_x = true; 
                 }
-            }
+                has_dts_hd = true; 
+            },
+            "eac3" | "ac3" => has_dd_plus = true,
+            _ => {}
         }
     }
 
+    // HLG n'a pas de visuel : seul le PNG audio est retenu, le badge HLG est généré à côté
+    let dynamic_range = DynamicRange::from_media(media);
+
     let video_part = if has_streams_access {
-        if is_dv && is_hdr { 
-            Some("DV-HDR") 
-        } else if is_dv && is_plus { 
-            Some("DV-Plus") 
-        } else if is_dv { 
-            Some("DV") 
-        } else if is_plus { 
-            Some("Plus") 
-        } else if is_hdr { 
-            Some("HDR") 
-        } else { 
-            None 
-        }
+        dynamic_range.as_ref().and_then(DynamicRange::asset_token)
     } else { 
         None 
     };
@@ -591,16 +1130,127 @@ pub fn get_codec_combo_filename(media: &PlexMedia) -> Option<String> {
 
     let result = match (video_part, audio_part) {
         (Some(v), Some(a)) => Some(format!("{}-{}.png", v, a)),
-        (Some(v), None) => Some(format!("{}.png", v)),
+        // Visuel seul : variante large "DOLBY VISION · HDR" quand elle existe
+        (Some(v), None) => Some(dynamic_range.as_ref().and_then(DynamicRange::extended_asset)
+            .unwrap_or_else(|| format!("{}.png", v))),
         (None, Some(a)) => Some(format!("{}.png", a)),
         (None, None) => None,
     };
 
-    if result.is_none() && has_streams_access {
-        if !found_audio_codec.contains("aac") && !found_audio_codec.contains("mp3") {
-             println!("      ℹ️ Info: Codec audio '{}' détecté, mais aucun badge combiné généré.", found_audio_codec);
-        }
+    if result.is_none() && has_streams_access
+        && !found_audio_codec.contains("aac") && !found_audio_codec.contains("mp3") {
+        debug!(codec = %found_audio_codec, "Codec audio détecté, mais aucun badge combiné généré");
     }
 
     result
 }
+
+/// Texte du badge généré quand aucun PNG ne correspond : "HLG", "FLAC 2.0", "HDR DTS-HD MA 7.1"
+pub fn get_codec_badge_text(media: &PlexMedia) -> Option<String> {
+    let video = DynamicRange::from_media(media).and_then(|range| range.label());
+
+    let audio_streams = media.streams_of(STREAM_AUDIO);
+    let audio_stream = audio_streams.iter()
+        .find(|s| s.get("selected").and_then(|v| v.as_bool()) == Some(true))
+        .or(audio_streams.first());
+    let codec = audio_stream
+        .and_then(|s| s.get("codec").and_then(|v| v.as_str()))
+        .or(media.audio_codec.as_deref())
+        .unwrap_or("")
+        .to_lowercase();
+
+    // Codecs courants sans intérêt seuls (comportement historique : pas de badge)
+    let audio = if video.is_none() && matches!(codec.as_str(), "" | "aac" | "mp3") {
+        None
+    } else {
+        audio_stream.and_then(|s| audio_label(s)).or_else(|| (!codec.is_empty()).then(|| codec_label(&codec)))
+    };
+
+    let parts: Vec<String> = [video, audio].into_iter().flatten().collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Description Plex de la piste ("Français (DTS-HD MA 7.1)" → "DTS-HD MA 7.1")
+fn audio_label(stream: &serde_json::Value) -> Option<String> {
+    let display = stream.get("displayTitle").and_then(|v| v.as_str())?;
+    let start = display.rfind('(')?;
+    let end = display[start..].find(')')? + start;
+    let label = display[start + 1..end].trim();
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+fn codec_label(codec: &str) -> String {
+    match codec {
+        "dca" | "dts" => "DTS".to_string(),
+        "eac3" => "DD+".to_string(),
+        "ac3" => "DD".to_string(),
+        "truehd" => "TrueHD".to_string(),
+        "opus" => "Opus".to_string(),
+        "vorbis" => "Vorbis".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn media(resolution: &str, video: Value, audio: Value) -> PlexMedia {
+        let mut video = video;
+        video["streamType"] = json!(1);
+        let mut audio = audio;
+        audio["streamType"] = json!(2);
+        serde_json::from_value(json!({
+            "videoResolution": resolution,
+            "Part": [{ "Stream": [video, audio] }],
+        })).unwrap()
+    }
+
+    fn aac() -> Value {
+        json!({ "codec": "aac", "channels": 2 })
+    }
+
+    fn truehd_atmos() -> Value {
+        json!({ "codec": "truehd", "channels": 8, "displayTitle": "English (TrueHD 7.1 Atmos)" })
+    }
+
+    fn selected_index(policy: VersionPolicy, versions: &[PlexMedia]) -> Option<usize> {
+        policy.select(versions).and_then(|s| versions.iter().position(|m| std::ptr::eq(m, s)))
+    }
+
+    #[test]
+    fn version_policy_best_prefers_resolution_then_range() {
+        let versions = [
+            media("1080", json!({ "DOVIProfile": 8, "DOVIBLCompatID": 1 }), truehd_atmos()),
+            media("4k", json!({ "colorTrc": "bt709" }), aac()),
+            media("4k", json!({ "colorTrc": "smpte2084" }), aac()),
+        ];
+
+        assert_eq!(selected_index(VersionPolicy::Best, &versions), Some(2));
+        assert_eq!(selected_index(VersionPolicy::Resolution, &versions), Some(1));
+        assert_eq!(selected_index(VersionPolicy::Hdr, &versions), Some(0));
+        assert_eq!(selected_index(VersionPolicy::Audio, &versions), Some(0));
+        assert_eq!(selected_index(VersionPolicy::First, &versions), Some(0));
+    }
+
+    #[test]
+    fn version_policy_keeps_plex_order_on_ties() {
+        let versions = [
+            media("1080", json!({ "colorTrc": "bt709" }), aac()),
+            media("1080", json!({ "colorTrc": "bt709" }), aac()),
+        ];
+
+        assert_eq!(selected_index(VersionPolicy::Best, &versions), Some(0));
+        assert_eq!(selected_index(VersionPolicy::Best, &[]), None);
+    }
+
+    #[test]
+    fn codec_combo_keeps_audio_asset_for_hlg() {
+        let hlg = media("4k", json!({ "colorTrc": "arib-std-b67" }), truehd_atmos());
+        assert_eq!(get_codec_combo_filename(&hlg).as_deref(), Some("TrueHD-Atmos.png"));
+
+        let dv = media("4k", json!({ "DOVIProfile": 8, "DOVIBLCompatID": 1 }), truehd_atmos());
+        assert_eq!(get_codec_combo_filename(&dv).as_deref(), Some("DV-HDR-TrueHD-Atmos.png"));
+    }
+}
@@ -0,0 +1,431 @@
+// backend/src/ratings.rs
+// Notes multi-sources (Plex, IMDb, Rotten Tomatoes, TMDB, Metacritic) et rangée de badges
+// configurée dans la section `ratings` de badges.json
+use crate::assets;
+use crate::badges::{parse_color, BadgeSpec, BadgeStyle};
+use crate::locale;
+use crate::plex::PlexRating;
+use image::Rgba;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing::{debug, info, warn};
+
+/// Jeu de données hors ligne (RATINGS_DATASET), chargé à la première recherche
+static DATASET: OnceLock<HashMap<String, DatasetEntry>> = OnceLock::new();
+
+// ==================== SOURCES ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingSource {
+    /// `audienceRating` de Plex (badge historique audience_score)
+    Plex,
+    Imdb,
+    RtCritic,
+    RtAudience,
+    Tmdb,
+    Metacritic,
+}
+
+impl RatingSource {
+    pub fn key(self) -> &'static str {
+        match self {
+            RatingSource::Plex => "plex",
+            RatingSource::Imdb => "imdb",
+            RatingSource::RtCritic => "rt_critic",
+            RatingSource::RtAudience => "rt_audience",
+            RatingSource::Tmdb => "tmdb",
+            RatingSource::Metacritic => "metacritic",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "plex" | "audience" => Some(RatingSource::Plex),
+            "imdb" => Some(RatingSource::Imdb),
+            "rt_critic" | "rt" | "rottentomatoes" => Some(RatingSource::RtCritic),
+            "rt_audience" => Some(RatingSource::RtAudience),
+            "tmdb" => Some(RatingSource::Tmdb),
+            "metacritic" | "mc" => Some(RatingSource::Metacritic),
+            _ => None,
+        }
+    }
+
+    /// Échelle native : pourcentage pour Rotten Tomatoes, /100 pour Metacritic, /10 sinon
+    pub fn scale(self) -> f64 {
+        match self {
+            RatingSource::RtCritic | RatingSource::RtAudience | RatingSource::Metacritic => 100.0,
+            _ => 10.0,
+        }
+    }
+
+    /// Préfixe affiché quand aucune icône n'existe pour la source
+    fn default_label(self) -> &'static str {
+        match self {
+            RatingSource::Plex => "",
+            RatingSource::Imdb => "IMDb",
+            RatingSource::RtCritic => "RT",
+            RatingSource::RtAudience => "RT Public",
+            RatingSource::Tmdb => "TMDB",
+            RatingSource::Metacritic => "MC",
+        }
+    }
+
+    /// Source d'une note Plex d'après son image ("rottentomatoes://image.rating.ripe" + type)
+    fn from_plex(rating: &PlexRating) -> Option<Self> {
+        let (scheme, _) = rating.image.split_once("://")?;
+        match (scheme, rating.kind.as_deref()) {
+            ("imdb", _) => Some(RatingSource::Imdb),
+            ("rottentomatoes", Some("audience")) => Some(RatingSource::RtAudience),
+            ("rottentomatoes", _) => Some(RatingSource::RtCritic),
+            ("themoviedb", _) => Some(RatingSource::Tmdb),
+            _ => None,
+        }
+    }
+}
+
+// ==================== NOTES ====================
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rating {
+    pub source: RatingSource,
+    /// Valeur dans l'échelle native de la source (7.8, 91, 74)
+    pub value: f64,
+    /// Nombre de votes, quand la source le fournit
+    pub votes: Option<u64>,
+}
+
+impl Rating {
+    /// Valeur affichée : "91%" (RT), "74" (Metacritic), "7,8" (décimale de la locale)
+    pub fn display(&self) -> String {
+        match self.source {
+            RatingSource::RtCritic | RatingSource::RtAudience => format!("{:.0}%", self.value),
+            RatingSource::Metacritic => format!("{:.0}", self.value),
+            _ => locale::current().decimal(self.value, 1),
+        }
+    }
+}
+
+/// Notes disponibles pour un élément, une seule par source (la première trouvée)
+#[derive(Debug, Clone, Default)]
+pub struct Ratings {
+    items: Vec<Rating>,
+}
+
+impl Ratings {
+    /// `audienceRating` et tableau `Rating` de Plex
+    pub fn from_plex(audience_rating: Option<f64>, ratings: Option<&Vec<PlexRating>>) -> Self {
+        let mut collected = Self::default();
+        if let Some(value) = audience_rating {
+            collected.add(RatingSource::Plex, value, None);
+        }
+        for rating in ratings.into_iter().flatten() {
+            match RatingSource::from_plex(rating) {
+                // Plex stocke tout sur 10, Rotten Tomatoes compris
+                Some(source) => collected.add(source, rating.value * source.scale() / 10.0, None),
+                None => debug!(image = %rating.image, "Source de note Plex inconnue"),
+            }
+        }
+        collected
+    }
+
+    /// Ajoute une note si la source n'en a pas déjà une (les notes Plex priment)
+    pub fn add(&mut self, source: RatingSource, value: f64, votes: Option<u64>) {
+        if value <= 0.0 || self.get(source).is_some() {
+            return;
+        }
+        self.items.push(Rating { source, value, votes });
+    }
+
+    pub fn get(&self, source: RatingSource) -> Option<&Rating> {
+        self.items.iter().find(|r| r.source == source)
+    }
+
+    /// Complète avec le jeu de données hors ligne (IMDb, Metacritic, Rotten Tomatoes)
+    pub fn add_dataset(&mut self, imdb_id: &str) {
+        let Some(entry) = dataset().get(imdb_id) else { return };
+        if let Some(value) = entry.imdb_rating {
+            self.add(RatingSource::Imdb, value, entry.imdb_votes);
+        }
+        if let Some(value) = entry.metascore {
+            self.add(RatingSource::Metacritic, value, None);
+        }
+        if let Some(value) = entry.rotten_tomatoes {
+            self.add(RatingSource::RtCritic, value, None);
+        }
+    }
+}
+
+// ==================== JEU DE DONNÉES ====================
+
+/// Ligne du CSV : `imdb_id,imdb_rating,imdb_votes,metascore,rotten_tomatoes`
+/// (format des exports OMDb : "N/A" pour une valeur absente, "1,234,567" votes, "91%")
+#[derive(Debug, Deserialize)]
+struct DatasetRow {
+    imdb_id: String,
+    #[serde(default)]
+    imdb_rating: String,
+    #[serde(default)]
+    imdb_votes: String,
+    #[serde(default)]
+    metascore: String,
+    #[serde(default)]
+    rotten_tomatoes: String,
+}
+
+#[derive(Debug, Clone)]
+struct DatasetEntry {
+    imdb_rating: Option<f64>,
+    imdb_votes: Option<u64>,
+    metascore: Option<f64>,
+    rotten_tomatoes: Option<f64>,
+}
+
+fn dataset() -> &'static HashMap<String, DatasetEntry> {
+    DATASET.get_or_init(|| match env::var("RATINGS_DATASET") {
+        Ok(path) if !path.is_empty() => load_dataset(Path::new(&path)),
+        _ => HashMap::new(),
+    })
+}
+
+fn load_dataset(path: &Path) -> HashMap<String, DatasetEntry> {
+    let mut reader = match csv::Reader::from_path(path) {
+        Ok(reader) => reader,
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "{}", locale::t("log.ratings.invalid_file"));
+            return HashMap::new();
+        }
+    };
+
+    let mut entries = HashMap::new();
+    for row in reader.deserialize::<DatasetRow>() {
+        match row {
+            Ok(row) => {
+                entries.insert(row.imdb_id.trim().to_string(), DatasetEntry {
+                    imdb_rating: parse_number(&row.imdb_rating),
+                    imdb_votes: parse_number(&row.imdb_votes).map(|v| v as u64),
+                    metascore: parse_number(&row.metascore),
+                    rotten_tomatoes: parse_number(&row.rotten_tomatoes),
+                });
+            }
+            Err(e) => debug!(error = %e, "Ligne du jeu de notes ignorée"),
+        }
+    }
+
+    info!(path = %path.display(), entries = entries.len(), "{}", locale::t("log.ratings.loaded"));
+    entries
+}
+
+/// "7.8", "1,234,567", "91%", "N/A" → nombre
+fn parse_number(value: &str) -> Option<f64> {
+    let cleaned: String = value.chars().filter(|c| c.is_ascii_digit() || *c == '.').collect();
+    cleaned.parse().ok()
+}
+
+// ==================== CONFIGURATION ====================
+
+/// Niveau d'une note par rapport aux seuils de sa source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingLevel {
+    High,
+    Mid,
+    Low,
+}
+
+impl RatingLevel {
+    pub fn key(self) -> &'static str {
+        match self {
+            RatingLevel::High => "high",
+            RatingLevel::Mid => "mid",
+            RatingLevel::Low => "low",
+        }
+    }
+}
+
+/// Couleurs de fond par niveau ("#RRGGBB[AA]")
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LevelColors {
+    pub high: Option<String>,
+    pub mid: Option<String>,
+    pub low: Option<String>,
+}
+
+/// Réglages d'une source
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SourceStyle {
+    /// Préfixe texte quand il n'y a pas d'icône ("IMDb")
+    pub label: Option<String>,
+    /// Icône PNG/SVG, relative au dossier overlays (défaut : `ratings/<source>.png`)
+    pub icon: Option<String>,
+    /// Seuils dans l'échelle native de la source (défaut : 80 % et 60 % de l'échelle)
+    pub high: Option<f64>,
+    pub mid: Option<f64>,
+    #[serde(default)]
+    pub colors: LevelColors,
+    /// Remplace `min_votes` pour cette source
+    pub min_votes: Option<u64>,
+}
+
+/// Section `ratings` de badges.json :
+/// `{ "sources": ["imdb", "rt_critic"], "min_votes": 500, "styles": { "imdb": { "high": 7.5 } } }`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RatingsConfig {
+    /// Sources affichées, de droite à gauche depuis le coin bas-droit
+    pub sources: Vec<RatingSource>,
+    /// Masque les notes ayant moins de votes (les sources sans nombre de votes restent affichées)
+    pub min_votes: u64,
+    pub styles: HashMap<RatingSource, SourceStyle>,
+}
+
+impl Default for RatingsConfig {
+    fn default() -> Self {
+        Self { sources: vec![RatingSource::Plex], min_votes: 0, styles: HashMap::new() }
+    }
+}
+
+impl RatingsConfig {
+    /// RATING_SOURCES ("imdb,rt_critic,tmdb") et RATING_MIN_VOTES remplacent le fichier
+    pub fn with_env(mut self) -> Self {
+        if let Ok(value) = env::var("RATING_SOURCES") {
+            let sources: Vec<RatingSource> = value.split(',')
+                .filter(|s| !s.trim().is_empty())
+                .filter_map(|s| RatingSource::parse(s).or_else(|| {
+                    warn!(source = %s, "{}", locale::t("log.ratings.unknown_source"));
+                    None
+                }))
+                .collect();
+            self.sources = sources;
+        }
+        if let Some(min_votes) = env::var("RATING_MIN_VOTES").ok().and_then(|v| v.trim().parse().ok()) {
+            self.min_votes = min_votes;
+        }
+        self
+    }
+
+    pub fn uses(&self, source: RatingSource) -> bool {
+        self.sources.contains(&source)
+    }
+
+    /// Mêmes réglages, limités à la note Plex (saisons)
+    pub fn plex_only(&self) -> Self {
+        Self { sources: vec![RatingSource::Plex], ..self.clone() }
+    }
+
+    fn style(&self, source: RatingSource) -> Option<&SourceStyle> {
+        self.styles.get(&source)
+    }
+
+    /// Notes à afficher, dans l'ordre des sources configurées
+    pub fn shown<'a>(&'a self, ratings: &'a Ratings) -> impl Iterator<Item = &'a Rating> + 'a {
+        self.sources.iter()
+            .filter_map(|source| ratings.get(*source))
+            .filter(|rating| self.has_enough_votes(rating))
+    }
+
+    fn has_enough_votes(&self, rating: &Rating) -> bool {
+        let min_votes = self.style(rating.source).and_then(|s| s.min_votes).unwrap_or(self.min_votes);
+        match rating.votes {
+            Some(votes) if votes < min_votes => {
+                debug!(source = rating.source.key(), votes, min_votes, "Note masquée : trop peu de votes");
+                false
+            }
+            _ => true,
+        }
+    }
+
+    pub fn level(&self, rating: &Rating) -> RatingLevel {
+        let style = self.style(rating.source);
+        let scale = rating.source.scale();
+        let high = style.and_then(|s| s.high).unwrap_or(scale * 0.8);
+        let mid = style.and_then(|s| s.mid).unwrap_or(scale * 0.6);
+
+        if rating.value >= high {
+            RatingLevel::High
+        } else if rating.value >= mid {
+            RatingLevel::Mid
+        } else {
+            RatingLevel::Low
+        }
+    }
+
+    /// Fond du badge généré selon le niveau (vert, ambre, rouge par défaut)
+    pub fn background(&self, source: RatingSource, level: RatingLevel) -> Rgba<u8> {
+        let colors = self.style(source).map(|s| &s.colors);
+        let configured = match level {
+            RatingLevel::High => colors.and_then(|c| c.high.as_deref()),
+            RatingLevel::Mid => colors.and_then(|c| c.mid.as_deref()),
+            RatingLevel::Low => colors.and_then(|c| c.low.as_deref()),
+        };
+        configured.and_then(parse_color).unwrap_or(match level {
+            RatingLevel::High => Rgba([33, 140, 74, 220]),
+            RatingLevel::Mid => Rgba([214, 150, 20, 220]),
+            RatingLevel::Low => Rgba([190, 40, 40, 220]),
+        })
+    }
+
+    pub fn label(&self, source: RatingSource) -> String {
+        self.style(source)
+            .and_then(|s| s.label.clone())
+            .unwrap_or_else(|| source.default_label().to_string())
+    }
+
+    /// Icône de la source si le fichier existe (variante SVG comprise)
+    pub fn icon(&self, source: RatingSource, overlays_base: &Path) -> Option<PathBuf> {
+        let relative = self.style(source)
+            .and_then(|s| s.icon.clone())
+            .unwrap_or_else(|| format!("ratings/{}.png", source.key()));
+        assets::resolve(&overlays_base.join(relative))
+    }
+
+    /// Badge généré d'une note : icône de la source (ou libellé) puis valeur, fond selon le niveau
+    pub fn spec(&self, rating: &Rating, base: BadgeStyle, overlays_base: &Path) -> BadgeSpec {
+        let image = self.icon(rating.source, overlays_base);
+        let label = self.label(rating.source);
+        let text = if image.is_some() || label.is_empty() {
+            rating.display()
+        } else {
+            format!("{} {}", label, rating.display())
+        };
+
+        BadgeSpec {
+            text,
+            icon: None,
+            image,
+            style: BadgeStyle { background: self.background(rating.source, self.level(rating)), ..base },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_strips_separators_and_units() {
+        assert_eq!(parse_number("7.8"), Some(7.8));
+        assert_eq!(parse_number("1,234,567"), Some(1_234_567.0));
+        assert_eq!(parse_number("91%"), Some(91.0));
+        assert_eq!(parse_number(" 64 "), Some(64.0));
+    }
+
+    #[test]
+    fn parse_number_rejects_missing_values() {
+        assert_eq!(parse_number("N/A"), None);
+        assert_eq!(parse_number(""), None);
+    }
+
+    #[test]
+    fn first_rating_of_a_source_wins() {
+        let mut ratings = Ratings::default();
+        ratings.add(RatingSource::Imdb, 0.0, None);
+        ratings.add(RatingSource::Imdb, 7.8, Some(1200));
+        ratings.add(RatingSource::Imdb, 6.1, Some(50));
+
+        let imdb = ratings.get(RatingSource::Imdb).unwrap();
+        assert_eq!((imdb.value, imdb.votes), (7.8, Some(1200)));
+    }
+}
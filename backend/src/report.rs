@@ -0,0 +1,275 @@
+// backend/src/report.rs
+// Rapports de traitement structurés (résultat, durées, overlays appliqués)
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+use anyhow::Result;
+use crate::locale;
+use crate::metrics;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Movie,
+    Show,
+    Season,
+}
+
+impl MediaKind {
+    /// Clé du catalogue pour la raison « déjà traité » (accordée en français)
+    fn already_processed_key(&self) -> &'static str {
+        match self {
+            MediaKind::Movie => "report.already_processed.movie",
+            MediaKind::Show => "report.already_processed.show",
+            MediaKind::Season => "report.already_processed.season",
+        }
+    }
+}
+
+/// Résultat du traitement d'un élément
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ProcessOutcome {
+    /// Poster généré, uploadé et labellisé
+    Rendered,
+    /// Élément ignoré volontairement (déjà traité, etc.)
+    Skipped { reason: String },
+    /// Aucun ID TMDB dans les métadonnées Plex
+    NoTmdbId,
+    /// Aucun poster trouvé sur TMDB
+    NoPoster,
+    /// Erreur API TMDB lors de la recherche du poster
+    LookupFailed { error: String },
+    /// Échec du téléchargement du poster source
+    DownloadFailed { error: String },
+    /// Erreur pendant l'application des overlays ou l'encodage
+    RenderFailed { error: String },
+    /// Échec de l'upload vers Plex
+    UploadFailed { error: String },
+    /// Impossible de récupérer l'élément côté Plex
+    PlexFailed { error: String },
+}
+
+impl ProcessOutcome {
+    pub fn is_rendered(&self) -> bool {
+        matches!(self, ProcessOutcome::Rendered)
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, ProcessOutcome::Skipped { .. })
+    }
+
+    pub fn is_failure(&self) -> bool {
+        !self.is_rendered() && !self.is_skipped()
+    }
+
+    /// Identifiant court (utilisé pour les exports et les compteurs)
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProcessOutcome::Rendered => "rendered",
+            ProcessOutcome::Skipped { .. } => "skipped",
+            ProcessOutcome::NoTmdbId => "no_tmdb_id",
+            ProcessOutcome::NoPoster => "no_poster",
+            ProcessOutcome::LookupFailed { .. } => "lookup_failed",
+            ProcessOutcome::DownloadFailed { .. } => "download_failed",
+            ProcessOutcome::RenderFailed { .. } => "render_failed",
+            ProcessOutcome::UploadFailed { .. } => "upload_failed",
+            ProcessOutcome::PlexFailed { .. } => "plex_failed",
+        }
+    }
+
+    /// Détail lisible (raison du skip ou message d'erreur)
+    pub fn detail(&self) -> String {
+        match self {
+            ProcessOutcome::Rendered => String::new(),
+            ProcessOutcome::Skipped { reason } => reason.clone(),
+            ProcessOutcome::NoTmdbId => locale::t("report.no_tmdb_id"),
+            ProcessOutcome::NoPoster => locale::t("report.no_poster"),
+            ProcessOutcome::LookupFailed { error }
+            | ProcessOutcome::DownloadFailed { error }
+            | ProcessOutcome::RenderFailed { error }
+            | ProcessOutcome::UploadFailed { error }
+            | ProcessOutcome::PlexFailed { error } => error.clone(),
+        }
+    }
+}
+
+/// Durées (ms) des différentes étapes du traitement
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StageTimings {
+    pub lookup_ms: u64,
+    pub download_ms: u64,
+    pub render_ms: u64,
+    pub upload_ms: u64,
+    pub total_ms: u64,
+}
+
+/// Rapport complet pour un élément traité
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessReport {
+    pub kind: MediaKind,
+    pub rating_key: String,
+    pub title: String,
+    #[serde(flatten)]
+    pub outcome: ProcessOutcome,
+    pub overlays: Vec<String>,
+    pub timings: StageTimings,
+    #[serde(skip)]
+    started_at: Option<Instant>,
+}
+
+impl ProcessReport {
+    pub fn new(kind: MediaKind, rating_key: &str, title: &str) -> Self {
+        Self {
+            kind,
+            rating_key: rating_key.to_string(),
+            title: title.to_string(),
+            outcome: ProcessOutcome::Rendered,
+            overlays: Vec::new(),
+            timings: StageTimings::default(),
+            started_at: Some(Instant::now()),
+        }
+    }
+
+    /// Rapport immédiat pour un élément ignoré
+    pub fn skipped(kind: MediaKind, rating_key: &str, title: &str, reason: &str) -> Self {
+        Self::new(kind, rating_key, title).finish(ProcessOutcome::Skipped { reason: reason.to_string() })
+    }
+
+    /// Rapport immédiat pour un élément qui porte déjà le label Rustizarr
+    pub fn already_processed(kind: MediaKind, rating_key: &str, title: &str) -> Self {
+        Self::skipped(kind, rating_key, title, &locale::t(kind.already_processed_key()))
+    }
+
+    /// Rapport immédiat pour un élément qui n'a pas pu être récupéré sur Plex
+    pub fn plex_failed(kind: MediaKind, rating_key: &str, title: &str, error: &anyhow::Error) -> Self {
+        Self::new(kind, rating_key, title).finish(ProcessOutcome::PlexFailed { error: format!("{:?}", error) })
+    }
+
+    /// Enregistre un overlay appliqué au poster
+    pub fn add_overlay(&mut self, name: impl Into<String>) {
+        self.overlays.push(name.into());
+    }
+
+    /// Fige le résultat et la durée totale
+    pub fn finish(mut self, outcome: ProcessOutcome) -> Self {
+        if let Some(start) = self.started_at {
+            self.timings.total_ms = start.elapsed().as_millis() as u64;
+        }
+        metrics::record_outcome(self.kind, &outcome);
+        self.outcome = outcome;
+        self
+    }
+}
+
+/// Chronomètre d'étape : `let t = Stage::start(); ...; report.timings.x_ms = t.ms();`
+pub struct Stage(Instant);
+
+impl Stage {
+    pub fn start() -> Self {
+        Stage(Instant::now())
+    }
+
+    pub fn ms(&self) -> u64 {
+        self.0.elapsed().as_millis() as u64
+    }
+}
+
+/// Compteurs agrégés d'une série de rapports
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportSummary {
+    pub total: usize,
+    pub rendered: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub by_outcome: BTreeMap<&'static str, usize>,
+}
+
+impl ReportSummary {
+    pub fn from_reports(reports: &[ProcessReport]) -> Self {
+        let mut summary = ReportSummary::default();
+        for report in reports {
+            summary.add(&report.outcome);
+        }
+        summary
+    }
+
+    pub fn add(&mut self, outcome: &ProcessOutcome) {
+        self.total += 1;
+        if outcome.is_rendered() {
+            self.rendered += 1;
+        } else if outcome.is_skipped() {
+            self.skipped += 1;
+        } else {
+            self.failed += 1;
+        }
+        *self.by_outcome.entry(outcome.code()).or_insert(0) += 1;
+    }
+}
+
+// ==================== EXPORT ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl ReportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Ligne "à plat" pour l'export CSV
+#[derive(Serialize)]
+struct ReportRow<'a> {
+    kind: MediaKind,
+    rating_key: &'a str,
+    title: &'a str,
+    outcome: &'static str,
+    detail: String,
+    overlays: String,
+    lookup_ms: u64,
+    download_ms: u64,
+    render_ms: u64,
+    upload_ms: u64,
+    total_ms: u64,
+}
+
+/// Écrit les rapports au format demandé
+pub fn write_reports(reports: &[ProcessReport], format: ReportFormat, path: &Path) -> Result<()> {
+    match format {
+        ReportFormat::Json => {
+            let json = serde_json::json!({
+                "summary": ReportSummary::from_reports(reports),
+                "items": reports,
+            });
+            std::fs::write(path, serde_json::to_string_pretty(&json)?)?;
+        }
+        ReportFormat::Csv => {
+            let mut writer = csv::Writer::from_path(path)?;
+            for report in reports {
+                writer.serialize(ReportRow {
+                    kind: report.kind,
+                    rating_key: &report.rating_key,
+                    title: &report.title,
+                    outcome: report.outcome.code(),
+                    detail: report.outcome.detail(),
+                    overlays: report.overlays.join(";"),
+                    lookup_ms: report.timings.lookup_ms,
+                    download_ms: report.timings.download_ms,
+                    render_ms: report.timings.render_ms,
+                    upload_ms: report.timings.upload_ms,
+                    total_ms: report.timings.total_ms,
+                })?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,338 @@
+// backend/src/scans.rs
+// Scans asynchrones : registre des scans en cours + diffusion de la progression (SSE)
+use axum::response::sse::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
+use futures::stream::{self, Stream, StreamExt};
+use backend::report::{ProcessReport, ReportSummary};
+
+/// Nombre de scans terminés conservés en mémoire (consultables via l'API)
+const MAX_FINISHED_SCANS: usize = 20;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanKind {
+    Movies,
+    Shows,
+    /// Saisons d'une seule série
+    Seasons,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScanStatus {
+    Pending,
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl ScanStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ScanStatus::Completed | ScanStatus::Cancelled | ScanStatus::Failed)
+    }
+}
+
+/// Résultat du traitement d'un élément du scan
+#[derive(Clone, Debug, Serialize)]
+pub struct ScanItemResult {
+    pub index: usize,
+    #[serde(flatten)]
+    pub report: ProcessReport,
+}
+
+/// Photo instantanée de l'état d'un scan (sans le détail par élément)
+#[derive(Clone, Debug, Serialize)]
+pub struct ScanSnapshot {
+    pub id: String,
+    pub kind: ScanKind,
+    pub status: ScanStatus,
+    pub total: usize,
+    pub done: usize,
+    pub rendered: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub by_outcome: std::collections::BTreeMap<&'static str, usize>,
+    pub current: Option<String>,
+    pub elapsed_secs: u64,
+    pub eta_secs: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Événements diffusés aux clients SSE
+#[derive(Clone, Debug)]
+pub enum ScanEvent {
+    Progress(ScanSnapshot),
+    Item(ScanItemResult),
+    Done(ScanSnapshot),
+}
+
+impl ScanEvent {
+    fn to_sse(&self) -> Event {
+        let event = match self {
+            ScanEvent::Progress(snapshot) => Event::default().event("progress").json_data(snapshot),
+            ScanEvent::Item(result) => Event::default().event("item").json_data(result),
+            ScanEvent::Done(snapshot) => Event::default().event("done").json_data(snapshot),
+        };
+        event.unwrap_or_else(|_| Event::default().event("error"))
+    }
+}
+
+struct ScanState {
+    status: ScanStatus,
+    total: usize,
+    summary: ReportSummary,
+    current: Option<String>,
+    started_at: Option<Instant>,
+    finished_at: Option<Instant>,
+    error: Option<String>,
+    results: Vec<ScanItemResult>,
+}
+
+/// Un scan lancé en tâche de fond
+pub struct ScanHandle {
+    pub id: String,
+    pub kind: ScanKind,
+    created_at: Instant,
+    state: Mutex<ScanState>,
+    events: broadcast::Sender<ScanEvent>,
+    cancelled: AtomicBool,
+}
+
+impl ScanHandle {
+    fn new(kind: ScanKind) -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            created_at: Instant::now(),
+            state: Mutex::new(ScanState {
+                status: ScanStatus::Pending,
+                total: 0,
+                summary: ReportSummary::default(),
+                current: None,
+                started_at: None,
+                finished_at: None,
+                error: None,
+                results: Vec::new(),
+            }),
+            events,
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    fn build_snapshot(&self, state: &ScanState) -> ScanSnapshot {
+        let done = state.summary.total;
+        let elapsed = match (state.started_at, state.finished_at) {
+            (Some(start), Some(end)) => end.duration_since(start),
+            (Some(start), None) => start.elapsed(),
+            _ => Default::default(),
+        };
+
+        // ETA : durée moyenne par élément × éléments restants
+        let eta_secs = if state.status == ScanStatus::Running && done > 0 {
+            let remaining = state.total.saturating_sub(done) as f64;
+            Some((elapsed.as_secs_f64() / done as f64 * remaining).round() as u64)
+        } else {
+            None
+        };
+
+        ScanSnapshot {
+            id: self.id.clone(),
+            kind: self.kind,
+            status: state.status,
+            total: state.total,
+            done,
+            rendered: state.summary.rendered,
+            skipped: state.summary.skipped,
+            failed: state.summary.failed,
+            by_outcome: state.summary.by_outcome.clone(),
+            current: state.current.clone(),
+            elapsed_secs: elapsed.as_secs(),
+            eta_secs,
+            error: state.error.clone(),
+        }
+    }
+
+    pub fn snapshot(&self) -> ScanSnapshot {
+        let state = self.state.lock().unwrap();
+        self.build_snapshot(&state)
+    }
+
+    pub fn results(&self) -> Vec<ScanItemResult> {
+        self.state.lock().unwrap().results.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    fn publish(&self, event: ScanEvent) {
+        // Aucun abonné n'est pas une erreur : le scan continue sans spectateur
+        let _ = self.events.send(event);
+    }
+
+    /// Passe le scan en cours d'exécution avec le nombre total d'éléments
+    pub fn start(&self, total: usize) {
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            state.status = ScanStatus::Running;
+            state.total = total;
+            state.started_at = Some(Instant::now());
+            self.build_snapshot(&state)
+        };
+        self.publish(ScanEvent::Progress(snapshot));
+    }
+
+    /// Ajoute des éléments découverts en cours de scan (ex: saisons d'une série)
+    pub fn add_total(&self, count: usize) {
+        self.state.lock().unwrap().total += count;
+    }
+
+    /// Signale l'élément en cours de traitement
+    pub fn begin_item(&self, title: &str) {
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            state.current = Some(title.to_string());
+            self.build_snapshot(&state)
+        };
+        self.publish(ScanEvent::Progress(snapshot));
+    }
+
+    /// Enregistre le rapport d'un élément et met à jour les compteurs
+    pub fn finish_item(&self, report: ProcessReport) {
+        let (result, snapshot) = {
+            let mut state = self.state.lock().unwrap();
+            state.summary.add(&report.outcome);
+            let result = ScanItemResult {
+                index: state.results.len(),
+                report,
+            };
+            state.results.push(result.clone());
+            state.current = None;
+            (result, self.build_snapshot(&state))
+        };
+        self.publish(ScanEvent::Item(result));
+        self.publish(ScanEvent::Progress(snapshot));
+    }
+
+    /// Termine le scan (Completed, ou Cancelled si une annulation a été demandée)
+    pub fn finish(&self) {
+        let status = if self.is_cancelled() { ScanStatus::Cancelled } else { ScanStatus::Completed };
+        self.close(status, None);
+    }
+
+    /// Termine le scan en erreur (ex: Plex injoignable)
+    pub fn fail(&self, error: String) {
+        self.close(ScanStatus::Failed, Some(error));
+    }
+
+    fn close(&self, status: ScanStatus, error: Option<String>) {
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            state.status = status;
+            state.current = None;
+            state.finished_at = Some(Instant::now());
+            state.error = error;
+            self.build_snapshot(&state)
+        };
+        self.publish(ScanEvent::Done(snapshot));
+    }
+
+    /// Flux SSE : état courant, puis événements en direct jusqu'à la fin du scan
+    pub fn event_stream(&self) -> impl Stream<Item = Result<Event, Infallible>> {
+        // Abonnement AVANT la photo instantanée pour ne perdre aucun événement
+        let rx = self.events.subscribe();
+        let snapshot = self.snapshot();
+
+        let (first, rx) = if snapshot.status.is_finished() {
+            (ScanEvent::Done(snapshot), None)
+        } else {
+            (ScanEvent::Progress(snapshot), Some(rx))
+        };
+
+        let live = stream::unfold(rx, |rx| async move {
+            let mut rx = rx?;
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        let next = if matches!(event, ScanEvent::Done(_)) { None } else { Some(rx) };
+                        return Some((event, next));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        stream::once(async move { first })
+            .chain(live)
+            .map(|event| Ok(event.to_sse()))
+    }
+}
+
+/// Registre des scans (en cours et récemment terminés)
+#[derive(Default)]
+pub struct ScanRegistry {
+    scans: Mutex<HashMap<String, Arc<ScanHandle>>>,
+}
+
+impl ScanRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Crée un nouveau scan, ou renvoie `Err` avec le scan déjà actif du même type
+    pub fn create(&self, kind: ScanKind) -> Result<Arc<ScanHandle>, Arc<ScanHandle>> {
+        let mut scans = self.scans.lock().unwrap();
+
+        if let Some(active) = scans.values()
+            .find(|s| s.kind == kind && !s.snapshot().status.is_finished())
+        {
+            return Err(active.clone());
+        }
+
+        Self::prune(&mut scans);
+
+        let handle = Arc::new(ScanHandle::new(kind));
+        scans.insert(handle.id.clone(), handle.clone());
+        Ok(handle)
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<ScanHandle>> {
+        self.scans.lock().unwrap().get(id).cloned()
+    }
+
+    pub fn list(&self) -> Vec<ScanSnapshot> {
+        let scans = self.scans.lock().unwrap();
+        scans.values().map(|s| s.snapshot()).collect()
+    }
+
+    /// Supprime les plus anciens scans terminés au-delà de MAX_FINISHED_SCANS
+    fn prune(scans: &mut HashMap<String, Arc<ScanHandle>>) {
+        let mut finished: Vec<(String, Instant)> = scans.values()
+            .filter(|s| s.snapshot().status.is_finished())
+            .map(|s| (s.id.clone(), s.created_at))
+            .collect();
+
+        if finished.len() < MAX_FINISHED_SCANS {
+            return;
+        }
+
+        finished.sort_by_key(|(_, created_at)| *created_at);
+        let excess = finished.len() + 1 - MAX_FINISHED_SCANS;
+        for (id, _) in finished.into_iter().take(excess) {
+            scans.remove(&id);
+        }
+    }
+}
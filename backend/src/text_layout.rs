@@ -0,0 +1,493 @@
+// backend/src/text_layout.rs
+// Mise en page des titres : taille auto-ajustée, coupures équilibrées, crénage, polices de repli
+use crate::locale;
+use anyhow::Result;
+use image::{Rgba, RgbaImage};
+use rusttype::{point, Font, GlyphId, Scale};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::{debug, warn};
+
+/// Largeur de référence des posters (les tailles de police y sont exprimées)
+const REFERENCE_WIDTH: f32 = 2000.0;
+
+/// Réduction de la taille de police à chaque essai
+const SIZE_STEP: f32 = 0.94;
+
+static FONT_CACHE: OnceLock<Mutex<HashMap<PathBuf, Arc<FontChain>>>> = OnceLock::new();
+
+// ==================== POLICES ====================
+
+/// Police principale suivie des polices de repli (glyphes absents : CJK, emoji, accents...)
+pub struct FontChain {
+    fonts: Vec<Font<'static>>,
+}
+
+impl FontChain {
+    /// Charge (une seule fois par dossier) `fonts/<primary>`, puis dans l'ordre :
+    /// TITLE_FALLBACK_FONTS (chemins séparés par des virgules), `fonts/fallback/*`, Avenir
+    pub fn load(overlays_base: &Path, primary: &str) -> Result<Arc<FontChain>> {
+        let fonts_dir = overlays_base.join("fonts");
+        let key = fonts_dir.join(primary);
+
+        let cache = FONT_CACHE.get_or_init(Default::default);
+        if let Some(chain) = cache.lock().unwrap().get(&key) {
+            return Ok(chain.clone());
+        }
+
+        let primary_font = read_font(&key)?
+            .ok_or_else(|| anyhow::anyhow!("Erreur chargement police {}", key.display()))?;
+
+        let mut candidates: Vec<PathBuf> = env::var("TITLE_FALLBACK_FONTS")
+            .map(|v| v.split(',').map(|p| PathBuf::from(p.trim())).filter(|p| !p.as_os_str().is_empty()).collect())
+            .unwrap_or_default();
+
+        if let Ok(entries) = fs::read_dir(fonts_dir.join("fallback")) {
+            let mut extra: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf")))
+                .collect();
+            extra.sort();
+            candidates.extend(extra);
+        }
+        candidates.push(fonts_dir.join("AvenirNextLTPro-Bold.ttf"));
+
+        let mut fonts = vec![primary_font];
+        for path in candidates.iter().filter(|p| **p != key) {
+            match read_font(path) {
+                Ok(Some(font)) => fonts.push(font),
+                Ok(None) => warn!(path = %path.display(), "{}", locale::t("log.text.fallback_font_unreadable")),
+                Err(_) => debug!(path = %path.display(), "Police de repli absente"),
+            }
+        }
+        debug!(primary, fallbacks = fonts.len() - 1, "Chaîne de polices chargée");
+
+        let chain = Arc::new(FontChain { fonts });
+        cache.lock().unwrap().insert(key, chain.clone());
+        Ok(chain)
+    }
+
+    fn has_glyph(font: &Font<'static>, c: char) -> bool {
+        font.glyph(c).id() != GlyphId(0)
+    }
+
+    /// Première police contenant le glyphe (la principale en dernier recours)
+    fn font_for(&self, c: char) -> usize {
+        if c.is_whitespace() {
+            return 0;
+        }
+        self.fonts.iter().position(|f| Self::has_glyph(f, c)).unwrap_or(0)
+    }
+
+    fn primary_has(&self, c: char) -> bool {
+        c.is_whitespace() || Self::has_glyph(&self.fonts[0], c)
+    }
+
+    /// Glyphes positionnés d'une ligne : (police, glyphe, x) et largeur totale (crénage inclus)
+    fn shape(&self, text: &str, size: f32) -> (Vec<(usize, GlyphId, f32)>, f32) {
+        let scale = Scale::uniform(size);
+        let mut glyphs = Vec::with_capacity(text.len());
+        let mut x = 0.0;
+        let mut previous: Option<(usize, GlyphId)> = None;
+
+        for c in text.chars() {
+            let index = self.font_for(c);
+            let font = &self.fonts[index];
+            let glyph = font.glyph(c).scaled(scale);
+            let id = glyph.id();
+
+            // Crénage uniquement entre deux glyphes de la même police
+            if let Some((prev_index, prev_id)) = previous {
+                if prev_index == index {
+                    x += font.pair_kerning(scale, prev_id, id);
+                }
+            }
+
+            glyphs.push((index, id, x));
+            x += glyph.h_metrics().advance_width;
+            previous = Some((index, id));
+        }
+
+        (glyphs, x)
+    }
+
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        self.shape(text, size).1
+    }
+
+    /// Hauteur au-dessus de la ligne de base (police principale)
+    pub fn ascent(&self, size: f32) -> f32 {
+        self.fonts[0].v_metrics(Scale::uniform(size)).ascent
+    }
+
+    /// Dessine une ligne, `y` étant le haut de la ligne (comme `draw_text_mut`)
+    pub fn draw_line(&self, image: &mut RgbaImage, color: Rgba<u8>, x: f32, y: f32, size: f32, text: &str) {
+        let scale = Scale::uniform(size);
+        let baseline = y + self.fonts[0].v_metrics(scale).ascent;
+        let (glyphs, _) = self.shape(text, size);
+
+        for (index, id, offset) in glyphs {
+            let glyph = self.fonts[index].glyph(id).scaled(scale).positioned(point(x + offset, baseline));
+            let Some(bounds) = glyph.pixel_bounding_box() else { continue };
+
+            glyph.draw(|gx, gy, coverage| {
+                let px = gx as i32 + bounds.min.x;
+                let py = gy as i32 + bounds.min.y;
+                if px < 0 || py < 0 || px >= image.width() as i32 || py >= image.height() as i32 {
+                    return;
+                }
+                blend(image.get_pixel_mut(px as u32, py as u32), color, coverage);
+            });
+        }
+    }
+}
+
+fn read_font(path: &Path) -> Result<Option<Font<'static>>> {
+    let data = fs::read(path)?;
+    Ok(Font::try_from_vec(data))
+}
+
+/// Mélange alpha d'un pixel de texte (couverture du glyphe × opacité de la couleur)
+fn blend(pixel: &mut Rgba<u8>, color: Rgba<u8>, coverage: f32) {
+    let alpha = coverage.clamp(0.0, 1.0) * color[3] as f32 / 255.0;
+    for channel in 0..3 {
+        pixel[channel] = (pixel[channel] as f32 * (1.0 - alpha) + color[channel] as f32 * alpha).round() as u8;
+    }
+    pixel[3] = pixel[3].max((alpha * 255.0) as u8);
+}
+
+// ==================== MISE EN PAGE ====================
+
+/// Mesures utilisées par la mise en page (police réelle ou largeur fixe dans les tests)
+pub trait TextMeasure {
+    fn measure(&self, text: &str, size: f32) -> f32;
+    /// La police principale a le glyphe (les espaces sont toujours acceptés)
+    fn primary_has(&self, c: char) -> bool;
+}
+
+impl TextMeasure for FontChain {
+    fn measure(&self, text: &str, size: f32) -> f32 {
+        FontChain::measure(self, text, size)
+    }
+
+    fn primary_has(&self, c: char) -> bool {
+        FontChain::primary_has(self, c)
+    }
+}
+
+/// Contraintes de mise en page d'un titre
+#[derive(Debug, Clone)]
+pub struct TitleStyle {
+    /// Taille maximale (px pour un poster de 2000px de large)
+    pub max_font_size: f32,
+    /// Taille minimale avant coupure des mots / ellipse
+    pub min_font_size: f32,
+    pub max_lines: usize,
+    /// Largeur utile (fraction de la largeur du poster)
+    pub max_width_ratio: f32,
+    /// Interligne (fraction de la taille de police)
+    pub line_height_ratio: f32,
+    pub uppercase: bool,
+}
+
+impl Default for TitleStyle {
+    fn default() -> Self {
+        Self {
+            max_font_size: 250.0,
+            min_font_size: 120.0,
+            max_lines: 3,
+            max_width_ratio: 0.92,
+            line_height_ratio: 0.85,
+            uppercase: true,
+        }
+    }
+}
+
+impl TitleStyle {
+    /// TITLE_MAX_FONT_SIZE, TITLE_MIN_FONT_SIZE, TITLE_MAX_LINES, TITLE_UPPERCASE
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let parse = |name: &str, default: f32| env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default);
+
+        let max_font_size = parse("TITLE_MAX_FONT_SIZE", defaults.max_font_size);
+        Self {
+            max_font_size,
+            min_font_size: parse("TITLE_MIN_FONT_SIZE", defaults.min_font_size).min(max_font_size),
+            max_lines: (parse("TITLE_MAX_LINES", defaults.max_lines as f32) as usize).max(1),
+            uppercase: env::var("TITLE_UPPERCASE").map(|v| v != "false" && v != "0").unwrap_or(defaults.uppercase),
+            ..defaults
+        }
+    }
+}
+
+/// Résultat : taille retenue et lignes (avec leur largeur)
+#[derive(Debug, Clone)]
+pub struct TitleLayout {
+    pub font_size: f32,
+    pub line_height: f32,
+    pub lines: Vec<(String, f32)>,
+}
+
+impl TitleLayout {
+    pub fn height(&self) -> f32 {
+        self.lines.len() as f32 * self.line_height
+    }
+}
+
+/// Majuscules caractère par caractère : la minuscule est conservée si la police
+/// principale n'a pas la majuscule correspondante (ex: capitales accentuées)
+pub fn prepare_text(chain: &impl TextMeasure, text: &str, uppercase: bool) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !uppercase {
+        return text;
+    }
+
+    text.chars()
+        .flat_map(|c| {
+            let upper: Vec<char> = c.to_uppercase().collect();
+            if upper.iter().all(|u| chain.primary_has(*u)) || !chain.primary_has(c) {
+                upper
+            } else {
+                vec![c]
+            }
+        })
+        .collect()
+}
+
+/// Cherche la plus grande taille tenant en `max_lines` lignes équilibrées
+pub fn layout_title(chain: &impl TextMeasure, text: &str, image_width: u32, style: &TitleStyle) -> TitleLayout {
+    let ratio = image_width as f32 / REFERENCE_WIDTH;
+    let max_width = image_width as f32 * style.max_width_ratio;
+    let min_size = style.min_font_size * ratio;
+    let words: Vec<&str> = text.split(' ').filter(|w| !w.is_empty()).collect();
+
+    let mut size = style.max_font_size * ratio;
+    loop {
+        if let Some(lines) = balanced_lines(chain, &words, size, max_width, style.max_lines) {
+            debug!(font_size = size, lines = lines.len(), "Titre mis en page");
+            return TitleLayout { font_size: size, line_height: size * style.line_height_ratio, lines };
+        }
+        if size <= min_size {
+            break;
+        }
+        size = (size * SIZE_STEP).max(min_size);
+    }
+
+    // Taille minimale atteinte : coupure des mots trop longs puis ellipse
+    debug!(font_size = min_size, "Titre trop long, coupure et ellipse");
+    let lines = forced_lines(chain, &words, min_size, max_width, style.max_lines);
+    TitleLayout { font_size: min_size, line_height: min_size * style.line_height_ratio, lines }
+}
+
+/// Répartition en un minimum de lignes, puis minimisation de la ligne la plus large
+fn balanced_lines(chain: &impl TextMeasure, words: &[&str], size: f32, max_width: f32, max_lines: usize) -> Option<Vec<(String, f32)>> {
+    let n = words.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    // Largeur de chaque séquence de mots words[i..j]
+    let mut widths = vec![vec![f32::INFINITY; n + 1]; n + 1];
+    for i in 0..n {
+        for j in (i + 1)..=n {
+            let width = chain.measure(&words[i..j].join(" "), size);
+            widths[i][j] = width;
+            if width > max_width {
+                break;
+            }
+        }
+    }
+
+    // best[l][j] : plus grande largeur de ligne minimale pour placer words[..j] sur l lignes
+    let mut best = vec![vec![f32::INFINITY; n + 1]; max_lines + 1];
+    let mut split = vec![vec![0usize; n + 1]; max_lines + 1];
+    best[0][0] = 0.0;
+
+    for lines in 1..=max_lines {
+        for j in 1..=n {
+            for i in (lines - 1)..j {
+                let width = widths[i][j];
+                if width > max_width || best[lines - 1][i].is_infinite() {
+                    continue;
+                }
+                let cost = best[lines - 1][i].max(width);
+                if cost < best[lines][j] {
+                    best[lines][j] = cost;
+                    split[lines][j] = i;
+                }
+            }
+        }
+
+        if best[lines][n].is_finite() {
+            let mut result = Vec::with_capacity(lines);
+            let mut end = n;
+            for l in (1..=lines).rev() {
+                let start = split[l][end];
+                result.push((words[start..end].join(" "), widths[start][end]));
+                end = start;
+            }
+            result.reverse();
+            return Some(result);
+        }
+    }
+
+    None
+}
+
+/// Dernier recours : mots coupés (après un tiret, sinon à la largeur), remplissage glouton,
+/// ellipse sur la dernière ligne
+fn forced_lines(chain: &impl TextMeasure, words: &[&str], size: f32, max_width: f32, max_lines: usize) -> Vec<(String, f32)> {
+    // Segments (texte, précédé d'une espace)
+    let mut pieces: Vec<(String, bool)> = Vec::new();
+    for word in words {
+        let mut space_before = true;
+        for part in word.split_inclusive('-') {
+            let mut current = String::new();
+            for c in part.chars() {
+                current.push(c);
+                if chain.measure(&current, size) > max_width && current.chars().count() > 1 {
+                    current.pop();
+                    pieces.push((std::mem::take(&mut current), space_before));
+                    space_before = false;
+                    current.push(c);
+                }
+            }
+            pieces.push((current, space_before));
+            space_before = false;
+        }
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for (piece, space_before) in pieces {
+        let separator = if space_before { " " } else { "" };
+        match lines.last_mut() {
+            Some(line) if chain.measure(&format!("{}{}{}", line, separator, piece), size) <= max_width => {
+                line.push_str(separator);
+                line.push_str(&piece);
+            }
+            _ => lines.push(piece),
+        }
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let ellipsis = if chain.primary_has('…') { "…" } else { "..." };
+        let last = lines.last_mut().expect("max_lines >= 1");
+        while !last.is_empty() && chain.measure(&format!("{}{}", last, ellipsis), size) > max_width {
+            last.pop();
+        }
+        *last = format!("{}{}", last.trim_end(), ellipsis);
+    }
+
+    lines.into_iter()
+        .map(|line| {
+            let width = chain.measure(&line, size);
+            (line, width)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Police à chasse fixe : chaque caractère mesure la moitié de la taille
+    #[derive(Default)]
+    struct Monospace {
+        missing: Vec<char>,
+    }
+
+    impl TextMeasure for Monospace {
+        fn measure(&self, text: &str, size: f32) -> f32 {
+            text.chars().count() as f32 * size / 2.0
+        }
+
+        fn primary_has(&self, c: char) -> bool {
+            !self.missing.contains(&c)
+        }
+    }
+
+    /// Taille 2.0 : un caractère = une unité de largeur
+    const SIZE: f32 = 2.0;
+
+    fn texts(lines: &[(String, f32)]) -> Vec<&str> {
+        lines.iter().map(|(line, _)| line.as_str()).collect()
+    }
+
+    #[test]
+    fn balanced_split_beats_greedy() {
+        // Glouton : "aaaa bb cc" (10) / "dddd" (4) ; équilibré : 7 / 7
+        let lines = balanced_lines(&Monospace::default(), &["aaaa", "bb", "cc", "dddd"], SIZE, 10.0, 3).unwrap();
+        assert_eq!(texts(&lines), ["aaaa bb", "cc dddd"]);
+        assert_eq!(lines.iter().map(|(_, w)| *w).fold(0.0, f32::max), 7.0);
+    }
+
+    #[test]
+    fn balanced_uses_fewest_lines() {
+        let lines = balanced_lines(&Monospace::default(), &["le", "parrain"], SIZE, 20.0, 3).unwrap();
+        assert_eq!(texts(&lines), ["le parrain"]);
+        assert!(balanced_lines(&Monospace::default(), &[], SIZE, 20.0, 3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn balanced_fails_when_a_word_or_line_budget_overflows() {
+        let chain = Monospace::default();
+        assert!(balanced_lines(&chain, &["abcdefghijkl"], SIZE, 10.0, 3).is_none());
+        assert!(balanced_lines(&chain, &["aaaa", "bbbb", "cccc"], SIZE, 5.0, 2).is_none());
+    }
+
+    #[test]
+    fn forced_splits_long_words_by_characters() {
+        let lines = forced_lines(&Monospace::default(), &["abcdefghijklmno"], SIZE, 6.0, 3);
+        assert_eq!(texts(&lines), ["abcdef", "ghijkl", "mno"]);
+        assert!(lines.iter().all(|(_, w)| *w <= 6.0));
+    }
+
+    #[test]
+    fn forced_splits_after_hyphens_first() {
+        let lines = forced_lines(&Monospace::default(), &["spider-man"], SIZE, 7.0, 3);
+        assert_eq!(texts(&lines), ["spider-", "man"]);
+    }
+
+    #[test]
+    fn forced_adds_ellipsis_over_line_budget() {
+        let words = ["one", "two", "three", "four"];
+        let lines = forced_lines(&Monospace::default(), &words, SIZE, 7.0, 2);
+        assert_eq!(texts(&lines), ["one two", "three…"]);
+
+        // Sans glyphe "…" : trois points, la ligne est raccourcie pour tenir
+        let ascii = Monospace { missing: vec!['…'] };
+        let lines = forced_lines(&ascii, &words, SIZE, 7.0, 2);
+        assert_eq!(texts(&lines), ["one two", "thre..."]);
+    }
+
+    #[test]
+    fn prepare_text_collapses_whitespace() {
+        let chain = Monospace::default();
+        assert_eq!(prepare_text(&chain, "  le   seigneur\n des anneaux ", false), "le seigneur des anneaux");
+        assert_eq!(prepare_text(&chain, " le\tparrain ", true), "LE PARRAIN");
+    }
+
+    #[test]
+    fn prepare_text_keeps_lowercase_without_capital_glyph() {
+        let chain = Monospace { missing: vec!['É'] };
+        assert_eq!(prepare_text(&chain, "éléphant", true), "éLéPHANT");
+        assert_eq!(prepare_text(&chain, "straße", true), "STRASSE");
+        assert_eq!(prepare_text(&chain, "東京", true), "東京");
+    }
+
+    #[test]
+    fn layout_shrinks_font_until_title_fits() {
+        let style = TitleStyle::default();
+        let short = layout_title(&Monospace::default(), "DUNE", 2000, &style);
+        assert_eq!(short.font_size, style.max_font_size);
+        assert_eq!(short.lines.len(), 1);
+
+        let long = layout_title(&Monospace::default(), "THE LORD OF THE RINGS THE RETURN OF THE KING", 2000, &style);
+        assert!(long.font_size < style.max_font_size);
+        assert!(long.lines.len() <= style.max_lines);
+        assert!(long.lines.iter().all(|(_, w)| *w <= 2000.0 * style.max_width_ratio));
+    }
+}
@@ -0,0 +1,240 @@
+// backend/src/theme.rs
+// Thèmes des overlays : couleurs du titre et des badges, ombre, halo, opacité des gradients,
+// variante claire ou foncée choisie selon la luminosité du poster (THEME_FILE, THEME_CONTRAST)
+use crate::badges::{parse_color, BadgeStyle};
+use crate::image_ops::Palette;
+use crate::locale;
+use image::{Rgb, Rgba};
+use serde::Deserialize;
+use std::env;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Fichier de thème par défaut, à la racine du dossier overlays
+const DEFAULT_FILE: &str = "theme.json";
+
+/// Luminosité des zones d'overlays au-delà de laquelle le poster est jugé clair
+const DEFAULT_THRESHOLD: f32 = 0.6;
+
+// ==================== VARIANTES ====================
+
+/// Couleurs d'une variante : `Light` (texte clair, le rendu historique) ou `Dark` (posters clairs)
+#[derive(Debug, Clone)]
+pub struct ThemeVariant {
+    pub title: Rgba<u8>,
+    pub title_shadow: Option<Rgba<u8>>,
+    /// Halo flou derrière le titre
+    pub title_glow: Option<Rgba<u8>>,
+    /// Multiplie l'opacité des gradients haut et bas (0 = aucun, 1 = PNG d'origine)
+    pub gradient_opacity: f32,
+    pub badge_background: Rgba<u8>,
+    pub badge_text: Rgba<u8>,
+    pub badge_border: Option<Rgba<u8>>,
+    /// Note posée sur le visuel audience_score
+    pub score_text: Rgba<u8>,
+}
+
+impl ThemeVariant {
+    fn light() -> Self {
+        let badge = BadgeStyle::default();
+        Self {
+            title: Rgba([255, 255, 255, 255]),
+            title_shadow: Some(Rgba([0, 0, 0, 220])),
+            title_glow: None,
+            gradient_opacity: 1.0,
+            badge_background: badge.background,
+            badge_text: badge.text,
+            badge_border: badge.border,
+            score_text: Rgba([0, 0, 0, 255]),
+        }
+    }
+
+    fn dark() -> Self {
+        Self {
+            title: Rgba([20, 20, 20, 255]),
+            title_shadow: None,
+            title_glow: Some(Rgba([255, 255, 255, 200])),
+            gradient_opacity: 0.35,
+            badge_background: Rgba([255, 255, 255, 215]),
+            badge_text: Rgba([15, 15, 15, 255]),
+            badge_border: Some(Rgba([0, 0, 0, 200])),
+            score_text: Rgba([0, 0, 0, 255]),
+        }
+    }
+
+    fn merged(mut self, o: &VariantOverride) -> Self {
+        if let Some(color) = o.title.as_deref().and_then(parse_color) {
+            self.title = color;
+        }
+        if let Some(value) = o.title_shadow.as_deref() {
+            self.title_shadow = optional_color(value);
+        }
+        if let Some(value) = o.title_glow.as_deref() {
+            self.title_glow = optional_color(value);
+        }
+        if let Some(opacity) = o.gradient_opacity {
+            self.gradient_opacity = opacity.clamp(0.0, 1.0);
+        }
+        if let Some(color) = o.badge_background.as_deref().and_then(parse_color) {
+            self.badge_background = color;
+        }
+        if let Some(color) = o.badge_text.as_deref().and_then(parse_color) {
+            self.badge_text = color;
+        }
+        if let Some(value) = o.badge_border.as_deref() {
+            self.badge_border = optional_color(value);
+        }
+        if let Some(color) = o.score_text.as_deref().and_then(parse_color) {
+            self.score_text = color;
+        }
+        self
+    }
+}
+
+/// "none" désactive l'effet
+fn optional_color(value: &str) -> Option<Rgba<u8>> {
+    if value.eq_ignore_ascii_case("none") { None } else { parse_color(value) }
+}
+
+/// Variante partielle du fichier de thème ("#RRGGBB[AA]", "none" pour désactiver)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VariantOverride {
+    pub title: Option<String>,
+    pub title_shadow: Option<String>,
+    pub title_glow: Option<String>,
+    pub gradient_opacity: Option<f32>,
+    pub badge_background: Option<String>,
+    pub badge_text: Option<String>,
+    pub badge_border: Option<String>,
+    pub score_text: Option<String>,
+}
+
+// ==================== CONFIGURATION ====================
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Contrast {
+    /// Variante claire partout (rendu historique)
+    #[default]
+    Light,
+    Dark,
+    /// Variante choisie selon la luminosité des zones d'overlays du poster
+    Auto,
+}
+
+impl Contrast {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "light" => Some(Contrast::Light),
+            "dark" => Some(Contrast::Dark),
+            "auto" => Some(Contrast::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// Contenu de `theme.json` :
+/// `{ "contrast": "auto", "tint_borders": true, "dark": { "title": "#1A1A1A", "gradient_opacity": 0.2 } }`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub contrast: Contrast,
+    /// Seuil de luminosité (0-1) du mode auto
+    pub threshold: Option<f32>,
+    /// Teinte l'inner glow avec la couleur d'accent du poster
+    pub tint_borders: bool,
+    pub light: VariantOverride,
+    pub dark: VariantOverride,
+}
+
+impl ThemeConfig {
+    /// THEME_FILE, sinon `<overlays>/theme.json` ; THEME_CONTRAST et BORDER_TINT remplacent le fichier
+    pub fn load(overlays_base: &Path) -> Self {
+        let path = env::var("THEME_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| overlays_base.join(DEFAULT_FILE));
+
+        let mut config = Self::read(&path);
+        if let Some(contrast) = env::var("THEME_CONTRAST").ok().as_deref().and_then(Contrast::parse) {
+            config.contrast = contrast;
+        }
+        if let Ok(value) = env::var("BORDER_TINT") {
+            config.tint_borders = value == "true" || value == "1";
+        }
+        config
+    }
+
+    fn read(path: &Path) -> Self {
+        let Ok(raw) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str::<Self>(&raw) {
+            Ok(config) => {
+                debug!(path = %path.display(), "Thème chargé");
+                config
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "{}", locale::t("log.theme.invalid_file"));
+                Self::default()
+            }
+        }
+    }
+
+    /// Thème d'un poster d'après sa palette
+    pub fn resolve(&self, palette: &Palette) -> PosterTheme {
+        let threshold = self.threshold.unwrap_or(DEFAULT_THRESHOLD);
+        let dark = match self.contrast {
+            Contrast::Light => false,
+            Contrast::Dark => true,
+            Contrast::Auto => palette.overlay_luminance >= threshold,
+        };
+
+        let variant = if dark {
+            ThemeVariant::dark().merged(&self.dark)
+        } else {
+            ThemeVariant::light().merged(&self.light)
+        };
+        debug!(dark, luminance = palette.overlay_luminance, "Variante de thème choisie");
+
+        PosterTheme {
+            dark,
+            variant,
+            border_tint: self.tint_borders.then_some(palette.accent),
+        }
+    }
+}
+
+// ==================== THÈME D'UN POSTER ====================
+
+/// Thème résolu pour un poster donné
+#[derive(Debug, Clone)]
+pub struct PosterTheme {
+    /// Variante foncée retenue (poster clair)
+    pub dark: bool,
+    pub variant: ThemeVariant,
+    /// Couleur d'accent appliquée à l'inner glow
+    pub border_tint: Option<Rgb<u8>>,
+}
+
+impl Default for PosterTheme {
+    fn default() -> Self {
+        Self { dark: false, variant: ThemeVariant::light(), border_tint: None }
+    }
+}
+
+impl PosterTheme {
+    /// Style de base des badges générés, avant les surcharges de badges.json
+    pub fn badge_style(&self) -> BadgeStyle {
+        BadgeStyle {
+            background: self.variant.badge_background,
+            text: self.variant.badge_text,
+            border: self.variant.badge_border,
+            ..BadgeStyle::default()
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        if self.dark { "dark" } else { "light" }
+    }
+}
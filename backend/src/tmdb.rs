@@ -1,17 +1,28 @@
 use reqwest::Client;
 use serde::Deserialize;
 use anyhow::Result;
+use crate::locale;
+use crate::metrics;
+use tracing::debug;
 
 #[derive(Clone)]
 pub struct TmdbClient {
     client: reqwest::Client,
     api_key: String,
     base_url: String,
+    /// Langue des réponses (`language`), ex: "fr" ou "pt-BR"
+    language: String,
+    /// Langue de repli des posters (ISO 639-1), après les textless
+    image_language: String,
 }
 
 #[derive(Deserialize, Debug)]
 struct ImageResponse {
+    #[serde(default)]
     posters: Vec<PosterImage>,
+    /// Logos de titre transparents (PNG ou SVG)
+    #[serde(default)]
+    logos: Vec<PosterImage>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -26,17 +37,113 @@ struct PosterImage {
 #[derive(Deserialize, Debug)]
 struct MovieDetails {
     poster_path: Option<String>,
+    vote_average: Option<f64>,
+    vote_count: Option<u64>,
 }
 
 #[derive(Deserialize, Debug)]
 struct ShowDetails {
     poster_path: Option<String>,
     status: Option<String>,
+    vote_average: Option<f64>,
+    vote_count: Option<u64>,
 }
 
 #[derive(Deserialize, Debug)]
 struct SeasonDetails {
     poster_path: Option<String>,
+    vote_average: Option<f64>,
+}
+
+/// Poster standard et note TMDB d'une saison
+#[derive(Debug, Clone, Default)]
+pub struct SeasonInfo {
+    pub poster_url: Option<String>,
+    /// `None` si la saison n'a reçu aucun vote
+    pub vote_average: Option<f64>,
+}
+
+/// Détails d'un film ou d'une série, récupérés en une seule requête
+#[derive(Debug, Clone, Default)]
+pub struct Details {
+    /// Poster standard (repli quand il n'existe pas de textless)
+    pub poster_url: Option<String>,
+    pub votes: Option<Votes>,
+    /// Séries : Returning Series, Ended, Canceled...
+    pub status: Option<String>,
+}
+
+/// Note TMDB d'un film ou d'une série
+#[derive(Debug, Clone, Copy)]
+pub struct Votes {
+    /// Sur 10
+    pub average: f64,
+    pub count: u64,
+}
+
+impl Votes {
+    /// `None` sans aucun vote (TMDB renvoie alors 0.0)
+    fn from_details(average: Option<f64>, count: Option<u64>) -> Option<Self> {
+        let count = count.unwrap_or(0);
+        if count == 0 { return None; }
+        Some(Self { average: average?, count })
+    }
+}
+
+/// Politique de sélection commune (films, séries, saisons) :
+/// 1. textless ("xx" ou sans langue), plus grande résolution puis meilleure note
+/// 2. à défaut, plus grande résolution dans la langue configurée
+fn select_poster<'a>(posters: &'a [PosterImage], language: &str) -> Option<&'a PosterImage> {
+    let textless = posters.iter()
+        .filter(|p| match &p.iso_639_1 {
+            Some(lang) => lang == "xx" || lang == "null",
+            None => true
+        })
+        .max_by(|a, b| {
+            (a.width * a.height).cmp(&(b.width * b.height))
+                .then(a.vote_average.partial_cmp(&b.vote_average).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+    if let Some(best) = textless {
+        debug!(width = best.width, height = best.height, vote = best.vote_average, "Meilleur poster textless trouvé");
+        return Some(best);
+    }
+
+    let best_localized = posters.iter()
+        .filter(|p| p.iso_639_1.as_deref() == Some(language))
+        .max_by_key(|p| p.width * p.height)?;
+    debug!(width = best_localized.width, height = best_localized.height, language, "Pas de textless pur, utilisation du meilleur poster localisé");
+    Some(best_localized)
+}
+
+/// Logo de titre : langue configurée, puis anglais, puis sans langue ; meilleure note puis plus large
+fn select_logo<'a>(logos: &'a [PosterImage], language: &str) -> Option<&'a PosterImage> {
+    let rank = |logo: &PosterImage| match logo.iso_639_1.as_deref() {
+        Some(lang) if lang == language => 0,
+        Some("en") => 1,
+        None | Some("xx") | Some("null") => 2,
+        Some(_) => 3,
+    };
+
+    logos.iter()
+        .filter(|logo| rank(logo) < 3)
+        .min_by(|a, b| {
+            rank(a).cmp(&rank(b))
+                .then(b.vote_average.partial_cmp(&a.vote_average).unwrap_or(std::cmp::Ordering::Equal))
+                .then(b.width.cmp(&a.width))
+        })
+}
+
+/// Les logos SVG sont demandés en PNG (TMDB les rastérise à la volée)
+fn logo_url(path: &str) -> String {
+    match path.strip_suffix(".svg") {
+        Some(stem) => original_url(&format!("{}.png", stem)),
+        None => original_url(path),
+    }
+}
+
+fn original_url(path: &str) -> String {
+    format!("https://image.tmdb.org/t/p/original{}", path)
 }
 
 impl TmdbClient {
@@ -45,226 +152,139 @@ impl TmdbClient {
             client: Client::new(),
             api_key,
             base_url: "https://api.themoviedb.org/3".to_string(),
+            language: locale::current().code().to_string(),
+            image_language: locale::current().language(),
         }
     }
 
+    /// Requête GET vers TMDB (latence et statut enregistrés dans les métriques)
+    async fn send(&self, endpoint: &str, url: &str) -> reqwest::Result<reqwest::Response> {
+        metrics::timed_send("tmdb", endpoint, self.client.get(url)).await
+    }
+
     // ==================== FILMS ====================
 
     /// Récupère le MEILLEUR poster textless pour un FILM (haute définition)
     pub async fn get_textless_poster(&self, tmdb_id: &str) -> Result<Option<String>> {
-        let url = format!("{}/movie/{}/images?api_key={}", self.base_url, tmdb_id, self.api_key);
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { return Ok(None); }
-
-        let images: ImageResponse = resp.json().await?;
-
-        // 1. Filtrer les candidats Textless ("xx" ou null)
-        let mut candidates: Vec<&PosterImage> = images.posters.iter()
-            .filter(|p| {
-                match &p.iso_639_1 {
-                    Some(lang) => lang == "xx" || lang == "null",
-                    None => true
-                }
-            })
-            .collect();
-
-        // 2. Si on en a trouvé, on trie pour trouver le "King"
-        if !candidates.is_empty() {
-            candidates.sort_by(|a, b| {
-                let res_a = a.width * a.height;
-                let res_b = b.width * b.height;
-                
-                res_b.cmp(&res_a)
-                    .then(b.vote_average.partial_cmp(&a.vote_average).unwrap_or(std::cmp::Ordering::Equal))
-            });
-
-            if let Some(best) = candidates.first() {
-                println!("      ✨ Meilleur poster textless trouvé : {}x{} (Note: {})", best.width, best.height, best.vote_average);
-                return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", best.file_path)));
-            }
-        }
-
-        // 3. Fallback : Meilleur en Français "fr"
-        let mut fr_candidates: Vec<&PosterImage> = images.posters.iter()
-            .filter(|p| p.iso_639_1.as_deref() == Some("fr"))
-            .collect();
-
-        if !fr_candidates.is_empty() {
-            fr_candidates.sort_by(|a, b| (b.width * b.height).cmp(&(a.width * a.height)));
-            
-            if let Some(best_fr) = fr_candidates.first() {
-                println!("      ⚠️ Pas de textless pur, utilisation du meilleur 'fr' : {}x{}", best_fr.width, best_fr.height);
-                return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", best_fr.file_path)));
-            }
-        }
+        let url = format!("{}/movie/{}/images?api_key={}&{}", self.base_url, tmdb_id, self.api_key, self.image_languages_param());
+        self.fetch_best_poster("movie_images", &url).await
+    }
 
-        Ok(None)
+    /// Récupère le logo de titre d'un FILM
+    pub async fn get_movie_logo(&self, tmdb_id: &str) -> Result<Option<String>> {
+        let url = format!("{}/movie/{}/images?api_key={}&{}", self.base_url, tmdb_id, self.api_key, self.logo_languages_param());
+        self.fetch_best_logo("movie_images", &url).await
     }
 
-    /// Récupère le poster standard d'un FILM
-    pub async fn get_standard_poster(&self, tmdb_id: &str) -> Result<Option<String>> {
-        let url = format!("{}/movie/{}?api_key={}", self.base_url, tmdb_id, self.api_key);
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { return Ok(None); }
+    /// Récupère les détails d'un FILM (poster standard, note)
+    pub async fn get_movie_details(&self, tmdb_id: &str) -> Result<Details> {
+        let url = format!("{}/movie/{}?api_key={}&language={}", self.base_url, tmdb_id, self.api_key, self.language);
 
-        let details: MovieDetails = resp.json().await?;
+        let resp = self.send("movie", &url).await?;
+        if !resp.status().is_success() { return Ok(Details::default()); }
 
-        if let Some(path) = details.poster_path {
-            return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", path)));
-        }
+        let details: MovieDetails = resp.json().await?;
 
-        Ok(None)
+        Ok(Details {
+            poster_url: details.poster_path.as_deref().map(original_url),
+            votes: Votes::from_details(details.vote_average, details.vote_count),
+            status: None,
+        })
     }
 
     // ==================== SÉRIES ====================
 
     /// Récupère le MEILLEUR poster textless pour une SÉRIE
     pub async fn get_show_textless_poster(&self, tmdb_id: &str) -> Result<Option<String>> {
-        let url = format!("{}/tv/{}/images?api_key={}", self.base_url, tmdb_id, self.api_key);
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { return Ok(None); }
-
-        let images: ImageResponse = resp.json().await?;
-
-        // 1. Filtrer les candidats Textless
-        let mut candidates: Vec<&PosterImage> = images.posters.iter()
-            .filter(|p| {
-                match &p.iso_639_1 {
-                    Some(lang) => lang == "xx" || lang == "null",
-                    None => true
-                }
-            })
-            .collect();
-
-        // 2. Tri par résolution et note
-        if !candidates.is_empty() {
-            candidates.sort_by(|a, b| {
-                let res_a = a.width * a.height;
-                let res_b = b.width * b.height;
-                
-                res_b.cmp(&res_a)
-                    .then(b.vote_average.partial_cmp(&a.vote_average).unwrap_or(std::cmp::Ordering::Equal))
-            });
-
-            if let Some(best) = candidates.first() {
-                println!("      ✨ Meilleur poster textless série trouvé : {}x{} (Note: {})", best.width, best.height, best.vote_average);
-                return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", best.file_path)));
-            }
-        }
-
-        // 3. Fallback français
-        let mut fr_candidates: Vec<&PosterImage> = images.posters.iter()
-            .filter(|p| p.iso_639_1.as_deref() == Some("fr"))
-            .collect();
-
-        if !fr_candidates.is_empty() {
-            fr_candidates.sort_by(|a, b| (b.width * b.height).cmp(&(a.width * a.height)));
-            
-            if let Some(best_fr) = fr_candidates.first() {
-                println!("      ⚠️ Pas de textless série, utilisation du meilleur 'fr' : {}x{}", best_fr.width, best_fr.height);
-                return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", best_fr.file_path)));
-            }
-        }
-
-        Ok(None)
+        let url = format!("{}/tv/{}/images?api_key={}&{}", self.base_url, tmdb_id, self.api_key, self.image_languages_param());
+        self.fetch_best_poster("tv_images", &url).await
     }
 
-    /// Récupère le poster standard d'une SÉRIE
-    pub async fn get_show_standard_poster(&self, tmdb_id: &str) -> Result<Option<String>> {
-        let url = format!("{}/tv/{}?api_key={}", self.base_url, tmdb_id, self.api_key);
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { return Ok(None); }
-
-        let details: ShowDetails = resp.json().await?;
-
-        if let Some(path) = details.poster_path {
-            return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", path)));
-        }
-
-        Ok(None)
+    /// Récupère le logo de titre d'une SÉRIE
+    pub async fn get_show_logo(&self, tmdb_id: &str) -> Result<Option<String>> {
+        let url = format!("{}/tv/{}/images?api_key={}&{}", self.base_url, tmdb_id, self.api_key, self.logo_languages_param());
+        self.fetch_best_logo("tv_images", &url).await
     }
 
-    /// Récupère le status d'une SÉRIE (Returning Series, Ended, Canceled, etc.)
-    pub async fn get_show_status(&self, tmdb_id: &str) -> Result<Option<String>> {
-        let url = format!("{}/tv/{}?api_key={}", self.base_url, tmdb_id, self.api_key);
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { return Ok(None); }
+    /// Récupère les détails d'une SÉRIE (poster standard, note, status)
+    pub async fn get_show_details(&self, tmdb_id: &str) -> Result<Details> {
+        let url = format!("{}/tv/{}?api_key={}&language={}", self.base_url, tmdb_id, self.api_key, self.language);
+
+        let resp = self.send("tv", &url).await?;
+        if !resp.status().is_success() { return Ok(Details::default()); }
 
         let details: ShowDetails = resp.json().await?;
 
-        Ok(details.status)
+        Ok(Details {
+            poster_url: details.poster_path.as_deref().map(original_url),
+            votes: Votes::from_details(details.vote_average, details.vote_count),
+            status: details.status,
+        })
     }
 
     // ==================== SAISONS ====================
 
-    /// Récupère le poster d'une SAISON spécifique
-    pub async fn get_season_poster(&self, show_tmdb_id: &str, season_number: u32) -> Result<Option<String>> {
+    /// Récupère le poster standard et la note d'une SAISON spécifique
+    pub async fn get_season_info(&self, show_tmdb_id: &str, season_number: u32) -> Result<SeasonInfo> {
         let url = format!(
-            "{}/tv/{}/season/{}?api_key={}",
-            self.base_url, show_tmdb_id, season_number, self.api_key
+            "{}/tv/{}/season/{}?api_key={}&language={}",
+            self.base_url, show_tmdb_id, season_number, self.api_key, self.language
         );
         
-        let resp = self.client.get(&url).send().await?;
+        let resp = self.send("season", &url).await?;
         
         if !resp.status().is_success() {
-            return Ok(None);
+            return Ok(SeasonInfo::default());
         }
         
         let details: SeasonDetails = resp.json().await?;
-        
-        if let Some(path) = details.poster_path {
-            Ok(Some(format!("https://image.tmdb.org/t/p/original{}", path)))
-        } else {
-            Ok(None)
-        }
+
+        Ok(SeasonInfo {
+            poster_url: details.poster_path.as_deref().map(original_url),
+            vote_average: details.vote_average.filter(|v| *v > 0.0),
+        })
     }
 
-    /// Récupère le poster textless d'une SAISON (si disponible)
+    /// Récupère le poster textless d'une SAISON (même politique que les films)
     pub async fn get_season_textless_poster(&self, show_tmdb_id: &str, season_number: u32) -> Result<Option<String>> {
         let url = format!(
-            "{}/tv/{}/season/{}/images?api_key={}",
-            self.base_url, show_tmdb_id, season_number, self.api_key
+            "{}/tv/{}/season/{}/images?api_key={}&{}",
+            self.base_url, show_tmdb_id, season_number, self.api_key, self.image_languages_param()
         );
-        
-        let resp = self.client.get(&url).send().await?;
-        if !resp.status().is_success() { 
-            // Fallback sur poster standard
-            return self.get_season_poster(show_tmdb_id, season_number).await;
-        }
+        self.fetch_best_poster("season_images", &url).await
+    }
 
-        let images: ImageResponse = resp.json().await?;
+    // ==================== COMMUN ====================
+
+    /// Restreint les images aux textless et à la langue configurée
+    fn image_languages_param(&self) -> String {
+        format!("include_image_language={},xx,null", self.image_language)
+    }
 
-        // Filtrer textless
-        let mut candidates: Vec<&PosterImage> = images.posters.iter()
-            .filter(|p| {
-                match &p.iso_639_1 {
-                    Some(lang) => lang == "xx" || lang == "null",
-                    None => true
-                }
-            })
-            .collect();
-
-        if !candidates.is_empty() {
-            candidates.sort_by(|a, b| {
-                let res_a = a.width * a.height;
-                let res_b = b.width * b.height;
-                
-                res_b.cmp(&res_a)
-                    .then(b.vote_average.partial_cmp(&a.vote_average).unwrap_or(std::cmp::Ordering::Equal))
-            });
-
-            if let Some(best) = candidates.first() {
-                println!("      ✨ Poster textless saison {} trouvé : {}x{}", season_number, best.width, best.height);
-                return Ok(Some(format!("https://image.tmdb.org/t/p/original{}", best.file_path)));
-            }
+    /// Logos : langue configurée, anglais en repli, et logos sans langue
+    fn logo_languages_param(&self) -> String {
+        format!("include_image_language={},en,null", self.image_language)
+    }
+
+    /// Liste d'images TMDB → URL du logo retenu par `select_logo`
+    async fn fetch_best_logo(&self, endpoint: &str, url: &str) -> Result<Option<String>> {
+        let resp = self.send(endpoint, url).await?;
+        if !resp.status().is_success() { return Ok(None); }
+
+        let images: ImageResponse = resp.json().await?;
+        let logo = select_logo(&images.logos, &self.image_language);
+        if let Some(logo) = logo {
+            debug!(width = logo.width, height = logo.height, language = ?logo.iso_639_1, "Logo de titre trouvé");
         }
+        Ok(logo.map(|l| logo_url(&l.file_path)))
+    }
+
+    /// Liste d'images TMDB → URL du poster retenu par `select_poster`
+    async fn fetch_best_poster(&self, endpoint: &str, url: &str) -> Result<Option<String>> {
+        let resp = self.send(endpoint, url).await?;
+        if !resp.status().is_success() { return Ok(None); }
 
-        // Fallback sur poster standard si pas de textless
-        self.get_season_poster(show_tmdb_id, season_number).await
+        let images: ImageResponse = resp.json().await?;
+        Ok(select_poster(&images.posters, &self.image_language).map(|p| original_url(&p.file_path)))
     }
 }
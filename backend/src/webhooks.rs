@@ -0,0 +1,696 @@
+// backend/src/webhooks.rs
+// Routage des webhooks (Plex, Radarr, Sonarr, Tautulli) : événement → action, et éléments à re-rendre
+use backend::plex::{PlexClient, PlexMovie, PlexShow};
+use backend::locale;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Événements traités par défaut (surchargés par WEBHOOK_EVENTS)
+const DEFAULT_EVENTS: &str = "library.new=render,media.rate=render,\
+    radarr.download=render,radarr.upgrade=render,radarr.rename=render,\
+    sonarr.download=render,sonarr.upgrade=render,sonarr.rename=render,\
+    tautulli.created=render";
+
+#[derive(Deserialize, Debug)]
+pub struct PlexWebhookPayload {
+    pub event: String,
+    #[serde(rename = "Metadata")]
+    pub metadata: Option<WebhookMetadata>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WebhookMetadata {
+    #[serde(rename = "ratingKey")]
+    pub rating_key: String,
+    #[serde(rename = "type")]
+    pub media_type: String,
+    /// Saison (épisode) ou série (saison)
+    #[serde(rename = "parentRatingKey")]
+    pub parent_rating_key: Option<String>,
+    /// Série (épisode)
+    #[serde(rename = "grandparentRatingKey")]
+    pub grandparent_rating_key: Option<String>,
+}
+
+// ==================== ACTIONS ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookAction {
+    /// Re-génère le poster de l'élément (et de ses parents pour un épisode)
+    Render,
+    /// Événement ignoré
+    Ignore,
+}
+
+impl FromStr for WebhookAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "render" => Ok(WebhookAction::Render),
+            "ignore" => Ok(WebhookAction::Ignore),
+            other => Err(format!("Action webhook inconnue : {}", other)),
+        }
+    }
+}
+
+/// Table événement → action
+///
+/// Format de WEBHOOK_EVENTS : `library.new=render,media.rate=render,library.on.deck=ignore`.
+/// Les événements Radarr / Sonarr / Tautulli sont préfixés (`radarr.download`, `sonarr.rename`...).
+/// Les événements absents de la table sont ignorés.
+///
+/// Plex n'émet pas d'événement dédié au re-match (« Corriger la correspondance ») : l'élément
+/// corrigé est re-rendu au prochain scan, ou immédiatement via `rustizarr process --id <ratingKey> --force`.
+#[derive(Debug, Clone)]
+pub struct WebhookRoutes {
+    actions: HashMap<String, WebhookAction>,
+}
+
+impl WebhookRoutes {
+    pub fn from_env() -> Self {
+        let spec = env::var("WEBHOOK_EVENTS").unwrap_or_else(|_| DEFAULT_EVENTS.to_string());
+        Self::parse(&spec)
+    }
+
+    pub fn parse(spec: &str) -> Self {
+        let mut actions = HashMap::new();
+
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let Some((event, action)) = entry.split_once('=') else {
+                warn!(entry, "{}", locale::t("log.webhook.invalid_entry"));
+                continue;
+            };
+            match action.parse() {
+                Ok(action) => {
+                    actions.insert(event.trim().to_string(), action);
+                }
+                Err(e) => warn!(entry, error = %e, "{}", locale::t("log.webhook.ignored_entry")),
+            }
+        }
+
+        Self { actions }
+    }
+
+    pub fn action_for(&self, event: &str) -> WebhookAction {
+        self.actions.get(event).copied().unwrap_or(WebhookAction::Ignore)
+    }
+
+    /// Label Prometheus de l'événement : événements configurés ou par défaut, "other" sinon
+    pub fn metric_event<'a>(&self, event: &'a str) -> &'a str {
+        let is_default = DEFAULT_EVENTS.split(',').any(|entry| entry.split('=').next() == Some(event));
+        if is_default || self.actions.contains_key(event) {
+            event
+        } else {
+            "other"
+        }
+    }
+
+    /// Événements actifs (pour le log de démarrage)
+    pub fn rendered_events(&self) -> Vec<&str> {
+        let mut events: Vec<&str> = self.actions.iter()
+            .filter(|(_, action)| **action == WebhookAction::Render)
+            .map(|(event, _)| event.as_str())
+            .collect();
+        events.sort();
+        events
+    }
+}
+
+// ==================== CIBLES ====================
+
+/// Élément Plex dont le poster doit être re-généré
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RenderTarget {
+    Movie(String),
+    Show(String),
+    Season(String),
+}
+
+impl RenderTarget {
+    pub fn rating_key(&self) -> &str {
+        match self {
+            RenderTarget::Movie(key) | RenderTarget::Show(key) | RenderTarget::Season(key) => key,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RenderTarget::Movie(_) => "movie",
+            RenderTarget::Show(_) => "show",
+            RenderTarget::Season(_) => "season",
+        }
+    }
+}
+
+impl fmt::Display for RenderTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind(), self.rating_key())
+    }
+}
+
+/// Éléments à re-rendre pour un webhook : un nouvel épisode re-génère sa saison et sa série
+pub fn targets_for(meta: &WebhookMetadata) -> Vec<RenderTarget> {
+    match meta.media_type.as_str() {
+        "movie" => vec![RenderTarget::Movie(meta.rating_key.clone())],
+        "show" => vec![RenderTarget::Show(meta.rating_key.clone())],
+        "season" => vec![RenderTarget::Season(meta.rating_key.clone())],
+        "episode" => {
+            let mut targets = Vec::new();
+            if let Some(season) = &meta.parent_rating_key {
+                targets.push(RenderTarget::Season(season.clone()));
+            }
+            if let Some(show) = &meta.grandparent_rating_key {
+                targets.push(RenderTarget::Show(show.clone()));
+            }
+            targets
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Rendus planifiés mais pas encore démarrés
+///
+/// Un pack d'épisodes déclenche un webhook par épisode : la saison et la série
+/// ne sont planifiées qu'une fois tant que le rendu n'a pas commencé.
+#[derive(Default)]
+pub struct PendingRenders {
+    targets: Mutex<HashSet<RenderTarget>>,
+}
+
+impl PendingRenders {
+    /// Réserve la cible, renvoie false si elle est déjà planifiée
+    pub fn claim(&self, target: &RenderTarget) -> bool {
+        self.targets.lock().unwrap().insert(target.clone())
+    }
+
+    pub fn release(&self, target: &RenderTarget) {
+        self.targets.lock().unwrap().remove(target);
+    }
+}
+
+// ==================== ATTENTE DE L'ANALYSE PLEX ====================
+
+/// Paramètres d'attente de l'analyse Plex avant le rendu
+#[derive(Debug, Clone)]
+pub struct PollSettings {
+    /// Intervalle entre deux lectures des métadonnées (WEBHOOK_POLL_INTERVAL, secondes)
+    pub interval: Duration,
+    /// Durée maximale d'une tentative (WEBHOOK_POLL_TIMEOUT, secondes)
+    pub timeout: Duration,
+    /// Délai avant une nouvelle tentative si l'analyse est incomplète (WEBHOOK_RETRY_DELAY, secondes)
+    pub retry_delay: Duration,
+    /// Nombre de tentatives avant abandon (WEBHOOK_MAX_ATTEMPTS)
+    pub max_attempts: u32,
+}
+
+impl PollSettings {
+    pub fn from_env() -> Self {
+        let secs = |name: &str, default: u64| {
+            Duration::from_secs(env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default))
+        };
+
+        Self {
+            interval: secs("WEBHOOK_POLL_INTERVAL", 5),
+            timeout: secs("WEBHOOK_POLL_TIMEOUT", 180),
+            retry_delay: secs("WEBHOOK_RETRY_DELAY", 600),
+            max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS").ok().and_then(|v| v.parse().ok()).unwrap_or(3).max(1),
+        }
+    }
+}
+
+pub enum PollResult<T> {
+    /// Métadonnées complètes
+    Ready(T),
+    /// Délai dépassé, analyse toujours en cours (ou élément inaccessible)
+    Incomplete,
+}
+
+/// Relit l'élément jusqu'à ce que `is_ready` soit vrai ou que le délai soit dépassé
+pub async fn poll_until_ready<T, F, Fut>(
+    settings: &PollSettings,
+    mut fetch: F,
+    is_ready: impl Fn(&T) -> bool,
+) -> PollResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let deadline = Instant::now() + settings.timeout;
+
+    loop {
+        tokio::time::sleep(settings.interval).await;
+
+        match fetch().await {
+            Ok(item) if is_ready(&item) => return PollResult::Ready(item),
+            Ok(_) => debug!("Analyse Plex en cours"),
+            Err(e) => debug!(error = %e, "Élément pas encore disponible"),
+        }
+
+        if Instant::now() >= deadline {
+            return PollResult::Incomplete;
+        }
+    }
+}
+
+// ==================== RADARR / SONARR / TAUTULLI ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalKind {
+    Movie,
+    Show,
+}
+
+/// Élément signalé par un outil externe, à retrouver dans Plex par ses guids
+#[derive(Debug, Clone)]
+pub struct ExternalItem {
+    pub kind: ExternalKind,
+    pub title: String,
+    /// Guids au format Plex ("tmdb://603", "imdb://tt0133093", "tvdb://81189")
+    pub guids: Vec<String>,
+    /// Saisons concernées (épisodes Sonarr / Tautulli)
+    pub seasons: Vec<u32>,
+}
+
+fn push_guid(guids: &mut Vec<String>, scheme: &str, id: Option<String>) {
+    if let Some(id) = id.map(|id| id.trim().to_string()).filter(|id| !id.is_empty() && id != "0") {
+        guids.push(format!("{}://{}", scheme, id));
+    }
+}
+
+/// Nom d'événement préfixé : Download + isUpgrade devient "upgrade"
+fn arr_event_name(source: &str, event_type: &str, is_upgrade: bool) -> String {
+    let event = if is_upgrade && event_type.eq_ignore_ascii_case("download") {
+        "upgrade".to_string()
+    } else {
+        event_type.to_lowercase()
+    };
+    format!("{}.{}", source, event)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RadarrPayload {
+    pub event_type: String,
+    pub movie: Option<RadarrMovie>,
+    #[serde(default)]
+    pub is_upgrade: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RadarrMovie {
+    pub title: String,
+    pub tmdb_id: Option<u64>,
+    pub imdb_id: Option<String>,
+}
+
+impl RadarrPayload {
+    pub fn event_name(&self) -> String {
+        arr_event_name("radarr", &self.event_type, self.is_upgrade)
+    }
+
+    pub fn item(&self) -> Option<ExternalItem> {
+        let movie = self.movie.as_ref()?;
+        let mut guids = Vec::new();
+        push_guid(&mut guids, "tmdb", movie.tmdb_id.map(|id| id.to_string()));
+        push_guid(&mut guids, "imdb", movie.imdb_id.clone());
+
+        Some(ExternalItem { kind: ExternalKind::Movie, title: movie.title.clone(), guids, seasons: Vec::new() })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SonarrPayload {
+    pub event_type: String,
+    pub series: Option<SonarrSeries>,
+    #[serde(default)]
+    pub episodes: Vec<SonarrEpisode>,
+    #[serde(default)]
+    pub is_upgrade: bool,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SonarrSeries {
+    pub title: String,
+    pub tvdb_id: Option<u64>,
+    pub tmdb_id: Option<u64>,
+    pub imdb_id: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SonarrEpisode {
+    pub season_number: u32,
+}
+
+impl SonarrPayload {
+    pub fn event_name(&self) -> String {
+        arr_event_name("sonarr", &self.event_type, self.is_upgrade)
+    }
+
+    pub fn item(&self) -> Option<ExternalItem> {
+        let series = self.series.as_ref()?;
+        let mut guids = Vec::new();
+        push_guid(&mut guids, "tvdb", series.tvdb_id.map(|id| id.to_string()));
+        push_guid(&mut guids, "tmdb", series.tmdb_id.map(|id| id.to_string()));
+        push_guid(&mut guids, "imdb", series.imdb_id.clone());
+
+        let mut seasons: Vec<u32> = self.episodes.iter().map(|e| e.season_number).collect();
+        seasons.sort_unstable();
+        seasons.dedup();
+
+        Some(ExternalItem { kind: ExternalKind::Show, title: series.title.clone(), guids, seasons })
+    }
+}
+
+/// Payload JSON du notifier "Webhook" de Tautulli (modèle défini par l'utilisateur)
+///
+/// Modèle conseillé pour le déclencheur "Recently Added" :
+/// `{"event": "created", "media_type": "{media_type}", "title": "{title}", "rating_key": "{rating_key}",
+///   "parent_rating_key": "{parent_rating_key}", "grandparent_rating_key": "{grandparent_rating_key}",
+///   "season_num": "{season_num}", "themoviedb_id": "{themoviedb_id}", "thetvdb_id": "{thetvdb_id}", "imdb_id": "{imdb_id}"}`
+#[derive(Deserialize, Debug)]
+pub struct TautulliPayload {
+    #[serde(default = "default_tautulli_event")]
+    pub event: String,
+    pub media_type: String,
+    #[serde(default)]
+    pub title: String,
+    pub rating_key: Option<String>,
+    pub parent_rating_key: Option<String>,
+    pub grandparent_rating_key: Option<String>,
+    pub season_num: Option<String>,
+    pub themoviedb_id: Option<String>,
+    pub thetvdb_id: Option<String>,
+    pub imdb_id: Option<String>,
+}
+
+fn default_tautulli_event() -> String {
+    "created".to_string()
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_ref().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+impl TautulliPayload {
+    pub fn event_name(&self) -> String {
+        format!("tautulli.{}", self.event.to_lowercase())
+    }
+
+    /// Tautulli connaît déjà les rating keys Plex : routage direct, sans recherche par guid
+    pub fn metadata(&self) -> Option<WebhookMetadata> {
+        Some(WebhookMetadata {
+            rating_key: non_empty(&self.rating_key)?,
+            media_type: self.media_type.clone(),
+            parent_rating_key: non_empty(&self.parent_rating_key),
+            grandparent_rating_key: non_empty(&self.grandparent_rating_key),
+        })
+    }
+
+    /// Repli par guid quand le modèle ne transmet pas les rating keys
+    pub fn item(&self) -> Option<ExternalItem> {
+        let kind = match self.media_type.as_str() {
+            "movie" => ExternalKind::Movie,
+            "show" | "season" | "episode" => ExternalKind::Show,
+            _ => return None,
+        };
+
+        let mut guids = Vec::new();
+        push_guid(&mut guids, "tmdb", non_empty(&self.themoviedb_id));
+        push_guid(&mut guids, "tvdb", non_empty(&self.thetvdb_id));
+        push_guid(&mut guids, "imdb", non_empty(&self.imdb_id));
+
+        let seasons = non_empty(&self.season_num).and_then(|s| s.parse().ok()).into_iter().collect();
+
+        Some(ExternalItem { kind, title: self.title.clone(), guids, seasons })
+    }
+}
+
+/// Retrouve les éléments Plex (et saisons) correspondant à un élément externe
+pub async fn resolve_targets(plex: &PlexClient, library_id: &str, item: &ExternalItem) -> anyhow::Result<Vec<RenderTarget>> {
+    let matches = match item.kind {
+        ExternalKind::Movie => plex.find_by_guids::<PlexMovie>(library_id, &item.guids).await?,
+        ExternalKind::Show => plex.find_by_guids::<PlexShow>(library_id, &item.guids).await?,
+    };
+    let mut targets = Vec::new();
+
+    for key in matches.into_iter().map(|m| m.rating_key) {
+        match item.kind {
+            ExternalKind::Movie => targets.push(RenderTarget::Movie(key)),
+            ExternalKind::Show => {
+                if !item.seasons.is_empty() {
+                    let seasons = plex.get_show_seasons(&key).await?;
+                    targets.extend(seasons.into_iter()
+                        .filter(|s| item.seasons.contains(&s.season_number))
+                        .map(|s| RenderTarget::Season(s.rating_key)));
+                }
+                targets.push(RenderTarget::Show(key));
+            }
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Vrai quand l'élément et toutes ses saisons existent dans Plex
+pub fn is_fully_resolved(item: &ExternalItem, targets: &[RenderTarget]) -> bool {
+    let seasons = targets.iter().filter(|t| matches!(t, RenderTarget::Season(_))).count();
+    seasons < targets.len() && seasons >= item.seasons.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_reads_event_actions() {
+        let routes = WebhookRoutes::parse(" library.new = render , media.scrobble=IGNORE,,sonarr.download=render ");
+        assert_eq!(routes.action_for("library.new"), WebhookAction::Render);
+        assert_eq!(routes.action_for("media.scrobble"), WebhookAction::Ignore);
+        assert_eq!(routes.rendered_events(), ["library.new", "sonarr.download"]);
+    }
+
+    #[test]
+    fn parse_skips_invalid_entries() {
+        let routes = WebhookRoutes::parse("library.new,media.rate=delete,radarr.download=render");
+        assert_eq!(routes.action_for("library.new"), WebhookAction::Ignore);
+        assert_eq!(routes.action_for("media.rate"), WebhookAction::Ignore);
+        assert_eq!(routes.rendered_events(), ["radarr.download"]);
+    }
+
+    #[test]
+    fn later_entries_override_earlier_ones() {
+        let routes = WebhookRoutes::parse("library.new=render,library.new=ignore");
+        assert_eq!(routes.action_for("library.new"), WebhookAction::Ignore);
+    }
+
+    #[test]
+    fn metric_event_buckets_unknown_events() {
+        let routes = WebhookRoutes::parse("library.on.deck=ignore");
+        assert_eq!(routes.metric_event("library.on.deck"), "library.on.deck");
+        assert_eq!(routes.metric_event("radarr.upgrade"), "radarr.upgrade");
+        assert_eq!(routes.metric_event("tautulli.{action}"), "other");
+        assert_eq!(routes.metric_event("library.new=render"), "other");
+    }
+
+    #[test]
+    fn defaults_render_new_and_rated_items() {
+        let routes = WebhookRoutes::parse(DEFAULT_EVENTS);
+        assert_eq!(routes.action_for("library.new"), WebhookAction::Render);
+        assert_eq!(routes.action_for("media.rate"), WebhookAction::Render);
+        assert_eq!(routes.action_for("media.play"), WebhookAction::Ignore);
+    }
+
+    // ==================== PAYLOADS ====================
+
+    #[test]
+    fn plex_episode_targets_season_and_show() {
+        let payload: PlexWebhookPayload = serde_json::from_value(json!({
+            "event": "library.new",
+            "Metadata": { "ratingKey": "12", "type": "episode", "parentRatingKey": "11", "grandparentRatingKey": "10" }
+        })).unwrap();
+        let meta = payload.metadata.unwrap();
+        assert_eq!(targets_for(&meta), [RenderTarget::Season("11".into()), RenderTarget::Show("10".into())]);
+    }
+
+    #[test]
+    fn targets_for_maps_media_types() {
+        let meta = |media_type: &str| WebhookMetadata {
+            rating_key: "5".into(),
+            media_type: media_type.into(),
+            parent_rating_key: None,
+            grandparent_rating_key: None,
+        };
+        assert_eq!(targets_for(&meta("movie")), [RenderTarget::Movie("5".into())]);
+        assert_eq!(targets_for(&meta("season")), [RenderTarget::Season("5".into())]);
+        assert!(targets_for(&meta("episode")).is_empty());
+        assert!(targets_for(&meta("track")).is_empty());
+    }
+
+    #[test]
+    fn radarr_download_upgrade_becomes_upgrade_event() {
+        let payload: RadarrPayload = serde_json::from_value(json!({
+            "eventType": "Download",
+            "isUpgrade": true,
+            "movie": { "id": 1, "title": "The Matrix", "tmdbId": 603, "imdbId": "tt0133093" }
+        })).unwrap();
+        assert_eq!(payload.event_name(), "radarr.upgrade");
+
+        let item = payload.item().unwrap();
+        assert_eq!(item.kind, ExternalKind::Movie);
+        assert_eq!(item.guids, ["tmdb://603", "imdb://tt0133093"]);
+        assert!(item.seasons.is_empty());
+    }
+
+    #[test]
+    fn radarr_test_event_has_no_item() {
+        let payload: RadarrPayload = serde_json::from_value(json!({ "eventType": "Test" })).unwrap();
+        assert_eq!(payload.event_name(), "radarr.test");
+        assert!(payload.item().is_none());
+    }
+
+    #[test]
+    fn sonarr_download_lists_seasons_and_skips_empty_ids() {
+        let payload: SonarrPayload = serde_json::from_value(json!({
+            "eventType": "Download",
+            "series": { "title": "Dark", "tvdbId": 334824, "tmdbId": 0, "imdbId": "" },
+            "episodes": [{ "seasonNumber": 2 }, { "seasonNumber": 1 }, { "seasonNumber": 2 }]
+        })).unwrap();
+        assert_eq!(payload.event_name(), "sonarr.download");
+
+        let item = payload.item().unwrap();
+        assert_eq!(item.kind, ExternalKind::Show);
+        assert_eq!(item.guids, ["tvdb://334824"]);
+        assert_eq!(item.seasons, [1, 2]);
+    }
+
+    #[test]
+    fn tautulli_routes_by_rating_key() {
+        let payload: TautulliPayload = serde_json::from_value(json!({
+            "media_type": "episode",
+            "title": "Dark",
+            "rating_key": "12",
+            "parent_rating_key": "11",
+            "grandparent_rating_key": "10"
+        })).unwrap();
+        assert_eq!(payload.event_name(), "tautulli.created");
+
+        let meta = payload.metadata().unwrap();
+        assert_eq!(targets_for(&meta), [RenderTarget::Season("11".into()), RenderTarget::Show("10".into())]);
+    }
+
+    #[test]
+    fn tautulli_falls_back_to_guids() {
+        let payload: TautulliPayload = serde_json::from_value(json!({
+            "event": "Created",
+            "media_type": "season",
+            "title": "Dark",
+            "rating_key": " ",
+            "season_num": "3",
+            "themoviedb_id": "70523",
+            "thetvdb_id": "",
+            "imdb_id": "tt5753856"
+        })).unwrap();
+        assert_eq!(payload.event_name(), "tautulli.created");
+        assert!(payload.metadata().is_none());
+
+        let item = payload.item().unwrap();
+        assert_eq!(item.kind, ExternalKind::Show);
+        assert_eq!(item.guids, ["tmdb://70523", "imdb://tt5753856"]);
+        assert_eq!(item.seasons, [3]);
+
+        let track: TautulliPayload = serde_json::from_value(json!({ "media_type": "track" })).unwrap();
+        assert!(track.item().is_none());
+    }
+
+    // ==================== RÉSOLUTION ====================
+
+    fn show_item(seasons: Vec<u32>) -> ExternalItem {
+        ExternalItem { kind: ExternalKind::Show, title: "Dark".into(), guids: vec!["tvdb://334824".into()], seasons }
+    }
+
+    #[test]
+    fn fully_resolved_needs_item_and_every_season() {
+        let season = |key: &str| RenderTarget::Season(key.into());
+        let show = RenderTarget::Show("10".into());
+
+        assert!(is_fully_resolved(&show_item(vec![1, 2]), &[season("101"), season("102"), show.clone()]));
+        assert!(!is_fully_resolved(&show_item(vec![1, 2]), &[season("101"), show.clone()]));
+        assert!(!is_fully_resolved(&show_item(vec![1]), &[season("101")]));
+        assert!(!is_fully_resolved(&show_item(Vec::new()), &[]));
+        assert!(is_fully_resolved(&show_item(Vec::new()), &[show]));
+    }
+
+    /// Plex factice : une série (tvdb://334824) et ses trois saisons
+    async fn fake_plex() -> String {
+        use axum::extract::{Path, Query};
+        use axum::routing::get;
+        use axum::Json;
+
+        async fn section(Query(query): Query<HashMap<String, String>>) -> Json<serde_json::Value> {
+            let metadata = match query.get("guid").map(String::as_str) {
+                Some("tvdb://334824") => json!([{ "ratingKey": "10", "title": "Dark", "type": "show", "Guid": [{ "id": "tvdb://334824" }] }]),
+                // Le filtre Plex peut renvoyer des éléments voisins : ils sont écartés par guid exact
+                Some("imdb://tt5753856") => json!([{ "ratingKey": "99", "title": "Other", "type": "show", "Guid": [{ "id": "imdb://tt0000001" }] }]),
+                _ => json!([]),
+            };
+            Json(json!({ "MediaContainer": { "totalSize": metadata.as_array().unwrap().len(), "Metadata": metadata } }))
+        }
+
+        async fn children(Path(key): Path<String>) -> Json<serde_json::Value> {
+            let seasons: Vec<_> = (1..=3).map(|n| json!({
+                "ratingKey": format!("{}{}", key, n),
+                "index": n,
+                "title": format!("Season {}", n),
+                "parentTitle": "Dark",
+                "parentRatingKey": key,
+            })).collect();
+            Json(json!({ "MediaContainer": { "Metadata": seasons } }))
+        }
+
+        let app = axum::Router::new()
+            .route("/library/sections/:id/all", get(section))
+            .route("/library/metadata/:key/children", get(children));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn resolve_targets_finds_show_and_requested_seasons() {
+        let plex = PlexClient::new(fake_plex().await, "token".into());
+        let mut item = show_item(vec![2, 3]);
+        item.guids.push("imdb://tt5753856".into());
+
+        let targets = resolve_targets(&plex, "2", &item).await.unwrap();
+        assert_eq!(targets, [
+            RenderTarget::Season("102".into()),
+            RenderTarget::Season("103".into()),
+            RenderTarget::Show("10".into()),
+        ]);
+        assert!(is_fully_resolved(&item, &targets));
+    }
+
+    #[tokio::test]
+    async fn resolve_targets_reports_missing_items() {
+        let plex = PlexClient::new(fake_plex().await, "token".into());
+        let item = ExternalItem { kind: ExternalKind::Movie, title: "Unknown".into(), guids: vec!["tmdb://1".into()], seasons: Vec::new() };
+
+        let targets = resolve_targets(&plex, "1", &item).await.unwrap();
+        assert!(targets.is_empty());
+        assert!(!is_fully_resolved(&item, &targets));
+    }
+}
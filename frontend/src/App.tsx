@@ -7,6 +7,7 @@ import { Card } from "./components/ui/card";
 import { Badge } from "./components/ui/badge";
 import { Button } from "./components/ui/button";
 import { Input } from "./components/ui/input";
+import { apiFetch, imageUrl, startScan, watchScan, type ScanSnapshot } from "./lib/api";
 
 // --- TYPES ---
 interface PlexLabel {
@@ -69,6 +70,7 @@ function App() {
   const [filterStatus, setFilterStatus] = useState<FilterType>('all');
   const [selectedMovie, setSelectedMovie] = useState<MovieDisplay | null>(null);
   const [comparisonMode, setComparisonMode] = useState<'side-by-side' | 'slider'>('side-by-side');
+  const [activeScan, setActiveScan] = useState<ScanSnapshot | null>(null);
 
   useEffect(() => {
     Promise.all([fetchLibrary(), fetchShows()]).finally(() => setLoading(false));
@@ -76,7 +78,7 @@ function App() {
 
   const fetchLibrary = async () => {
     try {
-      const response = await fetch('http://localhost:3030/api/library');
+      const response = await apiFetch('/api/library');
       const plexMovies: PlexMovie[] = await response.json();
 
       const formattedMovies: MovieDisplay[] = plexMovies.map(m => {
@@ -117,7 +119,7 @@ function App() {
 
   const fetchShows = async () => {
     try {
-      const response = await fetch('http://localhost:3030/api/shows');
+      const response = await apiFetch('/api/shows');
       const plexShows: PlexShow[] = await response.json();
 
       const formattedShows: ShowDisplay[] = plexShows.map(s => {
@@ -158,10 +160,10 @@ function App() {
     
     try {
       const endpoint = activeTab === 'movies' 
-        ? 'http://localhost:3030/api/library/refresh'
-        : 'http://localhost:3030/api/shows/refresh';
+        ? '/api/library/refresh'
+        : '/api/shows/refresh';
         
-      const response = await fetch(endpoint, { method: 'POST' });
+      const response = await apiFetch(endpoint, { method: 'POST' });
       const data = await response.json();
       
       if (data.success) {
@@ -186,18 +188,39 @@ function App() {
   };
 
   const triggerScan = async () => {
-    const endpoint = activeTab === 'movies'
-      ? 'http://localhost:3030/scan'
-      : 'http://localhost:3030/scan-shows';
-      
-    toast.promise(
-      fetch(endpoint),
-      {
-        loading: `Lancement du scan ${activeTab === 'movies' ? 'films' : 'séries'}...`,
-        success: '🚀 Scan lancé ! Surveillez le terminal serveur',
-        error: 'Erreur lors du lancement du scan'
-      }
-    );
+    const kind = activeTab;
+    const label = kind === 'movies' ? 'films' : 'séries';
+    const toastId = toast.loading(`Lancement du scan ${label}...`);
+
+    try {
+      const scan = await startScan({ kind });
+      setActiveScan(scan);
+      toast.loading(`Scan ${label} : ${scan.done}/${scan.total}`, { id: toastId });
+
+      watchScan(scan.id, {
+        onProgress: (snapshot) => {
+          setActiveScan(snapshot);
+          const current = snapshot.current ? ` · ${snapshot.current}` : '';
+          toast.loading(`Scan ${label} : ${snapshot.done}/${snapshot.total}${current}`, { id: toastId });
+        },
+        onDone: async (snapshot) => {
+          setActiveScan(null);
+          if (snapshot.status === 'completed') {
+            toast.success(`✅ Scan ${label} terminé : ${snapshot.rendered} traité(e)s, ${snapshot.skipped} ignoré(e)s, ${snapshot.failed} en échec`, { id: toastId });
+          } else {
+            toast.error(`Scan ${label} ${snapshot.status === 'cancelled' ? 'annulé' : 'en échec'}${snapshot.error ? ` : ${snapshot.error}` : ''}`, { id: toastId });
+          }
+          await (kind === 'movies' ? fetchLibrary() : fetchShows());
+        },
+        onError: () => {
+          setActiveScan(null);
+          toast.error(`Suivi du scan ${label} interrompu`, { id: toastId });
+        }
+      });
+    } catch (error) {
+      console.error('❌ Erreur scan:', error);
+      toast.error('Erreur lors du lancement du scan', { id: toastId });
+    }
   };
 
   // Filtrage combiné : recherche + statut
@@ -320,9 +343,11 @@ function App() {
              
              <Button 
                onClick={triggerScan} 
+               disabled={activeScan !== null}
                className="bg-white text-black hover:bg-zinc-200 font-medium text-xs h-9 shadow-lg shadow-white/5"
              >
-               <Zap size={14} className="mr-2 fill-black" /> Lancer Scan
+               <Zap size={14} className="mr-2 fill-black" />
+               {activeScan ? `Scan ${activeScan.done}/${activeScan.total}` : 'Lancer Scan'}
              </Button>
           </div>
         </div>
@@ -517,7 +542,7 @@ const MovieCard = ({ movie, mode, onClick }: { movie: MovieDisplay, mode: 'grid'
         ${isGrid ? 'aspect-[2/3] w-full' : 'h-full aspect-[2/3] rounded-md border-b-0'}`}>
         
         <img 
-            src={`http://localhost:3030/api/image/${movie.id}`} 
+            src={imageUrl(movie.id)} 
             alt={movie.title}
             className="w-full h-full object-cover transition-transform duration-500 group-hover:scale-110"
             loading="lazy" 
@@ -615,7 +640,7 @@ const ShowCard = ({ show, mode }: { show: ShowDisplay, mode: 'grid' | 'list' })
         ${isGrid ? 'aspect-[2/3] w-full' : 'h-full aspect-[2/3] rounded-md border-b-0'}`}>
         
         <img 
-            src={`http://localhost:3030/api/image/${show.id}`} 
+            src={imageUrl(show.id)} 
             alt={show.title}
             className="w-full h-full object-cover transition-transform duration-500 group-hover:scale-110"
             loading="lazy" 
@@ -685,8 +710,8 @@ const ComparisonModal = ({
     setSliderPosition(Math.max(0, Math.min(100, percentage)));
   };
 
-  const originalPosterUrl = `http://localhost:3030/api/image/${movie.id}`;
-  const processedPosterUrl = `http://localhost:3030/api/image/${movie.id}`;
+  const originalPosterUrl = imageUrl(movie.id);
+  const processedPosterUrl = imageUrl(movie.id);
 
   return (
     <AnimatePresence>
@@ -0,0 +1,89 @@
+// Accès au backend : URL de base et clé API lues depuis l'environnement Vite.
+// Sans VITE_API_URL, les URLs restent relatives (proxy Vite en dev, même origine en prod).
+const API_BASE = (import.meta.env.VITE_API_URL ?? '').replace(/\/+$/, '');
+const API_KEY: string | undefined = import.meta.env.VITE_API_KEY || undefined;
+
+export type ScanKind = 'movies' | 'shows' | 'seasons';
+export type ScanStatus = 'pending' | 'running' | 'completed' | 'cancelled' | 'failed';
+
+export interface ScanSnapshot {
+  id: string;
+  kind: ScanKind;
+  status: ScanStatus;
+  total: number;
+  done: number;
+  rendered: number;
+  skipped: number;
+  failed: number;
+  by_outcome: Record<string, number>;
+  current: string | null;
+  elapsed_secs: number;
+  eta_secs: number | null;
+  error: string | null;
+}
+
+export interface StartScanRequest {
+  kind: ScanKind;
+  force?: boolean;
+  library_id?: string;
+  seasons?: boolean;
+  show_id?: string;
+}
+
+export function apiUrl(path: string): string {
+  return `${API_BASE}${path}`;
+}
+
+// URL utilisable là où aucun header ne peut être envoyé (<img>, EventSource) : clé en paramètre
+export function authenticatedUrl(path: string): string {
+  if (!API_KEY) return apiUrl(path);
+  const separator = path.includes('?') ? '&' : '?';
+  return apiUrl(`${path}${separator}api_key=${encodeURIComponent(API_KEY)}`);
+}
+
+export function apiFetch(path: string, init: RequestInit = {}): Promise<Response> {
+  const headers = new Headers(init.headers);
+  if (API_KEY) headers.set('Authorization', `Bearer ${API_KEY}`);
+  return fetch(apiUrl(path), { ...init, headers });
+}
+
+export function imageUrl(id: string): string {
+  return authenticatedUrl(`/api/image/${id}`);
+}
+
+// Lance un scan ; un 409 renvoie le scan déjà en cours du même type
+export async function startScan(request: StartScanRequest): Promise<ScanSnapshot> {
+  const response = await apiFetch('/api/scans', {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify(request),
+  });
+  if (!response.ok && response.status !== 409) {
+    throw new Error((await response.text()) || `HTTP ${response.status}`);
+  }
+  return response.json();
+}
+
+interface ScanEventHandlers {
+  onProgress: (snapshot: ScanSnapshot) => void;
+  onDone: (snapshot: ScanSnapshot) => void;
+  onError: () => void;
+}
+
+// Suit la progression d'un scan en SSE ; renvoie la fonction de fermeture du flux
+export function watchScan(id: string, handlers: ScanEventHandlers): () => void {
+  const source = new EventSource(authenticatedUrl(`/api/scans/${id}/events`));
+  source.addEventListener('progress', (event) => {
+    handlers.onProgress(JSON.parse((event as MessageEvent).data));
+  });
+  source.addEventListener('done', (event) => {
+    source.close();
+    handlers.onDone(JSON.parse((event as MessageEvent).data));
+  });
+  source.onerror = () => {
+    // Flux coupé avant l'événement final : on abandonne plutôt que de reconnecter en boucle
+    source.close();
+    handlers.onError();
+  };
+  return () => source.close();
+}